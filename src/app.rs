@@ -1,11 +1,26 @@
 use makepad_widgets::*;
+use makepad_widgets::makepad_micro_serde::*;
 use makepad_map::GeoMapViewWidgetExt;
-use std::sync::{LazyLock, Mutex};
-use std::collections::HashMap;
-
-// Global storage for restaurant images by business ID
-static RESTAURANT_IMAGES: LazyLock<Mutex<HashMap<String, Vec<u8>>>> =
-    LazyLock::new(|| Mutex::new(HashMap::new()));
+use crate::cluster::{self, ClusterAction, ClusterIndex, ClusterMarker, MapPoint};
+use crate::deeplink::{self, DeepLink};
+use crate::hours::{self, OpenInterval, OpenState, Schedule, SpecialHours};
+use crate::geo::{self, Bounds, METERS_PER_MILE};
+use crate::geocode::{Gazetteer, Geocoder, Place};
+use crate::image_cache::{self, DiskImageCache};
+use crate::image_format::ImageLoadError;
+use crate::image_pipeline::{self, ImageDecodeAction, ImageLoadState, ImageSize};
+use crate::location::{self, LocationFix, LocationProvider};
+use crate::navigation::{Navigator, Page};
+use crate::provider::{self, AreaRequest, BusinessProvider, ProviderResponse, SearchRequest};
+use crate::phone;
+use crate::platform::{self, PlatformAction, PlatformActions};
+use crate::routing::{ExternalMap, GraphRouter, Route, Router, StraightLineRouter, TravelMode};
+use crate::reviews::{self, NewReview, Review, ReviewDraft, ReviewPage, ReviewSort, ReviewsRequest};
+use crate::search::{self, DistanceLimit, SearchFilters, SortMode};
+use crate::storage;
+use std::sync::Arc;
+use std::time::Instant;
+use std::collections::{HashMap, HashSet};
 
 live_design! {
     use link::theme::*;
@@ -100,7 +115,9 @@ live_design! {
                 let outline_edge = star_radius + 0.025;
                 let outline_band = smoothstep(outline_edge + aa, outline_edge - aa, r);
 
-                let is_filled = step(star_idx + 0.5, self.rating);
+                // Fraction of this star that is filled; half ratings fill the left half
+                let fill_amount = clamp(self.rating - star_idx, 0.0, 1.0);
+                let is_filled = step(local_x, fill_amount);
                 let col = mix(empty, filled, is_filled);
 
                 // Blend: outline ring behind, star fill on top
@@ -112,6 +129,162 @@ live_design! {
         }
     }
 
+    // Toggleable pill chip (filters, sort options); `selected` is driven from code
+    PillChip = <RoundedView> {
+        width: Fit, height: 32.0
+        padding: { left: 14.0, right: 14.0 }
+        align: { y: 0.5 }
+        cursor: Hand
+        show_bg: true
+        draw_bg: {
+            instance hover: 0.0
+            instance selected: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                let r = self.rect_size.y * 0.5;
+                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, r - 1.0);
+                let fill = mix(mix(#fff, #f5f5f5, self.hover), #d32323, self.selected);
+                sdf.fill_keep(fill);
+                sdf.stroke(mix(#ccc, #d32323, self.selected), 1.0);
+                return sdf.result;
+            }
+        }
+
+        label = <Label> {
+            width: Fit, height: Fit
+            text: ""
+            draw_text: { text_style: { font_size: 13.0 }, color: #333 }
+        }
+    }
+
+    // Reviews rendered from a template, one per review (the detail screen scrolls, so no virtualization)
+    ReviewList = {{ReviewList}} {
+        width: Fill, height: Fit
+        flow: Down
+        spacing: 20.0
+
+        item: <View> {
+            width: Fill, height: Fit
+            flow: Down
+            spacing: 6.0
+
+            <View> {
+                width: Fill, height: Fit
+                flow: Right
+                spacing: 10.0
+                align: { y: 0.5 }
+
+                avatar = <RoundedView> {
+                    width: 36.0, height: 36.0
+                    align: { x: 0.5, y: 0.5 }
+                    show_bg: true
+                    draw_bg: { color: #d32323, border_radius: 18.0 }
+                    initial = <Label> {
+                        width: Fit, height: Fit
+                        draw_text: { text_style: { font_size: 14.0 }, color: #fff }
+                        text: ""
+                    }
+                }
+
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Down
+                    spacing: 2.0
+                    author = <Label> {
+                        width: Fill, height: Fit
+                        draw_text: { text_style: { font_size: 14.0 }, color: #1a1a1a }
+                        text: ""
+                    }
+                    date = <Label> {
+                        width: Fill, height: Fit
+                        draw_text: { text_style: { font_size: 12.0 }, color: #999 }
+                        text: ""
+                    }
+                }
+            }
+
+            stars = <StarRating> {
+                width: 90.0, height: 18.0
+            }
+
+            text = <Label> {
+                width: Fill, height: Fit
+                draw_text: { text_style: { font_size: 14.0, line_spacing: 1.4 }, color: #333 }
+                text: ""
+            }
+
+            read_more = <View> {
+                width: Fit, height: Fit
+                cursor: Hand
+                read_more_label = <Label> {
+                    width: Fit, height: Fit
+                    draw_text: { text_style: { font_size: 13.0 }, color: #0073bb }
+                    text: "Read more"
+                }
+            }
+
+            photos_label = <Label> {
+                width: Fit, height: Fit
+                draw_text: { text_style: { font_size: 12.0 }, color: #666 }
+                text: ""
+            }
+        }
+    }
+
+    // Wrapping row of toggleable category chips, one per category in the results
+    CategoryChips = {{CategoryChips}} {
+        width: Fill, height: Fit
+        flow: RightWrap
+        spacing: 8.0
+        line_spacing: 8.0
+
+        chip: <PillChip> {}
+    }
+
+    // One suggestion in the location picker
+    LocationRow = <View> {
+        width: Fill, height: 36.0
+        padding: { left: 8.0 }
+        align: { y: 0.5 }
+        cursor: Hand
+        visible: false
+        name = <Label> {
+            width: Fill, height: Fit
+            draw_text: { text_style: { font_size: 14.0 }, color: #1a1a1a }
+            text: ""
+        }
+    }
+
+    // Type a neighborhood and pick where to search; suggestions come from the app's geocoder
+    LocationPicker = {{LocationPicker}} {
+        width: Fill, height: Fit
+        flow: Down
+        spacing: 2.0
+
+        location_input = <TextInput> {
+            width: Fill, height: 40.0
+            empty_text: "Neighborhood, e.g. Mission or North Beach"
+        }
+
+        current_row = <LocationRow> {
+            visible: true
+            name = { text: "Current Location", draw_text: { color: #0073bb } }
+        }
+        suggestion_0 = <LocationRow> {}
+        suggestion_1 = <LocationRow> {}
+        suggestion_2 = <LocationRow> {}
+        suggestion_3 = <LocationRow> {}
+        suggestion_4 = <LocationRow> {}
+
+        no_match = <Label> {
+            width: Fill, height: Fit
+            padding: { left: 8.0 }
+            visible: false
+            draw_text: { text_style: { font_size: 13.0 }, color: #999 }
+            text: "No matching neighborhood"
+        }
+    }
+
     // Search Bar widget
     SearchBar = {{SearchBar}} {
         width: Fill
@@ -134,17 +307,56 @@ live_design! {
         padding: { left: 12.0, right: 12.0 }
         spacing: 8.0
 
-        <Label> {
-            width: Fit, height: Fit
-            text: "Search"
-            draw_text: { text_style: { font_size: 14.0 }, color: #999 }
+        // Search icon
+        <View> {
+            width: 20.0, height: 20.0
+            show_bg: true
+            draw_bg: {
+                instance icon_color: #999
+                fn pixel(self) -> vec4 {
+                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                    let c = self.rect_size * 0.5;
+                    sdf.circle(c.x - 1.0, c.y - 1.0, 6.0);
+                    sdf.stroke(self.icon_color, 1.5);
+                    sdf.move_to(c.x + 3.0, c.y + 3.0);
+                    sdf.line_to(c.x + 8.0, c.y + 8.0);
+                    sdf.stroke(self.icon_color, 1.5);
+                    return sdf.result;
+                }
+            }
         }
+
         input = <TextInput> {
             width: Fill, height: Fit
             empty_text: "Restaurants, bars, cafes..."
             draw_bg: { color: #0000 }
             draw_text: { text_style: { font_size: 14.0 }, color: #333 }
         }
+
+        // Clear button (shown while the query is non-empty)
+        clear_button = <View> {
+            width: 20.0, height: 20.0
+            visible: false
+            cursor: Hand
+            show_bg: true
+            draw_bg: {
+                instance hover: 0.0
+                fn pixel(self) -> vec4 {
+                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                    let c = self.rect_size * 0.5;
+                    sdf.circle(c.x, c.y, c.x);
+                    sdf.fill(mix(#bbb, #999, self.hover));
+                    // X mark
+                    sdf.move_to(c.x - 4.0, c.y - 4.0);
+                    sdf.line_to(c.x + 4.0, c.y + 4.0);
+                    sdf.stroke(#fff, 1.5);
+                    sdf.move_to(c.x + 4.0, c.y - 4.0);
+                    sdf.line_to(c.x - 4.0, c.y + 4.0);
+                    sdf.stroke(#fff, 1.5);
+                    return sdf.result;
+                }
+            }
+        }
     }
 
     // Business Card widget with Yelp-style layout
@@ -210,6 +422,46 @@ live_design! {
                     instance opacity: 1.0
                 }
             }
+
+            // Failed state with tap-to-retry
+            photo_error = <View> {
+                width: Fill, height: Fill
+                visible: false
+                cursor: Hand
+                flow: Down
+                spacing: 6.0
+                align: { x: 0.5, y: 0.5 }
+
+                // Broken image icon
+                <View> {
+                    width: 28.0, height: 24.0
+                    show_bg: true
+                    draw_bg: {
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 3.0);
+                            sdf.stroke(#aaa, 1.5);
+                            // Mountain
+                            sdf.move_to(4.0, 19.0);
+                            sdf.line_to(11.0, 11.0);
+                            sdf.line_to(16.0, 16.0);
+                            sdf.line_to(19.0, 13.0);
+                            sdf.line_to(24.0, 19.0);
+                            sdf.stroke(#aaa, 1.5);
+                            // Slash
+                            sdf.move_to(3.0, 3.0);
+                            sdf.line_to(25.0, 21.0);
+                            sdf.stroke(#aaa, 1.5);
+                            return sdf.result;
+                        }
+                    }
+                }
+                error_label = <Label> {
+                    width: Fit, height: Fit
+                    draw_text: { text_style: { font_size: 10.0 }, color: #999 }
+                    text: "Tap to retry"
+                }
+            }
         }
 
         info = <View> {
@@ -223,13 +475,12 @@ live_design! {
                 flow: Right
                 align: { y: 0.5 }
 
-                name_label = <Label> {
+                // Html so matched query ranges can be rendered in bold
+                name_label = <Html> {
                     width: Fill, height: Fit
-                    draw_text: {
-                        text_style: { font_size: 17.0 }
-                        color: #1a1a1a
-                    }
-                    text: "Business Name"
+                    font_size: 17.0
+                    font_color: #1a1a1a
+                    body: "Business Name"
                 }
                 distance_label = <Label> {
                     width: Fit, height: Fit
@@ -470,6 +721,8 @@ live_design! {
 
         header = <View> {
             width: Fill, height: Fit
+            flow: Down
+            spacing: 10.0
             padding: { top: 12.0, bottom: 12.0, left: 16.0, right: 16.0 }
             show_bg: true
             draw_bg: { color: #fff }
@@ -489,38 +742,85 @@ live_design! {
                 }
                 flow: Right
                 align: { y: 0.5 }
-                padding: { left: 12.0, right: 12.0 }
+                padding: { left: 0.0, right: 12.0 }
                 spacing: 8.0
 
-                // Search icon
-                <View> {
-                    width: 20.0, height: 20.0
-                    show_bg: true
-                    draw_bg: {
-                        instance icon_color: #999
-                        fn pixel(self) -> vec4 {
-                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
-                            let c = self.rect_size * 0.5;
-                            sdf.circle(c.x - 1.0, c.y - 1.0, 6.0);
-                            sdf.stroke(self.icon_color, 1.5);
-                            sdf.move_to(c.x + 3.0, c.y + 3.0);
-                            sdf.line_to(c.x + 8.0, c.y + 8.0);
-                            sdf.stroke(self.icon_color, 1.5);
-                            return sdf.result;
+                // Live query input with debounce and clear button
+                query_bar = <SearchBar> {
+                    width: Fill, height: Fill
+                    show_bg: false
+                }
+
+                <View> { width: 1.0, height: 20.0, show_bg: true, draw_bg: { color: #ccc } }
+                // Opens the location picker below
+                location_button = <View> {
+                    width: Fit, height: Fill
+                    align: { y: 0.5 }
+                    cursor: Hand
+                    location_label = <Label> {
+                        width: Fit, height: Fit
+                        text: "Current Location"
+                        draw_text: { text_style: { font_size: 15.0 }, color: #999 }
+                    }
+                }
+            }
+
+            location_picker = <LocationPicker> { visible: false }
+
+            filter_row = <View> {
+                width: Fill, height: Fit
+                flow: Right
+                spacing: 8.0
+
+                // Opens the full filter sheet; the badge counts active filter groups
+                filters_chip = <PillChip> {
+                    spacing: 6.0
+                    label = { text: "Filters" }
+                    badge = <RoundedView> {
+                        width: 18.0, height: 18.0
+                        visible: false
+                        align: { x: 0.5, y: 0.5 }
+                        show_bg: true
+                        draw_bg: { color: (YELP_RED), border_radius: 9.0 }
+                        badge_label = <Label> {
+                            width: Fit, height: Fit
+                            draw_text: { text_style: { font_size: 10.0 }, color: #fff }
+                            text: ""
                         }
                     }
                 }
 
+                // Toggle: only list businesses open right now
+                open_now_chip = <PillChip> {
+                    label = { text: "Open Now" }
+                }
+
+                rating_chip = <PillChip> {
+                    label = { text: "4+ Stars" }
+                }
+
+                price_1 = <PillChip> { label = { text: "$" } }
+                price_2 = <PillChip> { label = { text: "$$" } }
+                price_3 = <PillChip> { label = { text: "$$$" } }
+                price_4 = <PillChip> { label = { text: "$$$$" } }
+            }
+
+            sort_row = <View> {
+                width: Fill, height: Fit
+                flow: Right
+                spacing: 6.0
+                align: { y: 0.5 }
+
                 <Label> {
                     width: Fit, height: Fit
-                    text: "Restaurants"
-                    draw_text: { text_style: { font_size: 15.0 }, color: #1a1a1a }
+                    text: "Sort by"
+                    draw_text: { text_style: { font_size: 13.0 }, color: #666 }
                 }
-                <View> { width: 1.0, height: 20.0, show_bg: true, draw_bg: { color: #ccc } }
-                <Label> {
+                // Same order as `SortMode::ALL`
+                sort_dropdown = <DropDown> {
                     width: Fit, height: Fit
-                    text: "Current Location"
-                    draw_text: { text_style: { font_size: 15.0 }, color: #999 }
+                    labels: ["Best Match", "Highest Rated", "Most Reviewed", "Nearest", "Price: Low to High", "Price: High to Low"]
+                    draw_text: { text_style: { font_size: 13.0 } }
                 }
             }
         }
@@ -528,13 +828,14 @@ live_design! {
         // Divider
         <View> { width: Fill, height: 1.0, show_bg: true, draw_bg: { color: #e0e0e0 } }
 
-        // Pull to refresh indicator (shown when refreshing)
-        refresh_indicator = <View> {
-            width: Fill, height: 50.0
+        // Pull-to-refresh header; its height follows the drag, then holds while refreshing
+        pull_header = <View> {
+            width: Fill, height: 0.0
+            visible: false
+            clip_y: true
             align: { x: 0.5, y: 0.5 }
             show_bg: true
             draw_bg: { color: #f5f5f5 }
-            visible: false
 
             <View> {
                 width: Fit, height: Fit
@@ -542,11 +843,33 @@ live_design! {
                 spacing: 8.0
                 align: { y: 0.5 }
 
-                refresh_spinner = <LoadingSpinner> {
+                // Points down while pulling, flips up once releasing will refresh
+                pull_arrow = <View> {
+                    width: 20.0, height: 20.0
+                    show_bg: true
+                    draw_bg: {
+                        instance flip: 0.0
+                        fn pixel(self) -> vec4 {
+                            let pos = vec2(self.pos.x, mix(self.pos.y, 1.0 - self.pos.y, self.flip));
+                            let sdf = Sdf2d::viewport(pos * self.rect_size);
+                            let c = self.rect_size * 0.5;
+                            sdf.move_to(c.x, c.y - 7.0);
+                            sdf.line_to(c.x, c.y + 7.0);
+                            sdf.stroke(#999, 1.5);
+                            sdf.move_to(c.x - 5.0, c.y + 2.0);
+                            sdf.line_to(c.x, c.y + 7.0);
+                            sdf.line_to(c.x + 5.0, c.y + 2.0);
+                            sdf.stroke(#999, 1.5);
+                            return sdf.result;
+                        }
+                    }
+                }
+                pull_spinner = <LoadingSpinner> {
                     width: 24.0, height: 24.0
+                    visible: false
                 }
-                <Label> {
-                    text: "Refreshing..."
+                pull_label = <Label> {
+                    text: "Pull to refresh"
                     draw_text: { text_style: { font_size: 13.0 }, color: #666 }
                 }
             }
@@ -601,46 +924,49 @@ live_design! {
             width: Fill, height: Fill
             drag_scrolling: true
 
-            // Pull-to-refresh trigger (first item)
-            pull_refresh = <View> {
+            business_card = <BusinessCard> {}
+
+            // Footer cells after the last card: next page loading, page error, end of results
+            footer_loading = <View> {
                 width: Fill, height: 60.0
                 align: { x: 0.5, y: 0.5 }
-                cursor: Hand
+                flow: Right
+                spacing: 8.0
 
-                <View> {
-                    width: Fit, height: Fit
-                    flow: Right
-                    spacing: 8.0
-                    align: { y: 0.5 }
+                <LoadingSpinner> { width: 20.0, height: 20.0 }
+                <Label> {
+                    text: "Loading more..."
+                    draw_text: { text_style: { font_size: 13.0 }, color: #999 }
+                }
+            }
 
-                    // Refresh arrow icon
-                    <View> {
-                        width: 20.0, height: 20.0
-                        show_bg: true
-                        draw_bg: {
-                            fn pixel(self) -> vec4 {
-                                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
-                                let c = self.rect_size * 0.5;
-                                // Circular arrow
-                                sdf.circle(c.x, c.y, 7.0);
-                                sdf.stroke(#999, 1.5);
-                                // Arrow head
-                                sdf.move_to(c.x + 5.0, c.y - 3.0);
-                                sdf.line_to(c.x + 7.0, c.y);
-                                sdf.line_to(c.x + 4.0, c.y);
-                                sdf.stroke(#999, 1.5);
-                                return sdf.result;
-                            }
-                        }
-                    }
-                    <Label> {
-                        text: "Pull to refresh"
-                        draw_text: { text_style: { font_size: 13.0 }, color: #999 }
-                    }
+            footer_error = <View> {
+                width: Fill, height: Fit
+                padding: 16.0
+                flow: Down
+                spacing: 4.0
+                align: { x: 0.5 }
+                cursor: Hand
+
+                footer_error_label = <Label> {
+                    text: ""
+                    draw_text: { text_style: { font_size: 13.0 }, color: #666 }
+                }
+                <Label> {
+                    text: "Tap to retry"
+                    draw_text: { text_style: { font_size: 13.0 }, color: #0073bb }
                 }
             }
 
-            business_card = <BusinessCard> {}
+            footer_end = <View> {
+                width: Fill, height: 60.0
+                align: { x: 0.5, y: 0.5 }
+
+                <Label> {
+                    text: "No more results"
+                    draw_text: { text_style: { font_size: 13.0 }, color: #999 }
+                }
+            }
         }
     }
 
@@ -727,41 +1053,219 @@ live_design! {
                     }
                 }
             }
-        }
-    }
 
-    // Business Detail Screen
-    BusinessDetailScreen = {{BusinessDetailScreen}} {
-        width: Fill
-        height: Fill
-        flow: Down
-        show_bg: true
-        draw_bg: {
-            instance opacity: 1.0
-            fn pixel(self) -> vec4 {
-                return vec4(1.0, 1.0, 1.0, self.opacity);
-            }
-        }
-        animator: {
-            fade = {
-                default: show
-                hide = {
-                    from: { all: Forward { duration: 0.3 } }
-                    apply: { draw_bg: { opacity: 0.0 } }
-                }
-                show = {
-                    from: { all: Forward { duration: 0.3 } }
-                    apply: { draw_bg: { opacity: 1.0 } }
+            // Shown once the user pans or zooms away from the searched area
+            <View> {
+                width: Fill, height: Fit
+                align: { x: 0.5 }
+                padding: { top: 12.0 }
+
+                search_area_button = <RoundedView> {
+                    width: Fit, height: 36.0
+                    visible: false
+                    padding: { left: 16.0, right: 16.0 }
+                    align: { y: 0.5 }
+                    cursor: Hand
+                    show_bg: true
+                    draw_bg: {
+                        color: #fff
+                        border_radius: 18.0
+                        border_size: 1.0
+                        border_color: #ccc
+                    }
+                    <Label> {
+                        text: "Search this area"
+                        draw_text: { text_style: { font_size: 14.0 }, color: #1a1a1a }
+                    }
                 }
             }
         }
 
-        // Header with back button
-        header = <View> {
-            width: Fill, height: 56.0
-            padding: { left: 8.0, right: 16.0 }
-            show_bg: true
-            draw_bg: { color: #fff }
+        // Preview of the selected marker; swipe sideways for the businesses nearest to it
+        <View> {
+            width: Fill, height: Fill
+            flow: Down
+            align: { y: 1.0 }
+            padding: { left: 12.0, right: 12.0, bottom: 12.0 }
+
+            preview_sheet = <RoundedView> {
+                width: Fill, height: Fit
+                visible: false
+                flow: Down
+                padding: { bottom: 8.0 }
+                show_bg: true
+                draw_bg: {
+                    color: #fff
+                    border_radius: 12.0
+                    border_size: 1.0
+                    border_color: #ddd
+                }
+
+                preview_card = <BusinessCard> {
+                    draw_bg: { color: #0000 }
+                }
+
+                <View> {
+                    width: Fill, height: Fit
+                    align: { x: 0.5 }
+                    preview_position = <Label> {
+                        text: ""
+                        draw_text: { text_style: { font_size: 12.0 }, color: #888 }
+                    }
+                }
+            }
+        }
+    }
+
+    // Route polyline drawn over a map, one quad per segment
+    RouteLine = {{RouteLine}} {
+        width: Fill, height: Fill
+        draw_segment: {
+            instance seg_start: vec2
+            instance seg_end: vec2
+            instance line_width: 5.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.move_to(self.seg_start.x, self.seg_start.y);
+                sdf.line_to(self.seg_end.x, self.seg_end.y);
+                sdf.stroke(#0073bb, self.line_width);
+                return sdf.result;
+            }
+        }
+    }
+
+    // Route from the user's location to a business with distance and ETA per travel mode
+    RouteScreen = {{RouteScreen}} {
+        width: Fill
+        height: Fill
+        flow: Overlay
+        show_bg: true
+        draw_bg: { color: #fff }
+
+        route_map = <GeoMapView> {
+            width: Fill, height: Fill
+            center_lat: 37.7749
+            center_lng: -122.4194
+            zoom: 14.0
+        }
+
+        route_line = <RouteLine> {}
+
+        <View> {
+            width: Fill, height: Fill
+            flow: Down
+
+            header = <View> {
+                width: Fill, height: 56.0
+                padding: { left: 8.0, right: 16.0 }
+                show_bg: true
+                draw_bg: { color: #fff }
+                flow: Right
+                align: { y: 0.5 }
+
+                back_button = <Button> {
+                    width: 44.0, height: 44.0
+                    text: "<"
+                    draw_text: {
+                        color: (YELP_RED)
+                        text_style: { font_size: 20.0 }
+                    }
+                    draw_bg: { color: #0000 }
+                }
+
+                <Label> {
+                    width: Fill, height: Fit
+                    draw_text: {
+                        text_style: { font_size: 18.0 }
+                        color: #1a1a1a
+                    }
+                    text: "Directions"
+                }
+            }
+
+            <View> { width: Fill, height: Fill }
+
+            <View> {
+                width: Fill, height: Fit
+                flow: Down
+                spacing: 12.0
+                padding: 16.0
+                show_bg: true
+                draw_bg: { color: #fff }
+
+                route_business = <Label> {
+                    width: Fill, height: Fit
+                    draw_text: { text_style: { font_size: 16.0 }, color: #1a1a1a }
+                    text: ""
+                }
+
+                route_summary = <Label> {
+                    width: Fill, height: Fit
+                    draw_text: { text_style: { font_size: 14.0 }, color: #666 }
+                    text: ""
+                }
+
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    spacing: 8.0
+
+                    walk_chip = <PillChip> { label = { text: "Walk" } }
+                    drive_chip = <PillChip> { label = { text: "Drive" } }
+                    transit_chip = <PillChip> { label = { text: "Transit" } }
+                }
+
+                // Hand the trip to a navigation app
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    spacing: 8.0
+                    align: { y: 0.5 }
+
+                    <Label> {
+                        draw_text: { text_style: { font_size: 13.0 }, color: #666 }
+                        text: "Open in…"
+                    }
+                    open_geo = <PillChip> { label = { text: "Maps app" } }
+                    open_google = <PillChip> { label = { text: "Google Maps" } }
+                    open_apple = <PillChip> { label = { text: "Apple Maps" } }
+                }
+            }
+        }
+    }
+
+    // Business Detail Screen
+    BusinessDetailScreen = {{BusinessDetailScreen}} {
+        width: Fill
+        height: Fill
+        flow: Down
+        show_bg: true
+        draw_bg: {
+            instance opacity: 1.0
+            fn pixel(self) -> vec4 {
+                return vec4(1.0, 1.0, 1.0, self.opacity);
+            }
+        }
+        animator: {
+            fade = {
+                default: show
+                hide = {
+                    from: { all: Forward { duration: 0.3 } }
+                    apply: { draw_bg: { opacity: 0.0 } }
+                }
+                show = {
+                    from: { all: Forward { duration: 0.3 } }
+                    apply: { draw_bg: { opacity: 1.0 } }
+                }
+            }
+        }
+
+        // Header with back button
+        header = <View> {
+            width: Fill, height: 56.0
+            padding: { left: 8.0, right: 16.0 }
+            show_bg: true
+            draw_bg: { color: #fff }
             flow: Right
             align: { y: 0.5 }
 
@@ -927,6 +1431,8 @@ live_design! {
                 // Action button with pill shape and hover states
                 action_buttons = <View> {
                     width: Fill, height: Fit
+                    flow: Right
+                    spacing: 12.0
                     padding: { left: 16.0, right: 16.0 }
 
                     directions_button = <RoundedView> {
@@ -962,6 +1468,118 @@ live_design! {
                             draw_text: { color: #fff, text_style: { font_size: 16.0 } }
                         }
                     }
+
+                    // Outlined so Directions stays the primary action; hidden without a dialable number
+                    call_button = <RoundedView> {
+                        width: Fill, height: 50.0
+                        align: { x: 0.5, y: 0.5 }
+                        cursor: Hand
+                        show_bg: true
+                        draw_bg: {
+                            instance hover: 0.0
+                            instance pressed: 0.0
+                            fn pixel(self) -> vec4 {
+                                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                let r = self.rect_size.y * 0.5;
+                                let fill = mix(mix(#fff, #fdecec, self.hover), #f8d3d3, self.pressed);
+                                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, r - 1.0);
+                                sdf.fill_keep(fill);
+                                sdf.stroke(#d32323, 1.5);
+                                return sdf.result;
+                            }
+                        }
+                        <Label> {
+                            text: "Call"
+                            draw_text: { color: (YELP_RED), text_style: { font_size: 16.0 } }
+                        }
+                    }
+
+                    // Copies a makepadyelp:// link to the business
+                    share_button = <RoundedView> {
+                        width: Fill, height: 50.0
+                        align: { x: 0.5, y: 0.5 }
+                        cursor: Hand
+                        show_bg: true
+                        draw_bg: {
+                            instance hover: 0.0
+                            instance pressed: 0.0
+                            fn pixel(self) -> vec4 {
+                                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                let r = self.rect_size.y * 0.5;
+                                let fill = mix(mix(#fff, #fdecec, self.hover), #f8d3d3, self.pressed);
+                                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, r - 1.0);
+                                sdf.fill_keep(fill);
+                                sdf.stroke(#d32323, 1.5);
+                                return sdf.result;
+                            }
+                        }
+                        share_label = <Label> {
+                            text: "Share"
+                            draw_text: { color: (YELP_RED), text_style: { font_size: 16.0 } }
+                        }
+                    }
+                }
+
+                // Contact section: address, phone, website
+                <View> {
+                    width: Fill, height: 1.0
+                    margin: { left: 16.0, right: 16.0 }
+                    show_bg: true, draw_bg: { color: #e0e0e0 }
+                }
+
+                contact_section = <View> {
+                    width: Fill, height: Fit
+                    flow: Down
+                    spacing: 12.0
+                    padding: { left: 16.0, right: 16.0 }
+
+                    address_row = <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 4.0
+                        <Label> {
+                            width: Fill, height: Fit
+                            draw_text: { text_style: { font_size: 12.0 }, color: #999 }
+                            text: "Address"
+                        }
+                        address_label = <Label> {
+                            width: Fill, height: Fit
+                            draw_text: { text_style: { font_size: 14.0, line_spacing: 1.4 }, color: #1a1a1a }
+                            text: ""
+                        }
+                    }
+
+                    phone_row = <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 4.0
+                        <Label> {
+                            width: Fill, height: Fit
+                            draw_text: { text_style: { font_size: 12.0 }, color: #999 }
+                            text: "Phone"
+                        }
+                        phone_label = <Label> {
+                            width: Fill, height: Fit
+                            draw_text: { text_style: { font_size: 14.0 }, color: #1a1a1a }
+                            text: ""
+                        }
+                    }
+
+                    website_row = <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 4.0
+                        <Label> {
+                            width: Fill, height: Fit
+                            draw_text: { text_style: { font_size: 12.0 }, color: #999 }
+                            text: "Website"
+                        }
+                        website_label = <Label> {
+                            width: Fill, height: Fit
+                            draw_text: { text_style: { font_size: 14.0 }, color: #0073bb }
+                            text: ""
+                        }
+                    }
                 }
 
                 // Description section
@@ -986,7 +1604,7 @@ live_design! {
                     description = <Label> {
                         width: Fill, height: Fit
                         draw_text: { text_style: { font_size: 14.0, line_spacing: 1.4 }, color: #666 }
-                        text: "A popular spot known for its delicious food and great atmosphere. Come visit us for an unforgettable dining experience."
+                        text: ""
                     }
                 }
 
@@ -1003,78 +1621,398 @@ live_design! {
                     spacing: 8.0
                     padding: { left: 16.0, right: 16.0, bottom: 16.0 }
 
-                    <Label> {
+                    <View> {
                         width: Fill, height: Fit
-                        draw_text: { text_style: { font_size: 16.0 }, color: #1a1a1a }
-                        text: "Hours"
+                        flow: Right
+                        spacing: 8.0
+                        align: { y: 0.5 }
+
+                        <Label> {
+                            width: Fit, height: Fit
+                            draw_text: { text_style: { font_size: 16.0 }, color: #1a1a1a }
+                            text: "Hours"
+                        }
+                        open_status_label = <Label> {
+                            width: Fit, height: Fit
+                            draw_text: { text_style: { font_size: 14.0 }, color: #666 }
+                            text: ""
+                        }
                     }
 
                     hours_label = <Label> {
                         width: Fill, height: Fit
-                        draw_text: { text_style: { font_size: 14.0 }, color: #666 }
-                        text: "Mon-Sun: 11:00 AM - 10:00 PM"
+                        draw_text: { text_style: { font_size: 14.0, line_spacing: 1.4 }, color: #666 }
+                        text: ""
                     }
                 }
-            }
-        }
-    }
 
-    // Main App with Stack Navigation
-    App = {{App}} {
-        ui: <Root> {
-            main_window = <Window> {
-                window: { inner_size: vec2(1080, 2424) }
-                show_bg: true
-                width: Fill
-                height: Fill
-                draw_bg: { fn pixel(self) -> vec4 { return #ffffff } }
+                // Reviews section
+                <View> {
+                    width: Fill, height: 1.0
+                    margin: { left: 16.0, right: 16.0 }
+                    show_bg: true, draw_bg: { color: #e0e0e0 }
+                }
 
-                body = <View> {
-                    width: Fill
-                    height: Fill
+                reviews_section = <View> {
+                    width: Fill, height: Fit
                     flow: Down
+                    spacing: 12.0
+                    padding: { left: 16.0, right: 16.0, bottom: 24.0 }
 
-                    // Title bar
                     <View> {
-                        width: Fill, height: 50.0
-                        show_bg: true
-                        draw_bg: { color: #d32323 }
-                        align: { x: 0.5, y: 0.5 }
+                        width: Fill, height: Fit
+                        flow: Right
+                        align: { y: 0.5 }
 
-                        <Label> {
-                            width: Fit, height: Fit
-                            draw_text: {
-                                text_style: { font_size: 18.0 }
-                                color: #ffffff
-                            }
-                            text: "Makepad Yelp"
+                        reviews_title = <Label> {
+                            width: Fill, height: Fit
+                            draw_text: { text_style: { font_size: 16.0 }, color: #1a1a1a }
+                            text: "Reviews"
+                        }
+                        write_review = <PillChip> {
+                            draw_bg: { selected: 1.0 }
+                            label = { text: "Write a review", draw_text: { color: #fff } }
                         }
                     }
 
-                    content = <View> {
-                        width: Fill
-                        height: Fill
-                        flow: Overlay
+                    // Sort options; hidden when the provider pages in one order only
+                    review_sort_chips = <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 8.0
 
-                        search_screen = <SearchScreen> { visible: true }
-                        map_screen = <MapScreen> { visible: false }
-                        detail_screen = <BusinessDetailScreen> { visible: false }
+                        sort_newest = <PillChip> { label = { text: "Newest" } }
+                        sort_highest = <PillChip> { label = { text: "Highest rated" } }
+                        sort_lowest = <PillChip> { label = { text: "Lowest rated" } }
                     }
 
-                    tab_bar = <YelpTabBar> {}
-                }
-            }
+                    review_list = <ReviewList> {}
+
+                    reviews_status = <Label> {
+                        width: Fill, height: Fit
+                        draw_text: { text_style: { font_size: 14.0 }, color: #999 }
+                        text: "Loading reviews..."
+                    }
+
+                    more_reviews = <PillChip> {
+                        label = { text: "More reviews" }
+                    }
+                }
+            }
+        }
+    }
+
+    // Compose a review: star picker, text, validation, and draft autosave
+    // Full-screen business photo
+    PhotoScreen = {{PhotoScreen}} {
+        width: Fill
+        height: Fill
+        flow: Overlay
+        show_bg: true
+        draw_bg: { color: #000 }
+
+        photo_image = <Image> {
+            width: Fill, height: Fill
+            fit: Smallest
+        }
+
+        <View> {
+            width: Fill, height: 56.0
+            padding: { left: 8.0, right: 16.0 }
+            flow: Right
+            align: { y: 0.5 }
+
+            back_button = <Button> {
+                width: 44.0, height: 44.0
+                text: "<"
+                draw_text: {
+                    color: #fff
+                    text_style: { font_size: 20.0 }
+                }
+                draw_bg: { color: #0000 }
+            }
+
+            photo_title = <Label> {
+                width: Fill, height: Fit
+                draw_text: {
+                    text_style: { font_size: 16.0 }
+                    color: #fff
+                }
+                text: ""
+            }
+        }
+    }
+
+    ReviewComposeScreen = {{ReviewComposeScreen}} {
+        width: Fill
+        height: Fill
+        flow: Down
+        show_bg: true
+        draw_bg: { color: #fff }
+
+        header = <View> {
+            width: Fill, height: 56.0
+            padding: { left: 8.0, right: 16.0 }
+            show_bg: true
+            draw_bg: { color: #fff }
+            flow: Right
+            align: { y: 0.5 }
+
+            cancel_button = <Button> {
+                width: 44.0, height: 44.0
+                text: "<"
+                draw_text: {
+                    color: (YELP_RED)
+                    text_style: { font_size: 20.0 }
+                }
+                draw_bg: { color: #0000 }
+            }
+
+            <Label> {
+                width: Fill, height: Fit
+                draw_text: {
+                    text_style: { font_size: 18.0 }
+                    color: #1a1a1a
+                }
+                text: "Write a review"
+            }
+        }
+
+        <View> { width: Fill, height: 1.0, show_bg: true, draw_bg: { color: #e0e0e0 } }
+
+        <ScrollYView> {
+            width: Fill, height: Fill
+
+            <View> {
+                width: Fill, height: Fit
+                flow: Down
+                spacing: 12.0
+                padding: 16.0
+
+                business_name = <Label> {
+                    width: Fill, height: Fit
+                    draw_text: { text_style: { font_size: 20.0 }, color: #1a1a1a }
+                    text: ""
+                }
+
+                <Label> {
+                    width: Fill, height: Fit
+                    draw_text: { text_style: { font_size: 14.0 }, color: #666 }
+                    text: "Tap or drag to rate"
+                }
+
+                rating_input = <StarRating> {
+                    width: 220.0, height: 40.0
+                    cursor: Hand
+                    interactive: true
+                }
+
+                review_input = <TextInput> {
+                    width: Fill, height: 200.0
+                    empty_text: "Share details of your own experience at this place"
+                }
+
+                char_count = <Label> {
+                    width: Fill, height: Fit
+                    draw_text: { text_style: { font_size: 12.0 }, color: #999 }
+                    text: ""
+                }
+
+                error_label = <Label> {
+                    width: Fill, height: Fit
+                    draw_text: { text_style: { font_size: 13.0 }, color: (YELP_RED) }
+                    text: ""
+                }
+
+                submit_button = <RoundedView> {
+                    width: Fill, height: 50.0
+                    margin: { top: 8.0 }
+                    align: { x: 0.5, y: 0.5 }
+                    cursor: Hand
+                    show_bg: true
+                    draw_bg: { color: (YELP_RED), border_radius: 25.0 }
+                    <Label> {
+                        text: "Post review"
+                        draw_text: { color: #fff, text_style: { font_size: 16.0 } }
+                    }
+                }
+
+                draft_label = <Label> {
+                    width: Fill, height: Fit
+                    draw_text: { text_style: { font_size: 12.0 }, color: #999 }
+                    text: "Draft saved"
+                }
+            }
+        }
+    }
+
+    // Bottom sheet with every search filter; changes apply when "Show results" is tapped
+    FilterSheet = {{FilterSheet}} {
+        width: Fill
+        height: Fill
+        flow: Down
+        show_bg: true
+        draw_bg: { color: #00000066 }
+
+        // Tapping above the sheet dismisses it
+        backdrop = <View> { width: Fill, height: Fill }
+
+        sheet = <RoundedView> {
+            width: Fill, height: Fit
+            flow: Down
+            spacing: 12.0
+            padding: { top: 16.0, bottom: 24.0, left: 16.0, right: 16.0 }
+            show_bg: true
+            draw_bg: { color: #fff, border_radius: 16.0 }
+
+            <View> {
+                width: Fill, height: Fit
+                flow: Right
+                align: { y: 0.5 }
+
+                <Label> {
+                    width: Fill, height: Fit
+                    draw_text: { text_style: { font_size: 18.0 }, color: #1a1a1a }
+                    text: "Filters"
+                }
+                reset_chip = <PillChip> {
+                    label = { text: "Reset" }
+                }
+            }
+
+            <Label> {
+                draw_text: { text_style: { font_size: 14.0 }, color: #666 }
+                text: "Price"
+            }
+            <View> {
+                width: Fill, height: Fit
+                flow: Right
+                spacing: 8.0
+                sheet_price_1 = <PillChip> { label = { text: "$" } }
+                sheet_price_2 = <PillChip> { label = { text: "$$" } }
+                sheet_price_3 = <PillChip> { label = { text: "$$$" } }
+                sheet_price_4 = <PillChip> { label = { text: "$$$$" } }
+            }
+
+            <Label> {
+                draw_text: { text_style: { font_size: 14.0 }, color: #666 }
+                text: "Rating"
+            }
+            <View> {
+                width: Fill, height: Fit
+                flow: Right
+                spacing: 8.0
+                rating_any = <PillChip> { label = { text: "Any" } }
+                rating_35 = <PillChip> { label = { text: "3.5+" } }
+                rating_40 = <PillChip> { label = { text: "4.0+" } }
+                rating_45 = <PillChip> { label = { text: "4.5+" } }
+            }
+
+            <Label> {
+                draw_text: { text_style: { font_size: 14.0 }, color: #666 }
+                text: "Distance"
+            }
+            <View> {
+                width: Fill, height: Fit
+                flow: Right
+                spacing: 8.0
+                distance_any = <PillChip> { label = { text: "Any" } }
+                distance_walking = <PillChip> { label = { text: "Walking (1 mi)" } }
+                distance_biking = <PillChip> { label = { text: "Biking (2 mi)" } }
+                distance_driving = <PillChip> { label = { text: "Driving (5 mi)" } }
+            }
+
+            <Label> {
+                draw_text: { text_style: { font_size: 14.0 }, color: #666 }
+                text: "Categories"
+            }
+            categories = <CategoryChips> {}
+
+            sheet_open_now = <PillChip> {
+                label = { text: "Open Now" }
+            }
+
+            apply_button = <RoundedView> {
+                width: Fill, height: 50.0
+                margin: { top: 8.0 }
+                align: { x: 0.5, y: 0.5 }
+                cursor: Hand
+                show_bg: true
+                draw_bg: { color: (YELP_RED), border_radius: 25.0 }
+                apply_label = <Label> {
+                    text: "Show results"
+                    draw_text: { color: #fff, text_style: { font_size: 16.0 } }
+                }
+            }
+        }
+    }
+
+    // Main App with Stack Navigation
+    App = {{App}} {
+        ui: <Root> {
+            main_window = <Window> {
+                window: { inner_size: vec2(1080, 2424) }
+                show_bg: true
+                width: Fill
+                height: Fill
+                draw_bg: { fn pixel(self) -> vec4 { return #ffffff } }
+
+                body = <View> {
+                    width: Fill
+                    height: Fill
+                    flow: Down
+
+                    // Title bar
+                    <View> {
+                        width: Fill, height: 50.0
+                        show_bg: true
+                        draw_bg: { color: #d32323 }
+                        align: { x: 0.5, y: 0.5 }
+
+                        <Label> {
+                            width: Fit, height: Fit
+                            draw_text: {
+                                text_style: { font_size: 18.0 }
+                                color: #ffffff
+                            }
+                            text: "Makepad Yelp"
+                        }
+                    }
+
+                    content = <View> {
+                        width: Fill
+                        height: Fill
+                        flow: Overlay
+
+                        search_screen = <SearchScreen> { visible: true }
+                        map_screen = <MapScreen> { visible: false }
+                        detail_screen = <BusinessDetailScreen> { visible: false }
+                        compose_screen = <ReviewComposeScreen> { visible: false }
+                        route_screen = <RouteScreen> { visible: false }
+                        photo_screen = <PhotoScreen> { visible: false }
+                        filter_sheet = <FilterSheet> { visible: false }
+                    }
+
+                    tab_bar = <YelpTabBar> {}
+                }
+            }
         }
     }
 }
 
 app_main!(App);
 
+/// Hands the encoded bytes to `image_pipeline`, which keeps them in memory (a layer
+/// over the disk cache) and decodes off the UI thread; widgets only draw its textures.
+fn store_image(business_id: &str, bytes: Vec<u8>) {
+    image_pipeline::decode_in_background(business_id, Arc::new(bytes));
+}
+
 // =====================
 // Data Types
 // =====================
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, SerJson, DeJson)]
 pub struct Business {
     pub id: String,
     pub name: String,
@@ -1086,9 +2024,94 @@ pub struct Business {
     pub distance_meters: Option<f64>,
     pub lat: f64,
     pub lng: f64,
+    pub image_url: Option<String>,
+    /// E.164 number, e.g. "+14158267000"
+    pub phone: Option<String>,
+    /// Number as printed locally, e.g. "(415) 826-7000"
+    pub display_phone: Option<String>,
+    /// Street address split into display lines
+    pub address: Vec<String>,
+    pub hours: Vec<OpenInterval>,
+    pub photos: Vec<String>,
+    /// Listing page for the business
+    pub url: Option<String>,
+    /// Holiday closures and other one-off changes to `hours`
+    pub special_hours: Vec<SpecialHours>,
+    /// IANA zone the hours are in; `hours::DEFAULT_TIME_ZONE` when missing
+    pub timezone: Option<String>,
+    /// Closed for good, as opposed to outside opening hours
+    pub is_closed: bool,
+    pub description: Option<String>,
 }
 
 impl Business {
+    /// Photo to show for this business; falls back to a stable placeholder per id.
+    pub fn photo_url(&self) -> String {
+        match self.image_url.as_ref().or(self.photos.first()) {
+            Some(url) => url.clone(),
+            None => format!("https://picsum.photos/seed/{}/320/240", self.id),
+        }
+    }
+
+    /// Number to dial in E.164, from `phone` or else parsed from `display_phone`.
+    pub fn dial_number(&self) -> Option<String> {
+        self.phone
+            .iter()
+            .chain(self.display_phone.iter())
+            .find_map(|number| phone::normalize_e164(number, phone::DEFAULT_COUNTRY_CODE))
+    }
+
+    /// Number as shown on the detail screen; the listing's own formatting wins.
+    pub fn phone_text(&self) -> Option<String> {
+        self.display_phone
+            .clone()
+            .or_else(|| self.dial_number().map(|number| phone::format_display(&number)))
+            .or_else(|| self.phone.clone())
+    }
+
+    pub fn schedule(&self) -> Schedule<'_> {
+        Schedule::new(&self.hours, &self.special_hours, self.timezone.as_deref())
+    }
+
+    /// Open or closed at `unix_secs`; None for closed-for-good listings and ones without hours.
+    pub fn open_state_at(&self, unix_secs: i64) -> Option<OpenState> {
+        if self.is_closed {
+            return None;
+        }
+        self.schedule().state_at(unix_secs)
+    }
+
+    pub fn is_open_at(&self, unix_secs: i64) -> bool {
+        self.open_state_at(unix_secs).is_some_and(|state| state.is_open())
+    }
+
+    /// "Open until 10 PM", "Closes in 30 min", "Opens tomorrow at 9 AM", "Permanently closed".
+    pub fn open_status_text_at(&self, unix_secs: i64) -> Option<String> {
+        if self.is_closed {
+            return Some("Permanently closed".to_string());
+        }
+        self.schedule().describe_at(unix_secs)
+    }
+
+    pub fn hours_text(&self) -> String {
+        hours::weekly_text(&self.hours)
+    }
+
+    pub fn address_text(&self) -> String {
+        self.address.join("\n")
+    }
+
+    /// Description if the listing has one, otherwise a line built from its categories.
+    pub fn about_text(&self) -> String {
+        if let Some(ref description) = self.description {
+            return description.clone();
+        }
+        match self.categories.as_slice() {
+            [] => format!("A local favorite in {}.", self.city),
+            categories => format!("{} in {}.", categories.join(", "), self.city),
+        }
+    }
+
     pub fn price_and_categories(&self) -> String {
         let mut parts = Vec::new();
         if let Some(ref price) = self.price {
@@ -1098,7 +2121,7 @@ impl Business {
             parts.push(self.categories.join(", "));
         }
         if let Some(distance) = self.distance_meters {
-            let miles = distance / 1609.34;
+            let miles = distance / METERS_PER_MILE;
             parts.push(format!("{:.1} mi", miles));
         }
         parts.join(" · ")
@@ -1106,7 +2129,7 @@ impl Business {
 
     pub fn distance_text(&self) -> String {
         if let Some(distance) = self.distance_meters {
-            let miles = distance / 1609.34;
+            let miles = distance / METERS_PER_MILE;
             format!("{} · {:.1} mi", self.city, miles)
         } else {
             self.city.clone()
@@ -1119,10 +2142,18 @@ impl Business {
         if let Some(ref price) = self.price {
             parts.push(price.clone());
         }
+        if let Some(status) = self.open_status_text_at(hours::now_unix()) {
+            parts.push(status);
+        }
         parts.join(" · ")
     }
 }
 
+/// Same opening span on each day in `days` (0 = Monday).
+fn weekly_hours(days: std::ops::RangeInclusive<u8>, start: u16, end: u16) -> Vec<OpenInterval> {
+    days.map(|day| OpenInterval { day, start, end, is_overnight: end <= start }).collect()
+}
+
 pub fn mock_businesses() -> Vec<Business> {
     vec![
         Business {
@@ -1130,30 +2161,72 @@ pub fn mock_businesses() -> Vec<Business> {
             price: Some("$$$".into()), categories: vec!["Italian".into(), "Pizza".into()],
             city: "San Francisco".into(), distance_meters: Some(850.0),
             lat: 37.7599, lng: -122.4148,
+            image_url: None,
+            phone: Some("+14158267000".into()), display_phone: Some("(415) 826-7000".into()),
+            address: vec!["2401 Harrison St".into(), "San Francisco, CA 94110".into()],
+            hours: weekly_hours(0..=6, 1700, 2200),
+            photos: Vec::new(),
+            url: Some("https://www.yelp.com/biz/flour-water-san-francisco".into()),
+            special_hours: Vec::new(), timezone: Some(hours::DEFAULT_TIME_ZONE.into()),
+            is_closed: false, description: None,
         },
         Business {
             id: "2".into(), name: "Tartine Bakery".into(), rating: 4.0, review_count: 8234,
             price: Some("$$".into()), categories: vec!["Bakeries".into(), "Cafes".into()],
             city: "San Francisco".into(), distance_meters: Some(1200.0),
             lat: 37.7614, lng: -122.4241,
+            image_url: None,
+            phone: Some("+14154872600".into()), display_phone: Some("(415) 487-2600".into()),
+            address: vec!["600 Guerrero St".into(), "San Francisco, CA 94110".into()],
+            hours: weekly_hours(0..=6, 800, 1700),
+            photos: Vec::new(),
+            url: Some("https://www.yelp.com/biz/tartine-bakery-san-francisco".into()),
+            special_hours: vec![SpecialHours {
+                date: "2026-12-25".into(), is_closed: true, start: None, end: None, is_overnight: false,
+            }], timezone: Some(hours::DEFAULT_TIME_ZONE.into()),
+            is_closed: false, description: None,
         },
         Business {
             id: "3".into(), name: "Burma Superstar".into(), rating: 4.0, review_count: 6712,
             price: Some("$$".into()), categories: vec!["Burmese".into()],
             city: "San Francisco".into(), distance_meters: Some(3400.0),
             lat: 37.7829, lng: -122.4589,
+            image_url: None,
+            phone: Some("+14153872147".into()), display_phone: Some("(415) 387-2147".into()),
+            address: vec!["309 Clement St".into(), "San Francisco, CA 94118".into()],
+            hours: [weekly_hours(0..=6, 1130, 1500), weekly_hours(0..=6, 1700, 2130)].concat(),
+            photos: Vec::new(),
+            url: Some("https://www.yelp.com/biz/burma-superstar-san-francisco".into()),
+            special_hours: Vec::new(), timezone: Some(hours::DEFAULT_TIME_ZONE.into()),
+            is_closed: false, description: None,
         },
         Business {
             id: "4".into(), name: "Zuni Cafe".into(), rating: 4.0, review_count: 3891,
             price: Some("$$$".into()), categories: vec!["American".into()],
             city: "San Francisco".into(), distance_meters: Some(2100.0),
             lat: 37.7755, lng: -122.4214,
+            image_url: None,
+            phone: Some("+14155522522".into()), display_phone: Some("(415) 552-2522".into()),
+            address: vec!["1658 Market St".into(), "San Francisco, CA 94102".into()],
+            hours: weekly_hours(1..=6, 1130, 2100),
+            photos: Vec::new(),
+            url: Some("https://www.yelp.com/biz/zuni-cafe-san-francisco".into()),
+            special_hours: Vec::new(), timezone: Some(hours::DEFAULT_TIME_ZONE.into()),
+            is_closed: false, description: None,
         },
         Business {
             id: "5".into(), name: "La Taqueria".into(), rating: 4.0, review_count: 5423,
             price: Some("$".into()), categories: vec!["Mexican".into(), "Tacos".into()],
             city: "San Francisco".into(), distance_meters: Some(1800.0),
             lat: 37.7509, lng: -122.4180,
+            image_url: None,
+            phone: Some("+14152857117".into()), display_phone: Some("(415) 285-7117".into()),
+            address: vec!["2889 Mission St".into(), "San Francisco, CA 94110".into()],
+            hours: weekly_hours(2..=6, 1100, 2045),
+            photos: Vec::new(),
+            url: Some("https://www.yelp.com/biz/la-taqueria-san-francisco".into()),
+            special_hours: Vec::new(), timezone: Some(hours::DEFAULT_TIME_ZONE.into()),
+            is_closed: false, description: None,
         },
     ]
 }
@@ -1162,16 +2235,71 @@ pub fn mock_businesses() -> Vec<Business> {
 // Widget Implementations
 // =====================
 
+/// Hover feedback for a `PillChip`; true when it was tapped.
+fn chip_tapped(cx: &mut Cx, event: &Event, chip: &ViewRef) -> bool {
+    match event.hits(cx, chip.area()) {
+        Hit::FingerUp(fe) => fe.is_over,
+        Hit::FingerHoverIn(_) => {
+            chip.apply_over(cx, live! { draw_bg: { hover: 1.0 } });
+            chip.redraw(cx);
+            false
+        }
+        Hit::FingerHoverOut(_) => {
+            chip.apply_over(cx, live! { draw_bg: { hover: 0.0 } });
+            chip.redraw(cx);
+            false
+        }
+        _ => false,
+    }
+}
+
+/// Fills a `PillChip` in Yelp red with white text when selected.
+fn set_chip_selected(cx: &mut Cx, chip: &ViewRef, selected: bool) {
+    style_chip(cx, chip, selected);
+    chip.redraw(cx);
+}
+
+/// Applies the selected look without queueing a redraw, for chips styled while drawing.
+fn style_chip(cx: &mut Cx, chip: &ViewRef, selected: bool) {
+    let (amount, text_color) = if selected {
+        (1.0, vec4(1.0, 1.0, 1.0, 1.0))
+    } else {
+        (0.0, vec4(0.2, 0.2, 0.2, 1.0))
+    };
+    chip.apply_over(cx, live! { draw_bg: { selected: (amount) } });
+    chip.label(ids!(label)).apply_over(cx, live! { draw_text: { color: (text_color) } });
+}
+
 #[derive(Live, LiveHook, Widget)]
 pub struct StarRating {
     #[deref] view: View,
     #[live] rating: f32,
+    /// Lets taps and drags pick a rating in half-star steps
+    #[live] interactive: bool,
 }
 
 impl Widget for StarRating {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         self.view.handle_event(cx, event, scope);
-    }
+        if !self.interactive { return; }
+
+        let abs_x = match event.hits(cx, self.view.area()) {
+            Hit::FingerDown(fe) => Some(fe.abs.x),
+            Hit::FingerMove(fe) => Some(fe.abs.x),
+            _ => None,
+        };
+        if let Some(abs_x) = abs_x {
+            let rect = self.view.area().rect(cx);
+            let fraction = ((abs_x - rect.pos.x) / rect.size.x.max(1.0)).clamp(0.0, 1.0);
+            // Round up to the next half star, never below half a star
+            let rating = ((fraction * 10.0).ceil() / 2.0).max(0.5) as f32;
+            if rating != self.rating {
+                self.rating = rating;
+                cx.widget_action(self.widget_uid(), &scope.path, StarRatingAction::Changed(rating));
+                self.redraw(cx);
+            }
+        }
+    }
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
         self.view.apply_over(cx, live! { draw_bg: { rating: (self.rating) } });
         self.view.draw_walk(cx, scope, walk)
@@ -1185,6 +2313,168 @@ impl StarRatingRef {
             inner.redraw(cx);
         }
     }
+
+    /// New rating picked on an interactive star rating.
+    pub fn changed(&self, actions: &Actions) -> Option<f32> {
+        if let StarRatingAction::Changed(rating) = actions.find_widget_action(self.widget_uid()).cast() {
+            Some(rating)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum StarRatingAction {
+    None,
+    Changed(f32),
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct ReviewList {
+    #[redraw] #[rust] area: Area,
+    #[walk] walk: Walk,
+    #[layout] layout: Layout,
+    #[live] item: Option<LivePtr>,
+    #[rust] reviews: Vec<Review>,
+    #[rust] expanded: HashSet<String>, // Ids of reviews showing their full text
+    #[rust] items: ComponentMap<LiveId, WidgetRef>,
+}
+
+impl Widget for ReviewList {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let mut toggled = None;
+        for (id, item) in self.items.iter() {
+            item.handle_event(cx, event, scope);
+            if let Hit::FingerUp(fe) = event.hits(cx, item.view(ids!(read_more)).area()) {
+                if fe.is_over {
+                    toggled = Some(*id);
+                }
+            }
+        }
+        if let Some(id) = toggled {
+            if let Some(review) = self.reviews.iter().find(|review| LiveId::from_str(&review.id) == id) {
+                if !self.expanded.remove(&review.id) {
+                    self.expanded.insert(review.id.clone());
+                }
+                self.redraw(cx);
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        cx.begin_turtle(walk, self.layout);
+        for review in &self.reviews {
+            let item = self.items.get_or_insert(cx, LiveId::from_str(&review.id), |cx| {
+                WidgetRef::new_from_ptr(cx, self.item)
+            });
+            item.label(ids!(initial)).set_text(cx, &review.initial());
+            item.label(ids!(author)).set_text(cx, &review.author);
+            item.label(ids!(date)).set_text(cx, &review.date_text());
+            item.star_rating(ids!(stars)).set_rating(cx, review.rating);
+
+            // Long reviews collapse to a preview until "Read more" is tapped
+            let preview = review.preview();
+            let expanded = self.expanded.contains(&review.id);
+            let text = match preview {
+                Some(ref preview) if !expanded => preview.as_str(),
+                _ => review.text.as_str(),
+            };
+            item.label(ids!(text)).set_text(cx, text);
+            item.view(ids!(read_more)).set_visible(cx, preview.is_some());
+            item.label(ids!(read_more_label)).set_text(cx, if expanded { "Show less" } else { "Read more" });
+
+            let photos = match review.photos.len() {
+                0 => String::new(),
+                1 => "1 photo".to_string(),
+                count => format!("{} photos", count),
+            };
+            item.widget(ids!(photos_label)).set_visible(cx, !photos.is_empty());
+            item.label(ids!(photos_label)).set_text(cx, &photos);
+
+            item.draw_all(cx, scope);
+        }
+        cx.end_turtle_with_area(&mut self.area);
+        self.items.retain_visible();
+        DrawStep::done()
+    }
+}
+
+impl ReviewListRef {
+    pub fn set_reviews(&self, cx: &mut Cx, reviews: Vec<Review>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.reviews = reviews;
+            inner.redraw(cx);
+        }
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct CategoryChips {
+    #[redraw] #[rust] area: Area,
+    #[walk] walk: Walk,
+    #[layout] layout: Layout,
+    #[live] chip: Option<LivePtr>,
+    #[rust] categories: Vec<String>,
+    #[rust] selected: Vec<String>,
+    #[rust] items: ComponentMap<LiveId, WidgetRef>,
+}
+
+impl Widget for CategoryChips {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let mut tapped = None;
+        for (id, item) in self.items.iter() {
+            if chip_tapped(cx, event, &item.as_view()) {
+                tapped = Some(*id);
+            }
+        }
+        let category = tapped.and_then(|id| {
+            self.categories.iter().find(|category| LiveId::from_str(category) == id).cloned()
+        });
+        if let Some(category) = category {
+            cx.widget_action(self.widget_uid(), &scope.path, CategoryChipsAction::Toggled(category));
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk: Walk) -> DrawStep {
+        cx.begin_turtle(walk, self.layout);
+        for category in &self.categories {
+            let item = self.items.get_or_insert(cx, LiveId::from_str(category), |cx| {
+                WidgetRef::new_from_ptr(cx, self.chip)
+            });
+            item.label(ids!(label)).set_text(cx, category);
+            style_chip(cx, &item.as_view(), self.selected.contains(category));
+            item.draw_all_unscoped(cx);
+        }
+        cx.end_turtle_with_area(&mut self.area);
+        self.items.retain_visible();
+        DrawStep::done()
+    }
+}
+
+impl CategoryChipsRef {
+    pub fn set_categories(&self, cx: &mut Cx, categories: Vec<String>, selected: &[String]) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.categories = categories;
+            inner.selected = selected.to_vec();
+            inner.redraw(cx);
+        }
+    }
+
+    /// Category whose chip was tapped.
+    pub fn toggled(&self, actions: &Actions) -> Option<String> {
+        if let CategoryChipsAction::Toggled(category) = actions.find_widget_action(self.widget_uid()).cast() {
+            Some(category)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum CategoryChipsAction {
+    None,
+    Toggled(String),
 }
 
 #[derive(Live, LiveHook, Widget)]
@@ -1225,23 +2515,182 @@ impl Widget for LoadingSpinner {
 #[derive(Live, LiveHook, Widget)]
 pub struct SearchBar {
     #[deref] view: View,
+    #[live(0.3)] debounce_secs: f64,
+    #[rust] pending_query: Option<String>,
+    #[rust] debounce_timer: Timer,
 }
 
 impl Widget for SearchBar {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
-        self.view.handle_event(cx, event, scope);
+        let actions = cx.capture_actions(|cx| self.view.handle_event(cx, event, scope));
+
+        // Restart the debounce window on every keystroke
+        if let Some(text) = self.view.text_input(ids!(input)).changed(&actions) {
+            self.view.view(ids!(clear_button)).set_visible(cx, !text.is_empty());
+            self.pending_query = Some(text);
+            cx.stop_timer(self.debounce_timer);
+            self.debounce_timer = cx.start_timeout(self.debounce_secs);
+        }
+
+        if self.debounce_timer.is_event(event).is_some() {
+            if let Some(query) = self.pending_query.take() {
+                cx.widget_action(self.widget_uid(), &scope.path, SearchBarAction::QueryChanged(query));
+            }
+        }
+
+        // Handle clear button
+        let clear_btn = self.view.view(ids!(clear_button));
+        match event.hits(cx, clear_btn.area()) {
+            Hit::FingerUp(fe) => {
+                if fe.is_over {
+                    self.clear(cx);
+                    cx.widget_action(self.widget_uid(), &scope.path, SearchBarAction::QueryChanged(String::new()));
+                }
+            }
+            Hit::FingerHoverIn(_) => {
+                clear_btn.apply_over(cx, live!{ draw_bg: { hover: 1.0 } });
+                clear_btn.redraw(cx);
+            }
+            Hit::FingerHoverOut(_) => {
+                clear_btn.apply_over(cx, live!{ draw_bg: { hover: 0.0 } });
+                clear_btn.redraw(cx);
+            }
+            _ => {}
+        }
+    }
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+impl SearchBar {
+    fn clear(&mut self, cx: &mut Cx) {
+        cx.stop_timer(self.debounce_timer);
+        self.pending_query = None;
+        self.view.text_input(ids!(input)).set_text(cx, "");
+        self.view.view(ids!(clear_button)).set_visible(cx, false);
+        self.redraw(cx);
+    }
+}
+
+impl SearchBarRef {
+    pub fn clear(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.clear(cx);
+        }
+    }
+
+    /// Shows `query` as if typed, without emitting `QueryChanged`.
+    pub fn set_query(&self, cx: &mut Cx, query: &str) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.clear(cx);
+            inner.view.text_input(ids!(input)).set_text(cx, query);
+            inner.view.view(ids!(clear_button)).set_visible(cx, !query.is_empty());
+        }
+    }
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum SearchBarAction {
+    None,
+    QueryChanged(String),
+}
+
+/// Suggestion rows in the `LocationPicker` DSL.
+const LOCATION_SUGGESTIONS: usize = 5;
+
+#[derive(Live, LiveHook, Widget)]
+pub struct LocationPicker {
+    #[deref] view: View,
+    #[live] visible: bool,
+    #[rust] suggestions: Vec<Place>,
+}
+
+impl Widget for LocationPicker {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if !self.visible { return; }
+        let actions = cx.capture_actions(|cx| self.view.handle_event(cx, event, scope));
+
+        if let Some(text) = self.view.text_input(ids!(location_input)).changed(&actions) {
+            cx.widget_action(self.widget_uid(), &scope.path, LocationPickerAction::QueryChanged(text));
+        }
+        if let Hit::FingerUp(fe) = event.hits(cx, self.view.view(ids!(current_row)).area()) {
+            if fe.is_over {
+                cx.widget_action(self.widget_uid(), &scope.path, LocationPickerAction::UseCurrentLocation);
+            }
+        }
+        let rows = [ids!(suggestion_0), ids!(suggestion_1), ids!(suggestion_2), ids!(suggestion_3), ids!(suggestion_4)];
+        for (place, row) in self.suggestions.iter().zip(rows) {
+            if let Hit::FingerUp(fe) = event.hits(cx, self.view.view(row).area()) {
+                if fe.is_over {
+                    cx.widget_action(self.widget_uid(), &scope.path, LocationPickerAction::Picked(place.clone()));
+                }
+            }
+        }
     }
+
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if !self.visible { return DrawStep::done(); }
+        let rows = [ids!(suggestion_0), ids!(suggestion_1), ids!(suggestion_2), ids!(suggestion_3), ids!(suggestion_4)];
+        for (index, row) in rows.into_iter().enumerate() {
+            let row = self.view.view(row);
+            match self.suggestions.get(index) {
+                Some(place) => {
+                    row.label(ids!(name)).set_text(cx, &place.name);
+                    row.set_visible(cx, true);
+                }
+                None => row.set_visible(cx, false),
+            }
+        }
+        let typed = !self.view.text_input(ids!(location_input)).text().trim().is_empty();
+        self.view.widget(ids!(no_match)).set_visible(cx, typed && self.suggestions.is_empty());
         self.view.draw_walk(cx, scope, walk)
     }
 }
 
+impl LocationPickerRef {
+    pub fn is_open(&self) -> bool {
+        self.borrow().is_some_and(|inner| inner.visible)
+    }
+
+    pub fn set_open(&self, cx: &mut Cx, open: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.visible = open;
+            if open {
+                inner.suggestions.clear();
+                let input = inner.view.text_input(ids!(location_input));
+                input.set_text(cx, "");
+                input.set_key_focus(cx);
+            }
+            inner.redraw(cx);
+        }
+    }
+
+    pub fn set_suggestions(&self, cx: &mut Cx, places: Vec<Place>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.suggestions = places;
+            inner.suggestions.truncate(LOCATION_SUGGESTIONS);
+            inner.redraw(cx);
+        }
+    }
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum LocationPickerAction {
+    None,
+    QueryChanged(String),
+    Picked(Place),
+    UseCurrentLocation,
+}
+
 #[derive(Live, LiveHook, Widget)]
 pub struct BusinessCard {
     #[deref] view: View,
     #[animator] animator: Animator,
     #[rust] business: Option<Business>,
-    #[rust] loaded_image_id: Option<String>, // Track which business's image is loaded
+    #[rust] highlight: String, // Query whose matches are bolded in the name
+    #[rust] loaded_image: Option<(String, u64)>, // Business id and texture generation shown
+    #[rust] photo_failed: bool,
 }
 
 impl Widget for BusinessCard {
@@ -1254,6 +2703,23 @@ impl Widget for BusinessCard {
             self.redraw(cx);
         }
 
+        // Retry a failed photo; checked before the card so the tap doesn't open details
+        if self.photo_failed {
+            let error_area = self.view.view(ids!(photo_error)).area();
+            if let Hit::FingerUp(fe) = event.hits(cx, error_area) {
+                if fe.is_over {
+                    if let Some(ref business) = self.business {
+                        cx.widget_action(
+                            self.widget_uid(),
+                            &scope.path,
+                            BusinessCardAction::RetryImage(business.clone()),
+                        );
+                    }
+                }
+                return;
+            }
+        }
+
         // Handle hits on our area
         match event.hits(cx, self.view.area()) {
             Hit::FingerUp(fe) => {
@@ -1282,8 +2748,9 @@ impl Widget for BusinessCard {
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
         if let Some(ref business) = self.business {
-            self.view.label(ids!(name_label)).set_text(cx, &business.name);
-            self.view.label(ids!(distance_label)).set_text(cx, &format!("{:.1} mi", business.distance_meters.unwrap_or(0.0) / 1609.34));
+            let ranges = search::match_ranges(&business.name, &self.highlight);
+            self.view.widget(ids!(name_label)).set_text(cx, &search::highlight_markup(&business.name, &ranges));
+            self.view.label(ids!(distance_label)).set_text(cx, &format!("{:.1} mi", business.distance_meters.unwrap_or(0.0) / METERS_PER_MILE));
             self.view.label(ids!(rating_num)).set_text(cx, &format!("{:.1}", business.rating));
             self.view.label(ids!(review_count)).set_text(cx, &format!("({} reviews)", business.review_count));
             self.view.label(ids!(meta_label)).set_text(cx, &business.meta_line());
@@ -1296,29 +2763,25 @@ impl Widget for BusinessCard {
             // Set star rating
             self.view.star_rating(ids!(stars)).set_rating(cx, business.rating);
 
-            // Load restaurant image from network if available
-            let needs_load = self.loaded_image_id.as_ref() != Some(&business.id);
-            let mut image_loaded = !needs_load;
-
-            if needs_load {
-                // Get image data from cache (clone to release lock quickly)
-                let image_data = RESTAURANT_IMAGES.lock().ok()
-                    .and_then(|images| images.get(&business.id).cloned());
-
-                if let Some(data) = image_data {
-                    let photo = self.view.image(ids!(photo));
-                    // Try loading as JPEG (picsum returns JPEG)
-                    if photo.load_jpg_from_data(cx, &data).is_ok() {
-                        self.loaded_image_id = Some(business.id.clone());
-                        image_loaded = true;
-                    }
+            // Use the thumbnail decoded off the UI thread; nothing is decoded during draw
+            if let Some((texture, generation)) = image_pipeline::texture(&business.id, ImageSize::Thumbnail) {
+                let shown = self.loaded_image.as_ref()
+                    .is_some_and(|(id, shown)| *id == business.id && *shown == generation);
+                if !shown {
+                    self.view.image(ids!(photo)).set_texture(cx, Some(texture));
+                    self.loaded_image = Some((business.id.clone(), generation));
                 }
             }
+            let image_loaded = self.loaded_image.as_ref().is_some_and(|(id, _)| *id == business.id);
+            let image_failed = !image_loaded
+                && matches!(image_pipeline::load_state(&business.id), ImageLoadState::Failed(_));
+            self.photo_failed = image_failed;
+            self.view.view(ids!(photo_error)).set_visible(cx, image_failed);
 
             // Show/hide spinner based on image loading state
             // Set size to 0 when hidden to completely remove it from layout and rendering
             let spinner = self.view.view(ids!(photo_spinner));
-            if image_loaded {
+            if image_loaded || image_failed {
                 spinner.apply_over(cx, live! {
                     width: 0.0, height: 0.0
                     draw_bg: { opacity: 0.0 }
@@ -1336,18 +2799,23 @@ impl Widget for BusinessCard {
 
 impl BusinessCard {
     pub fn set_business(&mut self, business: &Business) {
-        // Reset loaded_image_id if business changed to force image reload check
+        // Reset loaded_image if business changed to force image reload check
         if self.business.as_ref().map(|b| &b.id) != Some(&business.id) {
-            self.loaded_image_id = None;
+            self.loaded_image = None;
         }
         self.business = Some(business.clone());
     }
+
+    pub fn set_highlight(&mut self, query: &str) {
+        self.highlight = query.to_string();
+    }
 }
 
 #[derive(Clone, Debug, DefaultNone)]
 pub enum BusinessCardAction {
     None,
     Clicked(Business),
+    RetryImage(Business),
 }
 
 #[derive(Live, LiveHook, Widget)]
@@ -1462,6 +2930,25 @@ pub enum YelpTabBarAction {
     TabChanged(Tab),
 }
 
+/// Businesses the map preview card can swipe through.
+const PREVIEW_NEARBY: usize = 10;
+
+/// Cards from the end of the list at which the next page is requested.
+const LOAD_MORE_THRESHOLD: usize = 3;
+
+/// What the search list shows after its last card.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum ListFooter {
+    /// Nothing searched yet
+    #[default]
+    Hidden,
+    /// More pages exist; reaching the end of the list loads the next one
+    More,
+    Loading,
+    Error(String),
+    End,
+}
+
 #[derive(Live, LiveHook, Widget)]
 pub struct SearchScreen {
     #[deref] view: View,
@@ -1469,8 +2956,17 @@ pub struct SearchScreen {
     #[live(true)] visible: bool,
     #[live(true)] is_active: bool, // Whether to process events
     #[rust] businesses: Vec<Business>,
+    #[rust] query: String, // Query the current results were searched with
+    #[live(70.0)] pull_threshold: f64, // Header height at which releasing refreshes
+    #[live(120.0)] pull_max: f64, // Height the rubber band approaches but never reaches
     #[rust] is_refreshing: bool,
+    #[rust] pull_start: Option<DVec2>, // Finger down position of a pull that began at the top of the list
+    #[rust] pull_offset: f64,
+    #[rust] pull_release_frame: NextFrame, // Eases the header closed after a short pull
     #[rust] show_empty_state: bool,
+    #[rust] filters: SearchFilters,
+    #[rust] footer: ListFooter,
+    #[rust] load_more_frame: NextFrame, // Set while drawing once the end of the list is near
 }
 
 impl Widget for SearchScreen {
@@ -1481,45 +2977,116 @@ impl Widget for SearchScreen {
         if !self.is_active { return; }
         self.view.handle_event(cx, event, scope);
 
-        // Handle pull-to-refresh tap
-        let refresh_view = self.view.portal_list(ids!(list)).view(ids!(pull_refresh));
-        if let Hit::FingerUp(fe) = event.hits(cx, refresh_view.area()) {
-            if fe.is_over && !self.is_refreshing {
-                self.is_refreshing = true;
-                cx.widget_action(self.widget_uid(), &scope.path, SearchScreenAction::Refresh);
-                self.redraw(cx);
+        // Handle the location button
+        if let Hit::FingerUp(fe) = event.hits(cx, self.view.view(ids!(location_button)).area()) {
+            if fe.is_over {
+                let picker = self.view.location_picker(ids!(location_picker));
+                picker.set_open(cx, !picker.is_open());
             }
         }
-    }
-    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
-        if !self.visible { return DrawStep::done(); }
-        if self.businesses.is_empty() && !self.show_empty_state {
-            self.businesses = mock_businesses();
+
+        // Handle the filter bar
+        if chip_tapped(cx, event, &self.view.view(ids!(filters_chip))) {
+            cx.widget_action(self.widget_uid(), &scope.path, SearchScreenAction::OpenFilters);
+        }
+        let mut changed = chip_tapped(cx, event, &self.view.view(ids!(open_now_chip)));
+        if changed {
+            self.filters.open_now = !self.filters.open_now;
+        }
+        if chip_tapped(cx, event, &self.view.view(ids!(rating_chip))) {
+            self.filters.min_rating = if self.filters.min_rating == Some(4.0) { None } else { Some(4.0) };
+            changed = true;
+        }
+        let price_chips = [(1, ids!(price_1)), (2, ids!(price_2)), (3, ids!(price_3)), (4, ids!(price_4))];
+        for (level, chip) in price_chips {
+            if chip_tapped(cx, event, &self.view.view(chip)) {
+                self.filters.toggle_price(level);
+                changed = true;
+            }
+        }
+        if changed {
+            self.sync_filter_bar(cx);
+            cx.widget_action(self.widget_uid(), &scope.path, SearchScreenAction::FiltersChanged(self.filters.clone()));
+        }
+
+        // The list reached its end while drawing; ask for the next page
+        if self.load_more_frame.is_event(event).is_some() && self.footer == ListFooter::More {
+            self.footer = ListFooter::Loading;
+            cx.widget_action(self.widget_uid(), &scope.path, SearchScreenAction::LoadMore);
+            self.redraw(cx);
         }
 
-        // Show/hide refresh indicator
-        self.view.view(ids!(refresh_indicator)).set_visible(cx, self.is_refreshing);
+        // Handle retry on a failed page
+        if let ListFooter::Error(_) = self.footer {
+            let retry_view = self.view.portal_list(ids!(list)).view(ids!(footer_error));
+            if let Hit::FingerUp(fe) = event.hits(cx, retry_view.area()) {
+                if fe.is_over {
+                    self.footer = ListFooter::Loading;
+                    cx.widget_action(self.widget_uid(), &scope.path, SearchScreenAction::LoadMore);
+                    self.redraw(cx);
+                }
+            }
+        }
 
-        // Show/hide empty state vs list
-        let has_results = !self.businesses.is_empty() || !self.show_empty_state;
-        self.view.view(ids!(empty_state)).set_visible(cx, self.show_empty_state && self.businesses.is_empty());
-        self.view.portal_list(ids!(list)).set_visible(cx, has_results);
+        self.handle_pull(cx, event, scope);
+    }
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if !self.visible { return DrawStep::done(); }
+        // Size the pull-to-refresh header
+        let header_height = if self.is_refreshing { self.pull_threshold } else { self.pull_offset };
+        let header = self.view.view(ids!(pull_header));
+        header.set_visible(cx, header_height > 0.0);
+        header.apply_over(cx, live! { height: (header_height) });
+        let armed = self.pull_offset >= self.pull_threshold;
+        self.view.view(ids!(pull_arrow)).set_visible(cx, !self.is_refreshing);
+        self.view.view(ids!(pull_arrow)).apply_over(cx, live! { draw_bg: { flip: (if armed { 1.0 } else { 0.0 }) } });
+        self.view.widget(ids!(pull_spinner)).set_visible(cx, self.is_refreshing);
+        let label = if self.is_refreshing {
+            "Refreshing..."
+        } else if armed {
+            "Release to refresh"
+        } else {
+            "Pull to refresh"
+        };
+        self.view.label(ids!(pull_label)).set_text(cx, label);
+
+        // Show/hide empty state vs list (a query with no matches always shows it, unless it failed to load).
+        // While more pages may hold matches the list stays up, so its footer keeps loading them
+        // even when the filters hide every business loaded so far.
+        let failed = matches!(self.footer, ListFooter::Error(_));
+        let paging = matches!(self.footer, ListFooter::More | ListFooter::Loading);
+        let show_empty = self.businesses.is_empty() && !failed && !paging
+            && (self.show_empty_state || !self.query.is_empty());
+        self.view.view(ids!(empty_state)).set_visible(cx, show_empty);
+        self.view.portal_list(ids!(list)).set_visible(cx, !show_empty);
 
         while let Some(item) = self.view.draw_walk(cx, scope, walk).step() {
             if let Some(mut list) = item.as_portal_list().borrow_mut() {
-                // +1 for pull_refresh item at the top
-                list.set_item_range(cx, 0, self.businesses.len() + 1);
+                // +1 for the footer at the bottom
+                let footer_id = self.businesses.len();
+                list.set_item_range(cx, 0, footer_id + 1);
                 while let Some(item_id) = list.next_visible_item(cx) {
-                    if item_id == 0 {
-                        // Pull to refresh item (hidden when refreshing)
-                        let item = list.item(cx, item_id, live_id!(pull_refresh));
-                        if !self.is_refreshing {
-                            item.draw_all_unscoped(cx);
-                        }
-                    } else if item_id <= self.businesses.len() {
+                    if self.footer == ListFooter::More && item_id + LOAD_MORE_THRESHOLD >= footer_id {
+                        self.load_more_frame = cx.new_next_frame();
+                    }
+                    if item_id < footer_id {
                         let item = list.item(cx, item_id, live_id!(business_card));
                         if let Some(mut card) = item.borrow_mut::<BusinessCard>() {
-                            card.set_business(&self.businesses[item_id - 1]);
+                            card.set_business(&self.businesses[item_id]);
+                            card.set_highlight(&self.query);
+                        }
+                        item.draw_all_unscoped(cx);
+                    } else if item_id == footer_id {
+                        let template = match self.footer {
+                            ListFooter::Hidden => continue,
+                            ListFooter::End if self.businesses.is_empty() => continue,
+                            ListFooter::More | ListFooter::Loading => live_id!(footer_loading),
+                            ListFooter::Error(_) => live_id!(footer_error),
+                            ListFooter::End => live_id!(footer_end),
+                        };
+                        let item = list.item(cx, item_id, template);
+                        if let ListFooter::Error(ref message) = self.footer {
+                            item.label(ids!(footer_error_label)).set_text(cx, &format!("Couldn't load more results ({})", message));
                         }
                         item.draw_all_unscoped(cx);
                     }
@@ -1530,6 +3097,68 @@ impl Widget for SearchScreen {
     }
 }
 
+impl SearchScreen {
+    /// Drag down from the top of the list to reveal the header; release past the threshold to refresh.
+    fn handle_pull(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if self.pull_release_frame.is_event(event).is_some() {
+            self.pull_offset *= 0.7;
+            if self.pull_offset < 1.0 {
+                self.pull_offset = 0.0;
+            } else {
+                self.pull_release_frame = cx.new_next_frame();
+            }
+            self.redraw(cx);
+        }
+
+        let list = self.view.portal_list(ids!(list));
+        // The list owns the finger, so watch it without taking the capture away
+        match event.hits_with_capture_overload(cx, list.area(), true) {
+            Hit::FingerDown(fe) => {
+                let at_top = list.first_id() == 0 && list.scroll_position() >= 0.0;
+                if at_top && !self.is_refreshing {
+                    self.pull_start = Some(fe.abs);
+                }
+            }
+            Hit::FingerMove(fe) => {
+                if let Some(start) = self.pull_start {
+                    let dy = (fe.abs.y - start.y).max(0.0);
+                    // Rubber band: follows the finger at first, then resists more and more
+                    self.pull_offset = self.pull_max * (1.0 - (-dy / self.pull_max).exp());
+                    self.redraw(cx);
+                }
+            }
+            Hit::FingerUp(_) => {
+                if self.pull_start.take().is_some() {
+                    if self.pull_offset >= self.pull_threshold {
+                        self.is_refreshing = true;
+                        self.pull_offset = 0.0;
+                        cx.widget_action(self.widget_uid(), &scope.path, SearchScreenAction::Refresh);
+                    } else if self.pull_offset > 0.0 {
+                        self.pull_release_frame = cx.new_next_frame();
+                    }
+                    self.redraw(cx);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn sync_filter_bar(&mut self, cx: &mut Cx) {
+        let filters = self.filters.clone();
+        set_chip_selected(cx, &self.view.view(ids!(open_now_chip)), filters.open_now);
+        set_chip_selected(cx, &self.view.view(ids!(rating_chip)), filters.min_rating == Some(4.0));
+        let price_chips = [(1, ids!(price_1)), (2, ids!(price_2)), (3, ids!(price_3)), (4, ids!(price_4))];
+        for (level, chip) in price_chips {
+            set_chip_selected(cx, &self.view.view(chip), filters.prices.contains(&level));
+        }
+
+        let count = filters.active_count();
+        self.view.view(ids!(badge)).set_visible(cx, count > 0);
+        self.view.label(ids!(badge_label)).set_text(cx, &count.to_string());
+        self.redraw(cx);
+    }
+}
+
 impl SearchScreenRef {
     pub fn set_visible(&self, cx: &mut Cx, visible: bool) {
         if let Some(mut inner) = self.borrow_mut() {
@@ -1546,6 +3175,13 @@ impl SearchScreenRef {
         }
     }
 
+    /// Stops the screen reacting to input while a sheet covers it, without hiding it.
+    pub fn set_active(&self, active: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.is_active = active;
+        }
+    }
+
     pub fn set_refreshing(&self, cx: &mut Cx, refreshing: bool) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.is_refreshing = refreshing;
@@ -1560,12 +3196,67 @@ impl SearchScreenRef {
             inner.redraw(cx);
         }
     }
+
+    pub fn scroll_to_top(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.view.portal_list(ids!(list)).set_first_id_and_scroll(0, 0.0);
+            inner.redraw(cx);
+        }
+    }
+
+    /// Puts a search started elsewhere (a deep link) in the search bar.
+    pub fn set_query(&self, cx: &mut Cx, query: &str) {
+        if let Some(inner) = self.borrow() {
+            inner.view.search_bar(ids!(query_bar)).set_query(cx, query);
+        }
+    }
+
+    /// Names the place results are centered on ("Current Location" when following the device).
+    pub fn set_location_name(&self, cx: &mut Cx, name: &str) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.view.label(ids!(location_label)).set_text(cx, name);
+            inner.view.location_picker(ids!(location_picker)).set_open(cx, false);
+            inner.redraw(cx);
+        }
+    }
+
+    /// Reflects filters changed elsewhere (the filter sheet) in the filter bar.
+    pub fn set_filters(&self, cx: &mut Cx, filters: &SearchFilters) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.filters = filters.clone();
+            inner.sync_filter_bar(cx);
+        }
+    }
+
+    pub fn set_footer(&self, cx: &mut Cx, footer: ListFooter) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.footer = footer;
+            inner.redraw(cx);
+        }
+    }
+
+    /// Shows the results of a search; matches of `query` are highlighted in the cards.
+    pub fn set_results(&self, cx: &mut Cx, query: &str, businesses: Vec<Business>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            if inner.query != query {
+                inner.query = query.to_string();
+                inner.view.portal_list(ids!(list)).set_first_id_and_scroll(0, 0.0);
+            }
+            inner.businesses = businesses;
+            inner.show_empty_state = true;
+            inner.redraw(cx);
+        }
+    }
 }
 
 #[derive(Clone, Debug, DefaultNone)]
 pub enum SearchScreenAction {
     None,
     Refresh,
+    /// The list was scrolled near its end, or a failed page was tapped to retry
+    LoadMore,
+    OpenFilters,
+    FiltersChanged(SearchFilters),
 }
 
 #[derive(Live, LiveHook, Widget)]
@@ -1574,7 +3265,16 @@ pub struct MapScreen {
     #[animator] animator: Animator,
     #[live] visible: bool,
     #[rust] is_active: bool,
-    #[rust] markers_added: bool,
+    #[rust] businesses: Vec<Business>,
+    #[rust] clusters: Option<ClusterIndex>,
+    #[rust] markers: HashMap<LiveId, ClusterMarker>, // Markers on the map, by marker id
+    #[rust] marker_level: Option<usize>, // Cluster level the markers were synced at
+    #[rust] markers_dirty: bool, // Clusters or viewport changed since the markers were last synced
+    #[rust] dragging_map: bool, // A finger moved over the map since it went down
+    #[live(60.0)] swipe_threshold: f64, // Horizontal drag that moves the preview to the next business
+    #[rust] selected: Option<String>, // Business shown in the preview card
+    #[rust] preview_order: Vec<String>, // Ids the preview swipes through, nearest to the tapped marker first
+    #[rust] swipe_start: Option<DVec2>,
 }
 
 impl Widget for MapScreen {
@@ -1611,48 +3311,229 @@ impl Widget for MapScreen {
             _ => {}
         }
 
-        // Handle marker taps
+        // Handle marker taps: a business opens its details, a cluster zooms in until it splits
         let map = self.view.geo_map_view(ids!(map));
-        if let Some(marker_id) = map.marker_tapped(&actions) {
+        let marker_tapped = map.marker_tapped(&actions);
+        if let Some(marker_id) = marker_tapped {
             log!("Marker tapped: {:?}", marker_id);
-            // Find the business and emit action
-            let businesses = mock_businesses();
-            for business in businesses {
-                let business_live_id = LiveId::from_str(&business.id);
-                if marker_id == business_live_id {
-                    cx.widget_action(
-                        self.widget_uid(),
-                        &scope.path,
-                        BusinessCardAction::Clicked(business),
-                    );
-                    break;
+            match self.markers.get(&marker_id).cloned() {
+                Some(ClusterMarker { business_id: Some(id), .. }) => {
+                    self.preview_order = self.nearest_first(&id);
+                    self.select(cx, Some(id));
+                }
+                Some(marker) => {
+                    map.apply_over(cx, live! {
+                        center_lat: (marker.lat), center_lng: (marker.lng), zoom: (marker.expansion_zoom as f64)
+                    });
+                    self.markers_dirty = true;
+                    self.show_search_area_button(cx, true);
+                }
+                None => {}
+            }
+        }
+
+        // Swipe the preview sideways to step through nearby businesses; a tap opens details
+        let preview = self.view.view(ids!(preview_sheet));
+        let preview_rect = if self.selected.is_some() { preview.area().rect(cx) } else { Rect::default() };
+        let mut swiped = false;
+        match event.hits_with_capture_overload(cx, preview.area(), true) {
+            Hit::FingerDown(fe) => self.swipe_start = Some(fe.abs),
+            Hit::FingerMove(fe) => {
+                if let Some(start) = self.swipe_start {
+                    self.offset_preview(cx, fe.abs.x - start.x);
                 }
             }
+            Hit::FingerUp(fe) => {
+                if let Some(start) = self.swipe_start.take() {
+                    let dx = fe.abs.x - start.x;
+                    self.offset_preview(cx, 0.0);
+                    if dx.abs() >= self.swipe_threshold {
+                        swiped = true;
+                        self.step_preview(cx, if dx < 0.0 { 1 } else { -1 });
+                    }
+                }
+            }
+            _ => {}
+        }
+        // The card's own actions are captured above; pass them on unless the finger swiped
+        for action in actions.iter() {
+            match action.as_widget_action().cast() {
+                BusinessCardAction::Clicked(business) if !swiped => {
+                    cx.widget_action(self.widget_uid(), &scope.path, BusinessCardAction::Clicked(business));
+                }
+                BusinessCardAction::RetryImage(business) => {
+                    cx.widget_action(self.widget_uid(), &scope.path, BusinessCardAction::RetryImage(business));
+                }
+                _ => {}
+            }
+        }
+
+        // Offer "Search this area" after a pan or zoom; the map keeps the finger, so only watch
+        match event.hits_with_capture_overload(cx, map.area(), true) {
+            Hit::FingerMove(_) => self.dragging_map = true,
+            Hit::FingerUp(_) if std::mem::take(&mut self.dragging_map) => {
+                self.markers_dirty = true;
+                self.show_search_area_button(cx, true);
+            }
+            // A tap on empty map dismisses the preview
+            Hit::FingerUp(fe) if marker_tapped.is_none() && !preview_rect.contains(fe.abs) => {
+                self.select(cx, None);
+            }
+            Hit::FingerScroll(_) => {
+                // Regroup only when the zoom crosses into another cluster level
+                let level = map.borrow().map(|map| cluster::level_for_zoom(map.zoom));
+                self.markers_dirty |= level != self.marker_level;
+                self.show_search_area_button(cx, true);
+            }
+            _ => {}
+        }
+
+        if let Hit::FingerUp(fe) = event.hits(cx, self.view.view(ids!(search_area_button)).area()) {
+            if fe.is_over {
+                if let Some(bounds) = self.viewport_bounds(cx, 1.0) {
+                    cx.widget_action(self.widget_uid(), &scope.path, MapScreenAction::SearchArea(bounds));
+                }
+                self.show_search_area_button(cx, false);
+            }
         }
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
         if !self.visible { return DrawStep::done(); }
-        // Add markers on first draw
-        if !self.markers_added {
-            self.markers_added = true;
-            let businesses = mock_businesses();
-            let map = self.view.geo_map_view(ids!(map));
+        if std::mem::take(&mut self.markers_dirty) {
+            self.sync_markers(cx);
+        }
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
 
-            for business in &businesses {
-                let marker_id = LiveId::from_str(&business.id);
-                map.add_marker_with_label(
-                    cx,
-                    marker_id,
-                    business.lng,
-                    business.lat,
-                    &business.name,
-                    vec4(0.827, 0.137, 0.137, 1.0), // YELP_RED
-                );
+impl MapScreen {
+    /// Adds and removes only the markers that came or went at the current zoom and viewport.
+    /// Clustering itself happens off the UI thread; this just reads the prebuilt level.
+    fn sync_markers(&mut self, cx: &mut Cx) {
+        let map = self.view.geo_map_view(ids!(map));
+        let zoom = map.borrow().map_or(0.0, |map| map.zoom);
+        // A screen of margin on every side so short pans don't reveal missing pins
+        let bounds = self.viewport_bounds(cx, 3.0);
+        let wanted: HashMap<LiveId, ClusterMarker> = self.clusters
+            .iter()
+            .flat_map(|clusters| clusters.markers(zoom, bounds.as_ref()))
+            .into_iter()
+            .map(|marker| (LiveId::from_str(&marker.key), marker))
+            .collect();
+        // Bubble keys repeat across rebuilds, so a marker whose position or count changed is replaced too
+        self.markers.retain(|marker_id, shown| {
+            let keep = wanted.get(marker_id) == Some(shown);
+            if !keep {
+                map.remove_marker(cx, *marker_id);
             }
+            keep
+        });
+        for (marker_id, marker) in wanted {
+            if self.markers.contains_key(&marker_id) { continue; }
+            self.add_marker(cx, marker_id, &marker);
+            self.markers.insert(marker_id, marker);
         }
+        self.marker_level = Some(cluster::level_for_zoom(zoom));
+    }
 
-        self.view.draw_walk(cx, scope, walk)
+    fn add_marker(&self, cx: &mut Cx, marker_id: LiveId, marker: &ClusterMarker) {
+        let label = match &marker.business_id {
+            Some(id) => self.businesses.iter()
+                .find(|business| &business.id == id)
+                .map_or_else(String::new, |business| business.name.clone()),
+            None => marker.count.to_string(),
+        };
+        let color = match &marker.business_id {
+            Some(id) if self.selected.as_ref() == Some(id) => vec4(0.0, 0.451, 0.733, 1.0), // Blue for the previewed business
+            Some(_) => vec4(0.827, 0.137, 0.137, 1.0), // YELP_RED
+            None => vec4(0.553, 0.078, 0.078, 1.0), // Darker red so bubbles stand apart from pins
+        };
+        self.view.geo_map_view(ids!(map)).add_marker_with_label(cx, marker_id, marker.lng, marker.lat, &label, color);
+    }
+
+    /// Shows a business in the preview card and highlights its marker; None hides the card.
+    fn select(&mut self, cx: &mut Cx, business_id: Option<String>) {
+        let previous = std::mem::replace(&mut self.selected, business_id);
+        // Markers can't change color in place, so re-add the ones whose highlight changed
+        let changed = if previous != self.selected { [previous, self.selected.clone()] } else { [None, None] };
+        for id in changed.into_iter().flatten() {
+            let marker_id = LiveId::from_str(&id);
+            if let Some(marker) = self.markers.get(&marker_id) {
+                self.view.geo_map_view(ids!(map)).remove_marker(cx, marker_id);
+                self.add_marker(cx, marker_id, marker);
+            }
+        }
+        let business = self.selected.as_ref()
+            .and_then(|id| self.businesses.iter().find(|business| &business.id == id))
+            .cloned();
+        if let Some(business) = &business {
+            if let Some(mut card) = self.view.widget(ids!(preview_card)).borrow_mut::<BusinessCard>() {
+                card.set_business(business);
+            }
+            let position = self.preview_order.iter().position(|id| *id == business.id).unwrap_or(0);
+            let position_text = format!("{} of {} nearby", position + 1, self.preview_order.len().max(1));
+            self.view.label(ids!(preview_position)).set_text(cx, &position_text);
+        }
+        self.view.view(ids!(preview_sheet)).set_visible(cx, business.is_some());
+        self.redraw(cx);
+    }
+
+    /// Moves the preview `step` businesses along and pans the map to it.
+    fn step_preview(&mut self, cx: &mut Cx, step: isize) {
+        let position = self.selected.as_ref()
+            .and_then(|selected| self.preview_order.iter().position(|id| id == selected));
+        let next = position
+            .and_then(|position| position.checked_add_signed(step))
+            .and_then(|next| self.preview_order.get(next))
+            .cloned();
+        if let Some(business) = next.as_ref().and_then(|id| self.businesses.iter().find(|business| &business.id == id)) {
+            self.view.geo_map_view(ids!(map)).apply_over(cx, live! {
+                center_lat: (business.lat), center_lng: (business.lng)
+            });
+            self.markers_dirty = true;
+        }
+        if next.is_some() {
+            self.select(cx, next);
+        }
+    }
+
+    /// Slides the card with the finger while swiping.
+    fn offset_preview(&mut self, cx: &mut Cx, dx: f64) {
+        self.view.widget(ids!(preview_card)).apply_over(cx, live! {
+            margin: { left: (dx), right: (-dx) }
+        });
+        self.redraw(cx);
+    }
+
+    /// Up to `PREVIEW_NEARBY` business ids ordered by distance from `origin_id`, itself first.
+    fn nearest_first(&self, origin_id: &str) -> Vec<String> {
+        let origin = match self.businesses.iter().find(|business| business.id == origin_id) {
+            Some(origin) => origin,
+            None => return vec![origin_id.to_string()],
+        };
+        let mut by_distance: Vec<(f64, &str)> = self.businesses
+            .iter()
+            .map(|business| {
+                (geo::haversine_meters(origin.lat, origin.lng, business.lat, business.lng), business.id.as_str())
+            })
+            .collect();
+        by_distance.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| (a.1 != origin_id).cmp(&(b.1 != origin_id))));
+        by_distance.into_iter().take(PREVIEW_NEARBY).map(|(_, id)| id.to_string()).collect()
+    }
+
+    /// The part of the world on screen, grown `scale` times around its center.
+    fn viewport_bounds(&self, cx: &Cx, scale: f64) -> Option<Bounds> {
+        let map = self.view.geo_map_view(ids!(map));
+        let size = map.area().rect(cx).size;
+        if size.x <= 0.0 || size.y <= 0.0 { return None; }
+        let map = map.borrow()?;
+        Some(Bounds::from_viewport(map.center_lat, map.center_lng, map.zoom, size.x * scale, size.y * scale))
+    }
+
+    fn show_search_area_button(&mut self, cx: &mut Cx, show: bool) {
+        self.view.view(ids!(search_area_button)).set_visible(cx, show);
+        self.redraw(cx);
     }
 }
 
@@ -1670,12 +3551,279 @@ impl MapScreenRef {
             inner.redraw(cx);
         }
     }
+
+    /// Markers keep showing the previous businesses until the new clusters are built.
+    pub fn set_businesses(&self, cx: &mut Cx, businesses: Vec<Business>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            let points = businesses.iter()
+                .map(|business| MapPoint { id: business.id.clone(), lat: business.lat, lng: business.lng })
+                .collect();
+            cluster::build_in_background(points);
+            inner.businesses = businesses;
+            // Keep the preview if its business is still shown, refreshed with the new data
+            let inner = &mut *inner;
+            let businesses = &inner.businesses;
+            inner.preview_order.retain(|id| businesses.iter().any(|business| business.id == *id));
+            let selected = inner.selected.clone()
+                .filter(|id| businesses.iter().any(|business| business.id == *id));
+            inner.select(cx, selected);
+            inner.redraw(cx);
+        }
+    }
+
+    /// Picks up clusters finished in the background. Call on `ClusterAction::Ready`.
+    pub fn clusters_ready(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            if let Some(clusters) = cluster::take_built() {
+                log!("Clustered {} businesses", clusters.len());
+                inner.clusters = Some(clusters);
+                inner.markers_dirty = true;
+                inner.redraw(cx);
+            }
+        }
+    }
+
+    /// Moves the viewport to a neighborhood-level view around `lat`/`lng`.
+    pub fn set_center(&self, cx: &mut Cx, lat: f64, lng: f64) {
+        self.set_view(cx, lat, lng, NEIGHBORHOOD_ZOOM);
+    }
+
+    pub fn set_view(&self, cx: &mut Cx, lat: f64, lng: f64, zoom: f64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.view.geo_map_view(ids!(map)).apply_over(cx, live! {
+                center_lat: (lat), center_lng: (lng), zoom: (zoom)
+            });
+            inner.markers_dirty = true;
+            inner.show_search_area_button(cx, false);
+        }
+    }
 }
 
 #[derive(Clone, Debug, DefaultNone)]
 pub enum MapScreenAction {
     None,
     Back,
+    /// "Search this area" tapped; the bounds are what the map shows
+    SearchArea(Bounds),
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct RouteLine {
+    #[redraw] #[live] draw_segment: DrawQuad,
+    #[walk] walk: Walk,
+    #[live(5.0)] line_width: f64,
+    #[rust] points: Vec<(f64, f64)>,
+    #[rust] viewport: (f64, f64, f64), // Center latitude, longitude and zoom of the map underneath
+}
+
+impl Widget for RouteLine {
+    fn handle_event(&mut self, _cx: &mut Cx, _event: &Event, _scope: &mut Scope) {}
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk: Walk) -> DrawStep {
+        let rect = cx.walk_turtle(walk);
+        let (center_lat, center_lng, zoom) = self.viewport;
+        let world = geo::TILE_SIZE * 2f64.powf(zoom);
+        let (center_x, center_y) = geo::mercator_project(center_lat, center_lng);
+        let to_screen = |&(lat, lng): &(f64, f64)| {
+            let (x, y) = geo::mercator_project(lat, lng);
+            rect.center() + dvec2((x - center_x) * world, (y - center_y) * world)
+        };
+        let pad = dvec2(self.line_width, self.line_width);
+        for pair in self.points.windows(2) {
+            let (start, end) = (to_screen(&pair[0]), to_screen(&pair[1]));
+            let min = dvec2(start.x.min(end.x), start.y.min(end.y)) - pad;
+            let max = dvec2(start.x.max(end.x), start.y.max(end.y)) + pad;
+            let segment = Rect { pos: min, size: max - min };
+            if !segment.intersects(rect) { continue; }
+            let (start, end) = (start - min, end - min);
+            self.draw_segment.apply_over(cx, live! {
+                seg_start: (vec2(start.x as f32, start.y as f32))
+                seg_end: (vec2(end.x as f32, end.y as f32))
+                line_width: (self.line_width)
+            });
+            self.draw_segment.draw_abs(cx, segment);
+        }
+        DrawStep::done()
+    }
+}
+
+impl RouteLine {
+    /// Follows the map's current center and zoom so the line stays on the streets.
+    pub fn set_viewport(&mut self, center_lat: f64, center_lng: f64, zoom: f64) {
+        self.viewport = (center_lat, center_lng, zoom);
+    }
+
+    pub fn set_points(&mut self, points: Vec<(f64, f64)>) {
+        self.points = points;
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct RouteScreen {
+    #[deref] view: View,
+    #[live] visible: bool,
+    #[rust] business: Option<Business>,
+    #[rust] routes: Vec<Route>, // One per travel mode
+    #[rust] mode: TravelMode,
+    #[rust] fit_pending: bool, // Zoom to the route once the map has a size
+}
+
+impl Widget for RouteScreen {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if !self.visible { return; }
+        let actions = cx.capture_actions(|cx| self.view.handle_event(cx, event, scope));
+
+        if self.view.button(ids!(back_button)).clicked(&actions) {
+            cx.widget_action(self.widget_uid(), &scope.path, RouteScreenAction::Back);
+        }
+
+        let mode_chips = [
+            (ids!(walk_chip), TravelMode::Walk),
+            (ids!(drive_chip), TravelMode::Drive),
+            (ids!(transit_chip), TravelMode::Transit),
+        ];
+        for (chip_id, mode) in mode_chips {
+            if chip_tapped(cx, event, &self.view.view(chip_id)) && mode != self.mode {
+                self.mode = mode;
+                self.redraw(cx);
+            }
+        }
+
+        let app_chips = [
+            (ids!(open_geo), ExternalMap::Geo),
+            (ids!(open_google), ExternalMap::Google),
+            (ids!(open_apple), ExternalMap::Apple),
+        ];
+        for (chip_id, app) in app_chips {
+            if chip_tapped(cx, event, &self.view.view(chip_id)) {
+                if let Some(business) = &self.business {
+                    let url = app.directions_url(business.lat, business.lng, &business.name, self.mode);
+                    cx.widget_action(self.widget_uid(), &scope.path, RouteScreenAction::OpenExternal(url));
+                }
+            }
+        }
+
+        // The line is drawn over the map, so it has to follow every pan, zoom and fling
+        let map_area = self.view.geo_map_view(ids!(route_map)).area();
+        let map_moved = matches!(
+            event.hits_with_capture_overload(cx, map_area, true),
+            Hit::FingerMove(_) | Hit::FingerScroll(_)
+        );
+        if map_moved || matches!(event, Event::NextFrame(_)) {
+            self.view.widget(ids!(route_line)).redraw(cx);
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if !self.visible { return DrawStep::done(); }
+
+        let mode_chips = [
+            (ids!(walk_chip), TravelMode::Walk),
+            (ids!(drive_chip), TravelMode::Drive),
+            (ids!(transit_chip), TravelMode::Transit),
+        ];
+        for (chip_id, mode) in mode_chips {
+            let chip = self.view.view(chip_id);
+            style_chip(cx, &chip, mode == self.mode);
+            if let Some(route) = self.routes.iter().find(|route| route.mode == mode) {
+                chip.label(ids!(label)).set_text(cx, &format!("{} · {}", mode.label(), route.eta_text()));
+            }
+        }
+
+        let route = self.routes.iter().find(|route| route.mode == self.mode);
+        let summary = route.map_or_else(String::new, |route| {
+            format!("{} · {} by {}", route.distance_text(), route.eta_text(), route.mode.label().to_lowercase())
+        });
+        self.view.label(ids!(route_summary)).set_text(cx, &summary);
+
+        let map = self.view.geo_map_view(ids!(route_map));
+        if let Some(map) = map.borrow() {
+            if let Some(mut line) = self.view.widget(ids!(route_line)).borrow_mut::<RouteLine>() {
+                line.set_viewport(map.center_lat, map.center_lng, map.zoom);
+                line.set_points(route.map_or_else(Vec::new, |route| route.points.clone()));
+            }
+        }
+
+        let step = self.view.draw_walk(cx, scope, walk);
+        if self.fit_pending {
+            self.fit_route(cx);
+        }
+        step
+    }
+}
+
+impl RouteScreen {
+    /// Keeps the business centered and zooms out until the whole route is on screen.
+    fn fit_route(&mut self, cx: &mut Cx) {
+        let business = match &self.business {
+            Some(business) => business,
+            None => return,
+        };
+        let map = self.view.geo_map_view(ids!(route_map));
+        let size = map.area().rect(cx).size;
+        if size.x <= 0.0 || size.y <= 0.0 { return; }
+        self.fit_pending = false;
+
+        let (center_x, center_y) = geo::mercator_project(business.lat, business.lng);
+        let (mut extent_x, mut extent_y) = (0.0f64, 0.0f64);
+        for route in &self.routes {
+            for &(lat, lng) in &route.points {
+                let (x, y) = geo::mercator_project(lat, lng);
+                extent_x = extent_x.max((x - center_x).abs());
+                extent_y = extent_y.max((y - center_y).abs());
+            }
+        }
+        // Leave room for the header and the summary panel
+        let fit = |half_size: f64, extent: f64| (half_size * 0.6 / (extent.max(1e-9) * geo::TILE_SIZE)).log2();
+        let zoom = fit(size.x * 0.5, extent_x).min(fit(size.y * 0.5, extent_y)).clamp(3.0, ROUTE_MAX_ZOOM);
+        map.apply_over(cx, live! {
+            center_lat: (business.lat), center_lng: (business.lng), zoom: (zoom)
+        });
+        self.redraw(cx);
+    }
+}
+
+impl RouteScreenRef {
+    /// Shows `routes` (one per travel mode) from `origin` to `business`, walking first.
+    pub fn open(&self, cx: &mut Cx, business: &Business, origin: (f64, f64), routes: Vec<Route>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.view.label(ids!(route_business)).set_text(cx, &business.name);
+            let map = inner.view.geo_map_view(ids!(route_map));
+            map.apply_over(cx, live! { center_lat: (business.lat), center_lng: (business.lng) });
+            map.remove_marker(cx, live_id!(route_origin));
+            map.remove_marker(cx, live_id!(route_destination));
+            map.add_marker_with_label(cx, live_id!(route_origin), origin.1, origin.0, "You", vec4(0.0, 0.451, 0.733, 1.0));
+            map.add_marker_with_label(
+                cx,
+                live_id!(route_destination),
+                business.lng,
+                business.lat,
+                &business.name,
+                vec4(0.827, 0.137, 0.137, 1.0), // YELP_RED
+            );
+            inner.business = Some(business.clone());
+            inner.routes = routes;
+            inner.mode = TravelMode::Walk;
+            inner.fit_pending = true;
+            inner.visible = true;
+            inner.redraw(cx);
+        }
+    }
+
+    pub fn set_visible(&self, cx: &mut Cx, visible: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.visible = visible;
+            inner.redraw(cx);
+        }
+    }
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum RouteScreenAction {
+    None,
+    Back,
+    /// Directions URL for another app, in the travel mode picked
+    OpenExternal(String),
 }
 
 #[derive(Live, LiveHook, Widget)]
@@ -1685,7 +3833,15 @@ pub struct BusinessDetailScreen {
     #[live] visible: bool,
     #[rust] is_active: bool,
     #[rust] business: Option<Business>,
-    #[rust] image_loaded: bool,
+    #[rust] image_generation: Option<u64>, // Generation of the hero texture shown
+    #[rust] reviews: Vec<Review>,
+    #[rust] reviews_total: usize,
+    #[rust] review_sort: ReviewSort,
+    #[rust(ReviewSort::ALL.to_vec())] review_sorts: Vec<ReviewSort>, // Orders the provider can page through
+    #[rust] reviews_loading: bool,
+    #[rust] reviews_failed: bool, // The last page asked for could not be loaded
+    #[rust] review_notice: Option<String>, // e.g. why a submitted review was not accepted
+    #[rust] link_copied: bool, // Share was tapped for the business shown
 }
 
 impl Widget for BusinessDetailScreen {
@@ -1713,12 +3869,13 @@ impl Widget for BusinessDetailScreen {
                 dir_btn.redraw(cx);
             }
             Hit::FingerUp(fe) => {
-                // Keep pressed state visible briefly before action
+                // The detail screen is still there after the route view closes, so release first
+                dir_btn.apply_over(cx, live!{ draw_bg: { pressed: 0.0 } });
+                dir_btn.redraw(cx);
                 if fe.is_over {
-                    cx.widget_action(self.widget_uid(), &scope.path, DetailScreenAction::Directions);
-                } else {
-                    dir_btn.apply_over(cx, live!{ draw_bg: { pressed: 0.0 } });
-                    dir_btn.redraw(cx);
+                    if let Some(business) = self.business.clone() {
+                        cx.widget_action(self.widget_uid(), &scope.path, DetailScreenAction::Directions(business));
+                    }
                 }
             }
             Hit::FingerHoverIn(_) => {
@@ -1731,6 +3888,96 @@ impl Widget for BusinessDetailScreen {
             }
             _ => {}
         }
+
+        // Handle call button
+        let call_btn = self.view.view(ids!(call_button));
+        match event.hits(cx, call_btn.area()) {
+            Hit::FingerDown(_) => {
+                call_btn.apply_over(cx, live!{ draw_bg: { pressed: 1.0 } });
+                call_btn.redraw(cx);
+            }
+            Hit::FingerUp(fe) => {
+                call_btn.apply_over(cx, live!{ draw_bg: { pressed: 0.0 } });
+                call_btn.redraw(cx);
+                if fe.is_over {
+                    if let Some(number) = self.business.as_ref().and_then(Business::dial_number) {
+                        cx.widget_action(self.widget_uid(), &scope.path, DetailScreenAction::Call(number));
+                    }
+                }
+            }
+            Hit::FingerHoverIn(_) => {
+                call_btn.apply_over(cx, live!{ draw_bg: { hover: 1.0 } });
+                call_btn.redraw(cx);
+            }
+            Hit::FingerHoverOut(_) => {
+                call_btn.apply_over(cx, live!{ draw_bg: { hover: 0.0 } });
+                call_btn.redraw(cx);
+            }
+            _ => {}
+        }
+
+        // Handle share button
+        let share_btn = self.view.view(ids!(share_button));
+        match event.hits(cx, share_btn.area()) {
+            Hit::FingerDown(_) => {
+                share_btn.apply_over(cx, live!{ draw_bg: { pressed: 1.0 } });
+                share_btn.redraw(cx);
+            }
+            Hit::FingerUp(fe) => {
+                share_btn.apply_over(cx, live!{ draw_bg: { pressed: 0.0 } });
+                share_btn.redraw(cx);
+                if fe.is_over {
+                    if let Some(business) = self.business.clone() {
+                        cx.widget_action(self.widget_uid(), &scope.path, DetailScreenAction::Share(business));
+                    }
+                }
+            }
+            Hit::FingerHoverIn(_) => {
+                share_btn.apply_over(cx, live!{ draw_bg: { hover: 1.0 } });
+                share_btn.redraw(cx);
+            }
+            Hit::FingerHoverOut(_) => {
+                share_btn.apply_over(cx, live!{ draw_bg: { hover: 0.0 } });
+                share_btn.redraw(cx);
+            }
+            _ => {}
+        }
+
+        // Review sort chips and paging
+        let sort_chips = [
+            (ids!(sort_newest), ReviewSort::Newest),
+            (ids!(sort_highest), ReviewSort::Highest),
+            (ids!(sort_lowest), ReviewSort::Lowest),
+        ];
+        for (chip_id, sort) in sort_chips {
+            if chip_tapped(cx, event, &self.view.view(chip_id)) && sort != self.review_sort {
+                cx.widget_action(self.widget_uid(), &scope.path, DetailScreenAction::SortReviews(sort));
+            }
+        }
+        // Tapping the hero opens the photo full screen; a drag just scrolls
+        if let Hit::FingerUp(fe) = event.hits(cx, self.view.view(ids!(hero_container)).area()) {
+            if fe.is_over && fe.was_tap() {
+                if let Some(business) = self.business.clone() {
+                    cx.widget_action(self.widget_uid(), &scope.path, DetailScreenAction::ViewPhoto(business));
+                }
+            }
+        }
+        if chip_tapped(cx, event, &self.view.view(ids!(write_review))) {
+            if let Some(business) = self.business.clone() {
+                cx.widget_action(self.widget_uid(), &scope.path, DetailScreenAction::WriteReview(business));
+            }
+        }
+        if chip_tapped(cx, event, &self.view.view(ids!(more_reviews))) && !self.reviews_loading {
+            self.reviews_loading = true;
+            // After a failure the same chip asks for the failed page again
+            let action = if std::mem::take(&mut self.reviews_failed) {
+                DetailScreenAction::RetryReviews
+            } else {
+                DetailScreenAction::MoreReviews
+            };
+            cx.widget_action(self.widget_uid(), &scope.path, action);
+            self.sync_reviews_section(cx);
+        }
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
@@ -1746,30 +3993,45 @@ impl Widget for BusinessDetailScreen {
             self.view.label(ids!(meta_label)).set_text(cx, &business.price_and_categories());
             self.view.label(ids!(location_label)).set_text(cx, &business.distance_text());
 
+            // Contact rows are hidden when the listing lacks them
+            self.view.view(ids!(address_row)).set_visible(cx, !business.address.is_empty());
+            self.view.label(ids!(address_label)).set_text(cx, &business.address_text());
+            let phone = business.phone_text();
+            self.view.view(ids!(phone_row)).set_visible(cx, phone.is_some());
+            self.view.label(ids!(phone_label)).set_text(cx, phone.as_deref().unwrap_or(""));
+            self.view.view(ids!(call_button)).set_visible(cx, business.dial_number().is_some());
+            self.view.label(ids!(share_label)).set_text(cx, if self.link_copied { "Copied" } else { "Share" });
+            self.view.view(ids!(website_row)).set_visible(cx, business.url.is_some());
+            self.view.label(ids!(website_label)).set_text(cx, business.url.as_deref().unwrap_or(""));
+
+            self.view.label(ids!(description)).set_text(cx, &business.about_text());
+            self.view.label(ids!(hours_label)).set_text(cx, &business.hours_text());
+            let now = hours::now_unix();
+            let status_label = self.view.label(ids!(open_status_label));
+            status_label.set_text(cx, &business.open_status_text_at(now).unwrap_or_default());
+            let status_color = if business.is_open_at(now) {
+                vec4(0.0, 0.54, 0.0, 1.0)
+            } else {
+                vec4(0.827, 0.137, 0.137, 1.0)
+            };
+            status_label.apply_over(cx, live! { draw_text: { color: (status_color) } });
+
             // Set star rating
             self.view.star_rating(ids!(stars)).set_rating(cx, business.rating);
 
-            // Load hero image from network if available
-            if !self.image_loaded {
-                if let Ok(images) = RESTAURANT_IMAGES.lock() {
-                    if let Some(image_data) = images.get(&business.id) {
-                        let hero = self.view.image(ids!(hero_image));
-                        let hero_bg = self.view.image(ids!(hero_bg));
-                        if hero.load_jpg_from_data(cx, image_data).is_ok()
-                            || hero.load_png_from_data(cx, image_data).is_ok() {
-                            // Also load into background for blur effect
-                            let _ = hero_bg.load_jpg_from_data(cx, image_data)
-                                .or_else(|_| hero_bg.load_png_from_data(cx, image_data));
-                            self.image_loaded = true;
-                        }
-                    }
+            // Full size is decoded the first time the hero asks for it; same texture for the blurred background
+            if let Some((texture, generation)) = image_pipeline::texture(&business.id, ImageSize::Full) {
+                if self.image_generation != Some(generation) {
+                    self.view.image(ids!(hero_image)).set_texture(cx, Some(texture.clone()));
+                    self.view.image(ids!(hero_bg)).set_texture(cx, Some(texture));
+                    self.image_generation = Some(generation);
                 }
             }
 
             // Show/hide spinner based on image loading state
             // Set size to 0 when hidden to completely remove it
             let spinner = self.view.view(ids!(hero_spinner));
-            if self.image_loaded {
+            if self.image_generation.is_some() || image_pipeline::load_error(&business.id).is_some() {
                 spinner.apply_over(cx, live! {
                     width: 0.0, height: 0.0
                     draw_bg: { opacity: 0.0 }
@@ -1781,15 +4043,62 @@ impl Widget for BusinessDetailScreen {
                 });
             }
         }
-
         self.view.draw_walk(cx, scope, walk)
     }
 }
 
 impl BusinessDetailScreen {
+    fn sync_review_list(&mut self, cx: &mut Cx) {
+        self.view.review_list(ids!(review_list)).set_reviews(cx, self.reviews.clone());
+        self.sync_reviews_section(cx);
+        self.redraw(cx);
+    }
+
+    /// Updates the title, sort chips, status and "More reviews" chip; called when
+    /// the review state changes rather than while drawing, since each setter queues a redraw.
+    fn sync_reviews_section(&mut self, cx: &mut Cx) {
+        let title = match self.reviews_total {
+            0 => "Reviews".to_string(),
+            total => format!("Reviews ({})", total),
+        };
+        self.view.label(ids!(reviews_title)).set_text(cx, &title);
+        self.view.view(ids!(review_sort_chips)).set_visible(cx, self.review_sorts.len() > 1);
+        let sort_chips = [
+            (ids!(sort_newest), ReviewSort::Newest),
+            (ids!(sort_highest), ReviewSort::Highest),
+            (ids!(sort_lowest), ReviewSort::Lowest),
+        ];
+        for (chip_id, sort) in sort_chips {
+            let chip = self.view.view(chip_id);
+            chip.set_visible(cx, self.review_sorts.contains(&sort));
+            set_chip_selected(cx, &chip, self.review_sort == sort);
+        }
+
+        let status = if let Some(ref notice) = self.review_notice {
+            notice.as_str()
+        } else if self.reviews_loading {
+            "Loading reviews..."
+        } else if self.reviews_failed {
+            "Couldn't load reviews"
+        } else if self.reviews.is_empty() {
+            "No reviews yet"
+        } else {
+            ""
+        };
+        self.view.widget(ids!(reviews_status)).set_visible(cx, !status.is_empty());
+        self.view.label(ids!(reviews_status)).set_text(cx, status);
+        let has_more = !self.reviews_loading && (self.reviews_failed || self.reviews.len() < self.reviews_total);
+        let more_reviews = self.view.view(ids!(more_reviews));
+        more_reviews.set_visible(cx, has_more);
+        more_reviews.label(ids!(label)).set_text(cx, if self.reviews_failed { "Try again" } else { "More reviews" });
+    }
+
     pub fn set_business(&mut self, business: &Business) {
+        if self.business.as_ref().map(|b| &b.id) != Some(&business.id) {
+            self.image_generation = None; // Reset so image loads for new business
+            self.link_copied = false;
+        }
         self.business = Some(business.clone());
-        self.image_loaded = false; // Reset so image loads for new business
     }
 }
 
@@ -1801,6 +4110,95 @@ impl BusinessDetailScreenRef {
         }
     }
 
+    /// Confirms on the Share button that the link is on the clipboard.
+    pub fn show_link_copied(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.link_copied = true;
+            inner.redraw(cx);
+        }
+    }
+
+    /// Limits the sort chips to the orders the provider supports.
+    pub fn set_review_sorts(&self, cx: &mut Cx, sorts: &[ReviewSort]) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.review_sorts = sorts.to_vec();
+            inner.sync_reviews_section(cx);
+        }
+    }
+
+    /// Clears the reviews and shows the loading state for a new sort or business.
+    pub fn start_reviews(&self, cx: &mut Cx, sort: ReviewSort) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.reviews.clear();
+            inner.reviews_total = 0;
+            inner.review_sort = sort;
+            inner.reviews_loading = true;
+            inner.reviews_failed = false;
+            inner.review_notice = None;
+            inner.sync_review_list(cx);
+        }
+    }
+
+    /// Puts a just-written review at the top while the provider stores it.
+    pub fn insert_review(&self, cx: &mut Cx, review: Review) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.reviews.insert(0, review);
+            inner.reviews_total += 1;
+            inner.review_notice = None;
+            inner.sync_review_list(cx);
+        }
+    }
+
+    /// Swaps the optimistic copy for the review the provider stored.
+    pub fn replace_review(&self, cx: &mut Cx, local_id: &str, review: Review) {
+        if let Some(mut inner) = self.borrow_mut() {
+            if let Some(existing) = inner.reviews.iter_mut().find(|existing| existing.id == local_id) {
+                *existing = review;
+            }
+            inner.sync_review_list(cx);
+        }
+    }
+
+    /// Drops an optimistic review the provider rejected and says why.
+    pub fn remove_review(&self, cx: &mut Cx, local_id: &str, notice: String) {
+        if let Some(mut inner) = self.borrow_mut() {
+            let before = inner.reviews.len();
+            inner.reviews.retain(|review| review.id != local_id);
+            if inner.reviews.len() < before {
+                inner.reviews_total = inner.reviews_total.saturating_sub(1);
+            }
+            inner.review_notice = Some(notice);
+            inner.sync_review_list(cx);
+        }
+    }
+
+    /// Shows a page of reviews; the first page replaces what was there.
+    pub fn add_reviews(&self, cx: &mut Cx, request: &ReviewsRequest, page: ReviewPage) {
+        if let Some(mut inner) = self.borrow_mut() {
+            if request.offset == 0 {
+                inner.reviews.clear();
+            }
+            for review in page.reviews {
+                if !inner.reviews.iter().any(|existing| existing.id == review.id) {
+                    inner.reviews.push(review);
+                }
+            }
+            inner.reviews_total = page.total;
+            inner.reviews_loading = false;
+            inner.reviews_failed = false;
+            inner.sync_review_list(cx);
+        }
+    }
+
+    /// Ends the loading state after a failed page and offers to try again.
+    pub fn reviews_failed(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.reviews_loading = false;
+            inner.reviews_failed = true;
+            inner.sync_reviews_section(cx);
+        }
+    }
+
     pub fn set_visible(&self, cx: &mut Cx, visible: bool) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.is_active = visible;
@@ -1820,23 +4218,426 @@ impl BusinessDetailScreenRef {
 }
 
 #[derive(Clone, Debug, DefaultNone)]
-pub enum DetailScreenAction {
+pub enum DetailScreenAction {
+    None,
+    Back,
+    /// E.164 number to dial
+    Call(String),
+    Directions(Business),
+    SortReviews(ReviewSort),
+    MoreReviews,
+    RetryReviews,
+    WriteReview(Business),
+    ViewPhoto(Business),
+    Share(Business),
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct PhotoScreen {
+    #[deref] view: View,
+    #[live] visible: bool,
+    #[rust] business: Option<Business>,
+    #[rust] image_generation: Option<u64>, // Generation of the texture shown
+}
+
+impl Widget for PhotoScreen {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if !self.visible { return; }
+        let actions = cx.capture_actions(|cx| self.view.handle_event(cx, event, scope));
+        if self.view.button(ids!(back_button)).clicked(&actions) {
+            cx.widget_action(self.widget_uid(), &scope.path, PhotoScreenAction::Back);
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if !self.visible { return DrawStep::done(); }
+        if let Some(business) = &self.business {
+            // Full size decode shared with the detail hero
+            if let Some((texture, generation)) = image_pipeline::texture(&business.id, ImageSize::Full) {
+                if self.image_generation != Some(generation) {
+                    self.view.image(ids!(photo_image)).set_texture(cx, Some(texture));
+                    self.image_generation = Some(generation);
+                }
+            }
+        }
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+impl PhotoScreenRef {
+    pub fn open(&self, cx: &mut Cx, business: &Business) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.view.label(ids!(photo_title)).set_text(cx, &business.name);
+            if inner.business.as_ref().map(|shown| &shown.id) != Some(&business.id) {
+                inner.view.image(ids!(photo_image)).set_texture(cx, None);
+                inner.image_generation = None;
+            }
+            inner.business = Some(business.clone());
+            inner.visible = true;
+            inner.redraw(cx);
+        }
+    }
+
+    pub fn set_visible(&self, cx: &mut Cx, visible: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.visible = visible;
+            inner.redraw(cx);
+        }
+    }
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum PhotoScreenAction {
+    None,
+    Back,
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct ReviewComposeScreen {
+    #[deref] view: View,
+    #[live] visible: bool,
+    #[live(1.0)] autosave_secs: f64,
+    #[rust] business: Option<Business>,
+    #[rust] draft: ReviewDraft,
+    #[rust] autosave_timer: Timer,
+    #[rust] draft_saved: bool,
+    #[rust] error: Option<String>,
+}
+
+impl Widget for ReviewComposeScreen {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if !self.visible { return; }
+        let actions = cx.capture_actions(|cx| self.view.handle_event(cx, event, scope));
+
+        if let Some(rating) = self.view.star_rating(ids!(rating_input)).changed(&actions) {
+            self.draft.rating = rating;
+            self.draft_changed(cx);
+        }
+        if let Some(text) = self.view.text_input(ids!(review_input)).changed(&actions) {
+            self.draft.text = text;
+            self.draft_changed(cx);
+        }
+        if self.autosave_timer.is_event(event).is_some() {
+            self.save_draft(cx);
+        }
+
+        if self.view.button(ids!(cancel_button)).clicked(&actions) {
+            self.save_draft(cx);
+            cx.widget_action(self.widget_uid(), &scope.path, ReviewComposeAction::Cancel);
+        }
+
+        if let Hit::FingerUp(fe) = event.hits(cx, self.view.view(ids!(submit_button)).area()) {
+            if fe.is_over {
+                self.submit(cx, scope);
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if !self.visible { return DrawStep::done(); }
+        if let Some(ref business) = self.business {
+            self.view.label(ids!(business_name)).set_text(cx, &business.name);
+        }
+        self.view.star_rating(ids!(rating_input)).set_rating(cx, self.draft.rating);
+
+        let len = self.draft.text.trim().chars().count();
+        let count = if len < reviews::MIN_REVIEW_CHARS {
+            format!("{} characters ({} minimum)", len, reviews::MIN_REVIEW_CHARS)
+        } else {
+            format!("{} characters", len)
+        };
+        self.view.label(ids!(char_count)).set_text(cx, &count);
+        self.view.widget(ids!(error_label)).set_visible(cx, self.error.is_some());
+        self.view.label(ids!(error_label)).set_text(cx, self.error.as_deref().unwrap_or(""));
+        self.view.widget(ids!(draft_label)).set_visible(cx, self.draft_saved && !self.draft.is_empty());
+
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+impl ReviewComposeScreen {
+    fn draft_changed(&mut self, cx: &mut Cx) {
+        self.draft.updated = hours::now_unix();
+        self.draft_saved = false;
+        self.error = None;
+        // Write to disk once typing pauses
+        cx.stop_timer(self.autosave_timer);
+        self.autosave_timer = cx.start_timeout(self.autosave_secs);
+        self.redraw(cx);
+    }
+
+    fn save_draft(&mut self, cx: &mut Cx) {
+        cx.stop_timer(self.autosave_timer);
+        if self.draft.business_id.is_empty() { return; }
+        reviews::save_draft(&self.draft);
+        self.draft_saved = true;
+        self.redraw(cx);
+    }
+
+    fn submit(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        if let Err(err) = reviews::validate(self.draft.rating, &self.draft.text) {
+            self.error = Some(err.to_string());
+            self.redraw(cx);
+            return;
+        }
+        let review = NewReview {
+            local_id: format!("local-{:016x}", LiveId::unique().0),
+            business_id: self.draft.business_id.clone(),
+            author: "You".to_string(),
+            rating: self.draft.rating,
+            text: self.draft.text.trim().to_string(),
+        };
+        // The draft is restored by the app if the provider rejects the review
+        cx.stop_timer(self.autosave_timer);
+        reviews::delete_draft(&review.business_id);
+        self.draft = ReviewDraft { business_id: review.business_id.clone(), ..Default::default() };
+        self.view.text_input(ids!(review_input)).set_text(cx, "");
+        cx.widget_action(self.widget_uid(), &scope.path, ReviewComposeAction::Submit(review));
+    }
+}
+
+impl ReviewComposeScreenRef {
+    /// Shows the composer for `business`, restoring any saved draft.
+    pub fn open(&self, cx: &mut Cx, business: &Business) {
+        if let Some(mut inner) = self.borrow_mut() {
+            let draft = reviews::load_draft(&business.id);
+            inner.draft_saved = draft.is_some();
+            inner.draft = draft.unwrap_or_else(|| ReviewDraft { business_id: business.id.clone(), ..Default::default() });
+            inner.business = Some(business.clone());
+            inner.error = None;
+            let text = inner.draft.text.clone();
+            inner.view.text_input(ids!(review_input)).set_text(cx, &text);
+            inner.visible = true;
+            inner.redraw(cx);
+        }
+    }
+
+    pub fn set_visible(&self, cx: &mut Cx, visible: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.visible = visible;
+            inner.redraw(cx);
+        }
+    }
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum ReviewComposeAction {
+    None,
+    Cancel,
+    Submit(NewReview),
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct FilterSheet {
+    #[deref] view: View,
+    #[live] visible: bool,
+    #[rust] filters: SearchFilters, // Edited copy, applied on "Show results"
+    #[rust] businesses: Vec<Business>, // Unfiltered results, for the live result count
+}
+
+impl Widget for FilterSheet {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if !self.visible { return; }
+        let actions = cx.capture_actions(|cx| self.view.handle_event(cx, event, scope));
+
+        if let Hit::FingerUp(fe) = event.hits(cx, self.view.view(ids!(backdrop)).area()) {
+            if fe.is_over {
+                cx.widget_action(self.widget_uid(), &scope.path, FilterSheetAction::Close);
+                return;
+            }
+        }
+        if let Hit::FingerUp(fe) = event.hits(cx, self.view.view(ids!(apply_button)).area()) {
+            if fe.is_over {
+                cx.widget_action(self.widget_uid(), &scope.path, FilterSheetAction::Apply(self.filters.clone()));
+                return;
+            }
+        }
+
+        let mut changed = false;
+        if chip_tapped(cx, event, &self.view.view(ids!(reset_chip))) {
+            self.filters = SearchFilters::default();
+            changed = true;
+        }
+        let price_chips = [(1, ids!(sheet_price_1)), (2, ids!(sheet_price_2)), (3, ids!(sheet_price_3)), (4, ids!(sheet_price_4))];
+        for (level, chip) in price_chips {
+            if chip_tapped(cx, event, &self.view.view(chip)) {
+                self.filters.toggle_price(level);
+                changed = true;
+            }
+        }
+        for (rating, chip) in Self::rating_chips() {
+            if chip_tapped(cx, event, &self.view.view(chip)) {
+                self.filters.min_rating = rating;
+                changed = true;
+            }
+        }
+        for (distance, chip) in Self::distance_chips() {
+            if chip_tapped(cx, event, &self.view.view(chip)) {
+                self.filters.distance = distance;
+                changed = true;
+            }
+        }
+        if chip_tapped(cx, event, &self.view.view(ids!(sheet_open_now))) {
+            self.filters.open_now = !self.filters.open_now;
+            changed = true;
+        }
+        if let Some(category) = self.view.category_chips(ids!(categories)).toggled(&actions) {
+            self.filters.toggle_category(&category);
+            changed = true;
+        }
+        if changed {
+            self.sync_chips(cx);
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if !self.visible { return DrawStep::done(); }
+        let now = hours::now_unix();
+        let count = self.filters.apply(&self.businesses, |business| business.is_open_at(now)).len();
+        let text = match count {
+            0 => "No matching results".to_string(),
+            1 => "Show 1 result".to_string(),
+            count => format!("Show {} results", count),
+        };
+        self.view.label(ids!(apply_label)).set_text(cx, &text);
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+impl FilterSheet {
+    fn rating_chips() -> [(Option<f32>, &'static [LiveId]); 4] {
+        [
+            (None, ids!(rating_any)),
+            (Some(search::RATING_OPTIONS[0]), ids!(rating_35)),
+            (Some(search::RATING_OPTIONS[1]), ids!(rating_40)),
+            (Some(search::RATING_OPTIONS[2]), ids!(rating_45)),
+        ]
+    }
+
+    fn distance_chips() -> [(Option<DistanceLimit>, &'static [LiveId]); 4] {
+        [
+            (None, ids!(distance_any)),
+            (Some(DistanceLimit::Walking), ids!(distance_walking)),
+            (Some(DistanceLimit::Biking), ids!(distance_biking)),
+            (Some(DistanceLimit::Driving), ids!(distance_driving)),
+        ]
+    }
+
+    fn sync_chips(&mut self, cx: &mut Cx) {
+        let filters = self.filters.clone();
+        let price_chips = [(1, ids!(sheet_price_1)), (2, ids!(sheet_price_2)), (3, ids!(sheet_price_3)), (4, ids!(sheet_price_4))];
+        for (level, chip) in price_chips {
+            set_chip_selected(cx, &self.view.view(chip), filters.prices.contains(&level));
+        }
+        for (rating, chip) in Self::rating_chips() {
+            set_chip_selected(cx, &self.view.view(chip), filters.min_rating == rating);
+        }
+        for (distance, chip) in Self::distance_chips() {
+            set_chip_selected(cx, &self.view.view(chip), filters.distance == distance);
+        }
+        set_chip_selected(cx, &self.view.view(ids!(sheet_open_now)), filters.open_now);
+        let categories = search::category_options(&self.businesses);
+        self.view.category_chips(ids!(categories)).set_categories(cx, categories, &filters.categories);
+        self.redraw(cx);
+    }
+}
+
+impl FilterSheetRef {
+    /// Shows the sheet editing a copy of `filters`; `businesses` are the unfiltered results.
+    pub fn open(&self, cx: &mut Cx, filters: &SearchFilters, businesses: Vec<Business>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.filters = filters.clone();
+            inner.businesses = businesses;
+            inner.visible = true;
+            inner.sync_chips(cx);
+        }
+    }
+
+    pub fn set_visible(&self, cx: &mut Cx, visible: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.visible = visible;
+            inner.redraw(cx);
+        }
+    }
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum FilterSheetAction {
     None,
-    Back,
-    Call,
-    Directions,
+    Close,
+    Apply(SearchFilters),
 }
 
 // =====================
 // App
 // =====================
 
+// Initial map center (matches the `map` defaults in MapScreen) and marker radius
+const MAP_CENTER: (f64, f64) = (37.7749, -122.4194);
+const MAP_RADIUS_METERS: f64 = 10_000.0;
+// Map zoom after picking a neighborhood to search
+const NEIGHBORHOOD_ZOOM: f64 = 14.0;
+// Closest the route view zooms in when fitting a short route
+const ROUTE_MAX_ZOOM: f64 = 17.0;
+// Offline street graph the route view uses: San Francisco, a block every 150 m
+const STREET_GRID_BOUNDS: Bounds = Bounds { south: 37.70, west: -122.52, north: 37.82, east: -122.35 };
+const STREET_GRID_SPACING_METERS: f64 = 150.0;
+
+// How often the location provider is asked for new fixes
+const LOCATION_POLL_SECS: f64 = 1.0;
+
+// Photo requests: give up on silent requests after a timeout, retry transient
+// failures with exponential backoff (1s, 2s, 4s) before showing tap-to-retry
+const IMAGE_TIMEOUT_SECS: f64 = 15.0;
+const IMAGE_MAX_ATTEMPTS: u32 = 4;
+const IMAGE_RETRY_BASE_SECS: f64 = 1.0;
+
+struct ImageRequest {
+    business: Business,
+    attempt: u32,
+    sent_at: Instant,
+}
+
+struct ImageRetry {
+    business: Business,
+    attempt: u32,
+    timer: Timer,
+}
+
 #[derive(Live, LiveHook)]
 pub struct App {
     #[live] ui: WidgetRef,
-    #[rust] current_tab: Tab,
-    #[rust] showing_detail: bool,
+    #[rust] nav: Navigator, // History of each tab; decides which screens show
+    #[rust] screens: Screens, // Screen widgets currently shown
+    #[rust] shown_page: Option<(Tab, Page)>, // Page the screens were last set up for
     #[rust] images_pending: usize,
+    #[rust] provider: Option<Box<dyn BusinessProvider>>,
+    #[rust] search_term: String,
+    #[rust] businesses: Vec<Business>, // Current search results, every page loaded so far
+    #[rust] search_request: Option<SearchRequest>, // Latest search page asked for
+    #[rust] refreshing: bool, // Pull-to-refresh waiting for its first page
+    #[rust] next_search: Option<SearchRequest>, // Page to ask for on LoadMore (the failed one, to retry)
+    #[rust] nearby: Vec<Business>, // Businesses around the map center, or in the searched map area
+    #[rust] area_request: Option<AreaRequest>, // Latest "Search this area"
+    #[rust] filters: SearchFilters, // Applied to both the list and the map
+    #[rust] location: Option<Box<dyn LocationProvider>>,
+    #[rust] location_fix: Option<LocationFix>, // Distances are measured from here
+    #[rust] location_timer: Timer,
+    #[rust] geocoder: Option<Box<dyn Geocoder>>,
+    #[rust] router: Option<Box<dyn Router>>,
+    #[rust] platform: Option<Box<dyn PlatformActions>>, // Opens URLs in other apps, places calls
+    #[rust] search_center: Option<Place>, // Picked place; None follows the device location
+    #[rust] sort_mode: SortMode, // Order of the search list, kept across tab switches
+    #[rust] detail_id: Option<String>,
+    #[rust] linked_business: Option<String>, // Business a deep link waits on from the provider
+    #[rust] review_sort: ReviewSort,
+    #[rust] reviews_request: Option<ReviewsRequest>, // Latest reviews page asked for
+    #[rust] pending_reviews: HashMap<String, NewReview>, // Submitted reviews by local id, until the provider answers
+    #[rust] image_requests: HashMap<LiveId, ImageRequest>, // In-flight image requests
+    #[rust] image_retries: HashMap<String, ImageRetry>, // Scheduled retries by business id
+    #[rust] image_watchdog: Timer, // Interval checking for timed out image requests
+    #[rust] image_cache: Option<DiskImageCache>,
+    #[rust] revalidated_urls: HashSet<String>, // Photos checked against the server this session
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
@@ -1846,6 +4647,40 @@ pub enum Tab {
     Map,
 }
 
+/// Which screen widgets are shown; derived from the page on top of the navigation stack.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Screens {
+    search: bool,
+    map: bool,
+    detail: bool,
+    compose: bool,
+    route: bool,
+    photo: bool,
+    filters: bool,
+}
+
+impl Default for Screens {
+    /// As laid out in the DSL: the search list and nothing else.
+    fn default() -> Self {
+        Self::for_page(Tab::Search, &Page::Root)
+    }
+}
+
+impl Screens {
+    fn for_page(tab: Tab, page: &Page) -> Self {
+        let on_root = matches!(page, Page::Root | Page::Filters);
+        Self {
+            search: on_root && tab == Tab::Search,
+            map: on_root && tab == Tab::Map,
+            detail: matches!(page, Page::Detail(_)),
+            compose: matches!(page, Page::ComposeReview(_)),
+            route: matches!(page, Page::Directions(_)),
+            photo: matches!(page, Page::Photo(_)),
+            filters: matches!(page, Page::Filters),
+        }
+    }
+}
+
 impl LiveRegister for App {
     fn live_register(cx: &mut Cx) {
         crate::makepad_widgets::live_design(cx);
@@ -1855,101 +4690,291 @@ impl LiveRegister for App {
 
 impl MatchEvent for App {
     fn handle_startup(&mut self, cx: &mut Cx) {
-        self.request_images(cx);
+        // Open the disk cache first so cached photos paint before any network response
+        self.image_cache = Some(DiskImageCache::open(
+            storage::cache_dir().join("images"),
+            image_cache::DEFAULT_MAX_BYTES,
+        ));
+        self.provider = Some(provider::from_env());
+        if let Some(sorts) = self.provider.as_ref().map(|provider| provider.review_sorts()) {
+            self.review_sort = sorts.first().copied().unwrap_or_default();
+            self.ui.business_detail_screen(ids!(detail_screen)).set_review_sorts(cx, sorts);
+        }
+        let (lat, lng) = MAP_CENTER;
+        self.location = Some(location::from_env(lat, lng));
+        self.location_timer = cx.start_interval(LOCATION_POLL_SECS);
+        self.geocoder = Some(Box::new(Gazetteer::san_francisco()));
+        self.router = Some(Box::new(GraphRouter::street_grid(STREET_GRID_BOUNDS, STREET_GRID_SPACING_METERS)));
+        self.platform = Some(platform::actions());
+        self.poll_location(cx);
+        self.search(cx, "");
+        self.load_nearby(cx);
+        // Desktop launch with a link, e.g. `makepad-yelp makepadyelp://biz/{id}`
+        if let Some(link) = deeplink::from_args(std::env::args().skip(1)) {
+            self.open_deep_link(cx, link);
+        }
     }
 
     fn handle_network_responses(&mut self, cx: &mut Cx, responses: &NetworkResponsesEvent) {
-        let businesses = mock_businesses();
         for event in responses {
             // Check if this response is for one of our business images
-            for business in &businesses {
-                if event.request_id == LiveId::from_str(&business.id) {
-                    match &event.response {
-                        NetworkResponse::HttpResponse(response) => {
-                            if let Some(body) = &response.body {
-                                log!("Received image for {}: {} bytes", business.name, body.len());
-                                // Store in global map by business ID
-                                if let Ok(mut images) = RESTAURANT_IMAGES.lock() {
-                                    images.insert(business.id.clone(), body.clone());
-                                }
-                                // Track pending images
-                                if self.images_pending > 0 {
-                                    self.images_pending -= 1;
-                                    if self.images_pending == 0 {
-                                        // All images loaded, stop refreshing
-                                        self.ui.search_screen(ids!(search_screen)).set_refreshing(cx, false);
-                                    }
-                                }
-                                // Redraw to trigger image loading in cards
-                                self.ui.redraw(cx);
-                            }
-                        }
-                        NetworkResponse::HttpRequestError(err) => {
-                            log!("Image request error for {}: {:?}", business.name, err);
-                            // Still decrement pending on error
-                            if self.images_pending > 0 {
-                                self.images_pending -= 1;
-                                if self.images_pending == 0 {
-                                    self.ui.search_screen(ids!(search_screen)).set_refreshing(cx, false);
-                                }
-                            }
-                        }
-                        _ => {}
+            if self.image_requests.contains_key(&event.request_id) {
+                // Progress and other interim events leave the photo pending until its final response
+                if matches!(event.response, NetworkResponse::HttpResponse(_) | NetworkResponse::HttpRequestError(_)) {
+                    if let Some(request) = self.image_requests.remove(&event.request_id) {
+                        self.handle_image_response(cx, request, &event.response);
                     }
-                    break;
                 }
+                continue;
+            }
+
+            // Otherwise it may belong to the business provider
+            let response = self.provider.as_mut()
+                .and_then(|provider| provider.handle_network_response(event));
+            if let Some(response) = response {
+                self.handle_provider_response(cx, response);
             }
         }
+        if let Some(cache) = self.image_cache.as_mut() {
+            cache.flush();
+        }
     }
 
     fn handle_actions(&mut self, cx: &mut Cx, actions: &Actions) {
+        // Handle the sort selector in the search header
+        if let Some(index) = self.ui.drop_down(ids!(sort_dropdown)).selected(actions) {
+            self.sort_mode = SortMode::ALL[index.min(SortMode::ALL.len() - 1)];
+            // Results come back re-ordered, so start again from the top
+            self.ui.search_screen(ids!(search_screen)).scroll_to_top(cx);
+            self.show_results(cx);
+        }
+
         for action in actions.iter() {
+            // Decoded photos are ready to become textures
+            if let Some(ImageDecodeAction::Ready) = action.downcast_ref::<ImageDecodeAction>() {
+                if image_pipeline::upload_decoded(cx) {
+                    self.ui.redraw(cx);
+                }
+                continue;
+            }
+
+            // Map clusters for the latest businesses are ready
+            if let Some(ClusterAction::Ready) = action.downcast_ref::<ClusterAction>() {
+                self.ui.map_screen(ids!(map_screen)).clusters_ready(cx);
+                continue;
+            }
+
+            // Mobile platforms hand URLs to other apps through Makepad
+            if let Some(PlatformAction::OpenUrl(url)) = action.downcast_ref::<PlatformAction>() {
+                cx.open_url(url, OpenUrlInPlace::No);
+                continue;
+            }
+
             // Handle tab changes (widget action pattern)
             if let YelpTabBarAction::TabChanged(tab) = action.as_widget_action().cast() {
                 log!("App received TabChanged: {:?}", tab);
-                self.switch_tab(cx, &tab);
+                self.switch_tab(cx, tab);
                 continue;
             }
 
             // Handle business card clicks (widget action pattern)
             if let BusinessCardAction::Clicked(business) = action.as_widget_action().cast() {
                 log!("App received BusinessCardClicked: {}", business.name);
-                self.show_detail(cx, &business);
+                self.push_page(cx, Page::Detail(business.clone()));
+                self.load_business(cx, &business.id);
+                continue;
+            }
+
+            // Handle tap-to-retry on a failed photo
+            if let BusinessCardAction::RetryImage(business) = action.as_widget_action().cast() {
+                log!("App received RetryImage: {}", business.name);
+                if let Some(retry) = self.image_retries.remove(&business.id) {
+                    cx.stop_timer(retry.timer);
+                }
+                self.send_image_request(cx, business, 0, false);
                 continue;
             }
 
             // Handle back from detail (widget action pattern)
             if let DetailScreenAction::Back = action.as_widget_action().cast() {
                 log!("App received Back action");
-                self.hide_detail(cx);
+                self.pop_page(cx);
                 continue;
             }
 
-            // Handle directions button - switch to map
-            if let DetailScreenAction::Directions = action.as_widget_action().cast() {
+            // Handle directions button - route from the user's location
+            if let DetailScreenAction::Directions(business) = action.as_widget_action().cast() {
                 log!("App received Directions action");
-                self.hide_detail(cx);
-                self.switch_tab(cx, &Tab::Map);
+                self.push_page(cx, Page::Directions(business));
+                continue;
+            }
+
+            if let DetailScreenAction::ViewPhoto(business) = action.as_widget_action().cast() {
+                self.push_page(cx, Page::Photo(business));
+                continue;
+            }
+
+            if let PhotoScreenAction::Back = action.as_widget_action().cast() {
+                self.pop_page(cx);
+                continue;
+            }
+
+            if let RouteScreenAction::OpenExternal(url) = action.as_widget_action().cast() {
+                let result = self.platform.as_mut()
+                    .map_or_else(|| Err("no platform actions".to_string()), |platform| platform.open_url(&url));
+                if let Err(err) = result {
+                    log!("Could not hand off directions: {}", err);
+                }
+                continue;
+            }
+
+            if let RouteScreenAction::Back = action.as_widget_action().cast() {
+                self.pop_page(cx);
+                continue;
+            }
+
+            if let DetailScreenAction::SortReviews(sort) = action.as_widget_action().cast() {
+                self.review_sort = sort;
+                if let Some(id) = self.detail_id.clone() {
+                    self.load_reviews(cx, ReviewsRequest::new(&id, sort));
+                }
+                continue;
+            }
+
+            if let DetailScreenAction::MoreReviews = action.as_widget_action().cast() {
+                if let Some(request) = self.reviews_request.as_ref().map(ReviewsRequest::next_page) {
+                    self.load_reviews(cx, request);
+                }
+                continue;
+            }
+
+            if let DetailScreenAction::RetryReviews = action.as_widget_action().cast() {
+                if let Some(request) = self.reviews_request.clone() {
+                    self.load_reviews(cx, request);
+                }
+                continue;
+            }
+
+            if let DetailScreenAction::WriteReview(business) = action.as_widget_action().cast() {
+                self.push_page(cx, Page::ComposeReview(business));
                 continue;
             }
 
-            // Handle call button (placeholder - just log for now)
-            if let DetailScreenAction::Call = action.as_widget_action().cast() {
-                log!("App received Call action - would open phone dialer");
+            if let ReviewComposeAction::Cancel = action.as_widget_action().cast() {
+                self.pop_page(cx);
+                continue;
+            }
+
+            if let ReviewComposeAction::Submit(review) = action.as_widget_action().cast() {
+                self.pop_page(cx);
+                self.submit_review(cx, review);
+                continue;
+            }
+
+            // Handle share button - copy the business's deep link
+            if let DetailScreenAction::Share(business) = action.as_widget_action().cast() {
+                let link = DeepLink::Business(business.id).to_url();
+                log!("Copied {}", link);
+                cx.copy_to_clipboard(&link);
+                self.ui.business_detail_screen(ids!(detail_screen)).show_link_copied(cx);
+                continue;
+            }
+
+            // Handle call button - hand the number to the dialer
+            if let DetailScreenAction::Call(number) = action.as_widget_action().cast() {
+                log!("App received Call action: {}", number);
+                let result = self.platform.as_mut()
+                    .map_or_else(|| Err("no platform actions".to_string()), |platform| platform.dial(&number));
+                if let Err(err) = result {
+                    log!("Could not place call: {}", err);
+                }
+                continue;
+            }
+
+            if let MapScreenAction::SearchArea(bounds) = action.as_widget_action().cast() {
+                let request = AreaRequest { term: self.search_term.clone(), bounds };
+                self.area_request = Some(request.clone());
+                let response = self.provider.as_mut()
+                    .and_then(|provider| provider.search_area(cx, &request));
+                if let Some(response) = response {
+                    self.handle_provider_response(cx, response);
+                }
                 continue;
             }
 
             // Handle back from map screen - switch to search
             if let MapScreenAction::Back = action.as_widget_action().cast() {
                 log!("App received Map Back action");
-                self.switch_tab(cx, &Tab::Search);
+                self.switch_tab(cx, Tab::Search);
+                continue;
+            }
+
+            // Handle debounced search queries from the search bar
+            if let SearchBarAction::QueryChanged(query) = action.as_widget_action().cast() {
+                log!("App received search query: {:?}", query);
+                self.search(cx, &query);
+                continue;
+            }
+
+            // Handle the search list reaching its end (or a tap on a failed page)
+            if let SearchScreenAction::LoadMore = action.as_widget_action().cast() {
+                if let Some(request) = self.next_search.take() {
+                    self.load_search_page(cx, request);
+                }
+                continue;
+            }
+
+            // Handle the location picker in the search header
+            if let LocationPickerAction::QueryChanged(query) = action.as_widget_action().cast() {
+                let places = self.geocoder.as_ref()
+                    .map(|geocoder| geocoder.geocode(&query, LOCATION_SUGGESTIONS))
+                    .unwrap_or_default();
+                self.ui.location_picker(ids!(location_picker)).set_suggestions(cx, places);
+                continue;
+            }
+
+            if let LocationPickerAction::Picked(place) = action.as_widget_action().cast() {
+                log!("Searching around {}", place.name);
+                self.set_search_center(cx, Some(place));
+                continue;
+            }
+
+            if let LocationPickerAction::UseCurrentLocation = action.as_widget_action().cast() {
+                self.set_search_center(cx, None);
                 continue;
             }
 
             // Handle search screen refresh
             if let SearchScreenAction::Refresh = action.as_widget_action().cast() {
                 log!("App received Refresh action");
-                self.refresh_images(cx);
+                self.refreshing = true;
+                let term = self.search_term.clone();
+                self.search(cx, &term);
+                self.load_nearby(cx);
+                continue;
+            }
+
+            if let SearchScreenAction::FiltersChanged(filters) = action.as_widget_action().cast() {
+                self.filters = filters;
+                self.show_results(cx);
+                continue;
+            }
+
+            if let SearchScreenAction::OpenFilters = action.as_widget_action().cast() {
+                self.push_page(cx, Page::Filters);
+                continue;
+            }
+
+            if let FilterSheetAction::Close = action.as_widget_action().cast() {
+                self.pop_page(cx);
+                continue;
+            }
+
+            if let FilterSheetAction::Apply(filters) = action.as_widget_action().cast() {
+                self.pop_page(cx);
+                self.filters = filters;
+                self.ui.search_screen(ids!(search_screen)).set_filters(cx, &self.filters);
+                self.show_results(cx);
                 continue;
             }
         }
@@ -1968,64 +4993,567 @@ impl AppMain for App {
 
         // Also handle system events like Startup
         self.match_event(cx, event);
+
+        self.handle_image_timers(cx, event);
+
+        if self.location_timer.is_event(event).is_some() {
+            self.poll_location(cx);
+        }
     }
 }
 
 impl App {
-    fn switch_tab(&mut self, cx: &mut Cx, tab: &Tab) {
-        if self.showing_detail {
-            self.hide_detail(cx);
-        }
-        if self.current_tab == *tab { return; }
-        self.current_tab = *tab;
-        self.ui.search_screen(ids!(search_screen)).set_visible(cx, *tab == Tab::Search);
-        self.ui.map_screen(ids!(map_screen)).set_visible(cx, *tab == Tab::Map);
-        // Update tab bar highlighting
-        self.ui.yelp_tab_bar(ids!(tab_bar)).set_active_tab(cx, *tab);
-        self.ui.redraw(cx);
+    /// Shows `tab` where it was left.
+    fn switch_tab(&mut self, cx: &mut Cx, tab: Tab) {
+        if self.nav.switch_tab(tab) {
+            self.apply_navigation(cx, true);
+        }
+    }
+
+    fn push_page(&mut self, cx: &mut Cx, page: Page) {
+        self.nav.push(page);
+        self.apply_navigation(cx, false);
+    }
+
+    fn pop_page(&mut self, cx: &mut Cx) {
+        if self.nav.pop() {
+            self.apply_navigation(cx, true);
+        }
     }
 
-    fn show_detail(&mut self, cx: &mut Cx, business: &Business) {
-        self.showing_detail = true;
+    /// Brings the screens in line with the top of the current tab's stack.
+    /// `returning` is set when going back (or switching tabs) to a page seen before.
+    fn apply_navigation(&mut self, cx: &mut Cx, returning: bool) {
+        let tab = self.nav.tab();
+        let top = self.nav.top().clone();
+        let entered = self.shown_page.as_ref()
+            .map_or(true, |(shown_tab, shown)| *shown_tab != tab || !shown.same_as(&top));
+        if entered {
+            self.enter_page(cx, &top, returning);
+        }
 
-        // Set business data on detail screen
-        self.ui.business_detail_screen(ids!(detail_screen)).set_business(cx, business);
+        // Only touch screens whose visibility changes, so visible ones don't replay their fade
+        let screens = Screens::for_page(tab, &top);
+        let before = std::mem::replace(&mut self.screens, screens);
+        if screens.search != before.search {
+            self.ui.search_screen(ids!(search_screen)).set_visible(cx, screens.search);
+        }
+        if screens.map != before.map {
+            self.ui.map_screen(ids!(map_screen)).set_visible(cx, screens.map);
+        }
+        if screens.detail != before.detail {
+            self.ui.business_detail_screen(ids!(detail_screen)).set_visible(cx, screens.detail);
+        }
+        if screens.compose != before.compose {
+            self.ui.review_compose_screen(ids!(compose_screen)).set_visible(cx, screens.compose);
+        }
+        if screens.route != before.route {
+            self.ui.route_screen(ids!(route_screen)).set_visible(cx, screens.route);
+        }
+        if screens.photo != before.photo {
+            self.ui.photo_screen(ids!(photo_screen)).set_visible(cx, screens.photo);
+        }
+        if screens.filters != before.filters {
+            self.ui.filter_sheet(ids!(filter_sheet)).set_visible(cx, screens.filters);
+        }
+        // The list stays drawn under the filter sheet but must not take its input
+        self.ui.search_screen(ids!(search_screen)).set_active(screens.search && !screens.filters);
 
-        // Show detail screen, hide others
-        self.ui.search_screen(ids!(search_screen)).set_visible(cx, false);
-        self.ui.map_screen(ids!(map_screen)).set_visible(cx, false);
-        self.ui.business_detail_screen(ids!(detail_screen)).set_visible(cx, true);
-        self.ui.yelp_tab_bar(ids!(tab_bar)).set_visible(cx, false);
+        let tab_bar = self.ui.yelp_tab_bar(ids!(tab_bar));
+        tab_bar.set_visible(cx, self.nav.shows_tab_bar());
+        tab_bar.set_active_tab(cx, tab);
+        self.shown_page = Some((tab, top));
         self.ui.redraw(cx);
     }
 
-    fn hide_detail(&mut self, cx: &mut Cx) {
-        self.showing_detail = false;
+    /// Fills the screen a page uses with its content.
+    fn enter_page(&mut self, cx: &mut Cx, page: &Page, returning: bool) {
+        match page {
+            Page::Root => {}
+            Page::Filters => {
+                self.ui.filter_sheet(ids!(filter_sheet)).open(cx, &self.filters, self.businesses.clone());
+            }
+            Page::Detail(business) => {
+                // Going back keeps the loaded reviews, unless the other tab showed another business since
+                if returning && self.detail_id.as_ref() == Some(&business.id) { return; }
+                self.detail_id = Some(business.id.clone());
+                self.ui.business_detail_screen(ids!(detail_screen)).set_business(cx, business);
+                self.load_reviews(cx, ReviewsRequest::new(&business.id, self.review_sort));
+            }
+            Page::ComposeReview(business) => {
+                self.ui.review_compose_screen(ids!(compose_screen)).open(cx, business);
+            }
+            Page::Directions(business) => {
+                let origin = self.location_fix.map_or(MAP_CENTER, |fix| (fix.lat, fix.lng));
+                let routes = self.routes_to(origin, business);
+                self.ui.route_screen(ids!(route_screen)).open(cx, business, origin, routes);
+            }
+            Page::Photo(business) => {
+                self.ui.photo_screen(ids!(photo_screen)).open(cx, business);
+            }
+        }
+    }
+
+    fn search(&mut self, cx: &mut Cx, term: &str) {
+        self.search_term = term.to_string();
+        let center = self.search_center.as_ref().map(|place| (place.lat, place.lng));
+        self.load_search_page(cx, SearchRequest::new(term).with_center(center));
+    }
+
+    /// Re-runs the search and map around `place`, or around the device when None.
+    fn set_search_center(&mut self, cx: &mut Cx, place: Option<Place>) {
+        let name = place.as_ref().map_or("Current Location", |place| place.name.as_str());
+        self.ui.search_screen(ids!(search_screen)).set_location_name(cx, name);
+        self.search_center = place;
+        let (lat, lng) = self.map_center();
+        self.ui.map_screen(ids!(map_screen)).set_center(cx, lat, lng);
+        let term = self.search_term.clone();
+        self.search(cx, &term);
+        self.load_nearby(cx);
+    }
+
+    /// Picked place, else the device fix, else the default city center.
+    fn map_center(&self) -> (f64, f64) {
+        match (&self.search_center, &self.location_fix) {
+            (Some(place), _) => (place.lat, place.lng),
+            (None, Some(fix)) => (fix.lat, fix.lng),
+            (None, None) => MAP_CENTER,
+        }
+    }
+
+    /// Takes the newest fix from the location provider and re-measures every distance from it.
+    fn poll_location(&mut self, cx: &mut Cx) {
+        let elapsed = cx.seconds_since_app_start();
+        let fix = self.location.as_mut()
+            .and_then(|location| location.updates(elapsed).pop());
+        if let Some(fix) = fix {
+            self.location_fix = Some(fix);
+            location::apply_distances(&mut self.businesses, &fix);
+            location::apply_distances(&mut self.nearby, &fix);
+            self.show_results(cx);
+        }
+    }
+
+    /// Shows what a deep link points at, from the root of the tab it belongs to.
+    fn open_deep_link(&mut self, cx: &mut Cx, link: DeepLink) {
+        log!("Opening {}", link.to_url());
+        let tab = match link {
+            DeepLink::Map { .. } => Tab::Map,
+            _ => Tab::Search,
+        };
+        self.nav.switch_tab(tab);
+        self.nav.pop_to_root();
+        self.linked_business = None;
+        match link {
+            DeepLink::Business(id) => {
+                let known = self.businesses.iter().chain(&self.nearby).find(|business| business.id == id).cloned();
+                match known {
+                    Some(business) => self.push_page(cx, Page::Detail(business)),
+                    // Pushed when the provider answers
+                    None => {
+                        self.linked_business = Some(id.clone());
+                        self.apply_navigation(cx, true);
+                    }
+                }
+                self.load_business(cx, &id);
+            }
+            DeepLink::Search { query, location } => {
+                self.apply_navigation(cx, true);
+                self.ui.search_screen(ids!(search_screen)).set_query(cx, &query);
+                self.search_term = query;
+                let place = location.as_ref().and_then(|name| {
+                    self.geocoder.as_ref()
+                        .and_then(|geocoder| geocoder.geocode(name, 1).into_iter().next())
+                });
+                match place {
+                    Some(place) => self.set_search_center(cx, Some(place)),
+                    None => {
+                        if let Some(name) = location {
+                            log!("Unknown location in link: {}", name);
+                        }
+                        let term = self.search_term.clone();
+                        self.search(cx, &term);
+                    }
+                }
+            }
+            DeepLink::Map { lat, lng, zoom } => {
+                self.apply_navigation(cx, true);
+                self.ui.map_screen(ids!(map_screen)).set_view(cx, lat, lng, zoom.unwrap_or(NEIGHBORHOOD_ZOOM));
+                self.load_nearby_at(cx, lat, lng);
+            }
+        }
+    }
+
+    /// Fetches the full record of a business; cards may only carry search-result fields.
+    fn load_business(&mut self, cx: &mut Cx, id: &str) {
+        let response = self.provider.as_mut()
+            .and_then(|provider| provider.business(cx, id));
+        if let Some(response) = response {
+            self.handle_provider_response(cx, response);
+        }
+    }
+
+    fn load_nearby(&mut self, cx: &mut Cx) {
+        let (lat, lng) = self.map_center();
+        self.load_nearby_at(cx, lat, lng);
+    }
+
+    fn load_nearby_at(&mut self, cx: &mut Cx, lat: f64, lng: f64) {
+        let response = self.provider.as_mut()
+            .and_then(|provider| provider.nearby(cx, lat, lng, MAP_RADIUS_METERS));
+        if let Some(response) = response {
+            self.handle_provider_response(cx, response);
+        }
+    }
+
+    fn load_search_page(&mut self, cx: &mut Cx, request: SearchRequest) {
+        self.search_request = Some(request.clone());
+        self.next_search = None;
+        self.ui.search_screen(ids!(search_screen)).set_footer(cx, ListFooter::Loading);
+        let response = self.provider.as_mut()
+            .and_then(|provider| provider.search(cx, &request));
+        if let Some(response) = response {
+            self.handle_provider_response(cx, response);
+        }
+    }
+
+    /// One route per travel mode, straight lines where the router has no answer.
+    fn routes_to(&self, origin: (f64, f64), business: &Business) -> Vec<Route> {
+        let destination = (business.lat, business.lng);
+        TravelMode::ALL
+            .iter()
+            .filter_map(|&mode| {
+                let routed = match &self.router {
+                    Some(router) => router.route(origin, destination, mode),
+                    None => Err("no router".to_string()),
+                };
+                routed
+                    .or_else(|err| {
+                        log!("Routing {} to {} in a straight line: {}", mode.label(), business.name, err);
+                        StraightLineRouter.route(origin, destination, mode)
+                    })
+                    .ok()
+            })
+            .collect()
+    }
+
+    /// Shows the review right away and replaces it once the provider answers.
+    fn submit_review(&mut self, cx: &mut Cx, review: NewReview) {
+        if self.detail_id.as_ref() == Some(&review.business_id) {
+            self.ui.business_detail_screen(ids!(detail_screen)).insert_review(cx, review.optimistic());
+        }
+        self.pending_reviews.insert(review.local_id.clone(), review.clone());
+        let response = self.provider.as_mut()
+            .and_then(|provider| provider.submit_review(cx, &review));
+        if let Some(response) = response {
+            self.handle_provider_response(cx, response);
+        }
+    }
+
+    fn load_reviews(&mut self, cx: &mut Cx, request: ReviewsRequest) {
+        if request.offset == 0 {
+            self.ui.business_detail_screen(ids!(detail_screen)).start_reviews(cx, request.sort);
+        }
+        self.reviews_request = Some(request.clone());
+        let response = self.provider.as_mut()
+            .and_then(|provider| provider.reviews(cx, &request));
+        if let Some(response) = response {
+            self.handle_provider_response(cx, response);
+        }
+    }
+
+    fn handle_provider_response(&mut self, cx: &mut Cx, response: ProviderResponse) {
+        match response {
+            ProviderResponse::Search { request, businesses, has_more } => {
+                // Drop pages for queries the user has already typed past
+                if self.search_request.as_ref() != Some(&request) { return; }
+                log!("Search {:?} returned {} businesses from offset {}", request.term, businesses.len(), request.offset);
+                if request.offset == 0 {
+                    self.businesses.clear();
+                }
+                // Results can shift between pages, so skip any business already listed
+                let mut seen: HashSet<String> = self.businesses.iter().map(|business| business.id.clone()).collect();
+                let mut added: Vec<Business> = businesses
+                    .into_iter()
+                    .filter(|business| seen.insert(business.id.clone()))
+                    .collect();
+                if let Some(fix) = self.location_fix {
+                    location::apply_distances(&mut added, &fix);
+                }
+                self.businesses.extend(added.iter().cloned());
+                self.next_search = has_more.then(|| request.next_page());
+                let footer = if has_more { ListFooter::More } else { ListFooter::End };
+                self.ui.search_screen(ids!(search_screen)).set_footer(cx, footer);
+                self.show_results(cx);
+                if request.offset == 0 && std::mem::take(&mut self.refreshing) {
+                    // Spinner stops once every photo has been revalidated
+                    self.refresh_images(cx);
+                } else {
+                    self.request_images(cx, added, false);
+                }
+            }
+            ProviderResponse::SearchFailed { request, message } => {
+                if self.search_request.as_ref() != Some(&request) { return; }
+                log!("Search {:?} failed at offset {}: {}", request.term, request.offset, message);
+                self.next_search = Some(request);
+                self.refreshing = false;
+                self.ui.search_screen(ids!(search_screen)).set_footer(cx, ListFooter::Error(message));
+                self.ui.search_screen(ids!(search_screen)).set_refreshing(cx, false);
+            }
+            ProviderResponse::Nearby(mut businesses) => {
+                log!("Nearby returned {} businesses", businesses.len());
+                if let Some(fix) = self.location_fix {
+                    location::apply_distances(&mut businesses, &fix);
+                }
+                self.request_images(cx, businesses.clone(), false);
+                self.nearby = businesses;
+                self.show_results(cx);
+            }
+            ProviderResponse::Area { request, mut businesses } => {
+                if self.area_request.as_ref() != Some(&request) { return; }
+                log!("Area search returned {} businesses", businesses.len());
+                if let Some(fix) = self.location_fix {
+                    location::apply_distances(&mut businesses, &fix);
+                }
+                self.request_images(cx, businesses.clone(), false);
+                self.nearby = businesses;
+                self.show_results(cx);
+            }
+            ProviderResponse::Reviews { request, page } => {
+                // Only the latest request for the business on screen is shown
+                if self.reviews_request.as_ref() != Some(&request) { return; }
+                log!("Loaded {} of {} reviews for {}", page.reviews.len(), page.total, request.business_id);
+                self.ui.business_detail_screen(ids!(detail_screen)).add_reviews(cx, &request, page);
+            }
+            ProviderResponse::ReviewsFailed { request, message } => {
+                if self.reviews_request.as_ref() != Some(&request) { return; }
+                log!("Could not load reviews for {}: {}", request.business_id, message);
+                self.ui.business_detail_screen(ids!(detail_screen)).reviews_failed(cx);
+            }
+            ProviderResponse::ReviewSubmitted { local_id, business_id, result } => {
+                let pending = self.pending_reviews.remove(&local_id);
+                let on_screen = self.detail_id.as_ref() == Some(&business_id);
+                let detail = self.ui.business_detail_screen(ids!(detail_screen));
+                match result {
+                    Ok(review) => {
+                        log!("Review {} posted for {}", review.id, business_id);
+                        if on_screen {
+                            detail.replace_review(cx, &local_id, review);
+                        }
+                    }
+                    Err(message) => {
+                        log!("Review for {} was not posted: {}", business_id, message);
+                        // Put the text back so the user can try again
+                        if let Some(review) = pending {
+                            reviews::save_draft(&ReviewDraft {
+                                business_id: review.business_id,
+                                rating: review.rating,
+                                text: review.text,
+                                updated: hours::now_unix(),
+                            });
+                        }
+                        if on_screen {
+                            detail.remove_review(cx, &local_id, format!("Your review wasn't posted: {}. It was saved as a draft.", message));
+                        }
+                    }
+                }
+            }
+            ProviderResponse::Business(mut business) => {
+                if let Some(fix) = self.location_fix {
+                    location::apply_distances(std::slice::from_mut(&mut business), &fix);
+                }
+                self.nav.update_business(&business);
+                let on_screen = matches!(self.nav.top(), Page::Detail(shown) if shown.id == business.id);
+                if self.linked_business.as_ref() == Some(&business.id) {
+                    // A deep link was waiting for this business
+                    self.linked_business = None;
+                    self.push_page(cx, Page::Detail(business));
+                } else if on_screen {
+                    self.ui.business_detail_screen(ids!(detail_screen)).set_business(cx, &business);
+                }
+            }
+            ProviderResponse::Error(message) => {
+                log!("Business provider error: {}", message);
+                // A linked business that failed to load won't arrive later
+                self.linked_business = None;
+                self.ui.search_screen(ids!(search_screen)).set_refreshing(cx, false);
+            }
+        }
+    }
+
+    /// Pushes the current results to the search screen (sorted) and map, minus those the filters rule out.
+    fn show_results(&mut self, cx: &mut Cx) {
+        let now = hours::now_unix();
+        let is_open = |business: &Business| business.is_open_at(now);
+        let mut listed = self.filters.apply(&self.businesses, is_open);
+        search::sort_businesses(&mut listed, self.sort_mode);
+        self.ui.search_screen(ids!(search_screen)).set_results(cx, &self.search_term, listed);
+        let mapped = self.filters.apply(&self.nearby, is_open);
+        self.ui.map_screen(ids!(map_screen)).set_businesses(cx, mapped);
+    }
+
+    /// Paints photos from the disk cache right away, then requests each photo
+    /// (conditionally when cached) unless it was already checked this session.
+    fn request_images(&mut self, cx: &mut Cx, businesses: Vec<Business>, revalidate: bool) {
+        let mut requested = 0;
+        for business in businesses {
+            let url = business.photo_url();
+            let in_flight = self.image_requests.values().any(|request| request.business.id == business.id)
+                || self.image_retries.contains_key(&business.id);
+            if in_flight { continue; }
+            if !revalidate && self.revalidated_urls.contains(&url) { continue; }
+
+            if let Some(cache) = self.image_cache.as_mut() {
+                if !image_pipeline::has_source(&business.id) {
+                    if let Some(bytes) = cache.get(&url) {
+                        store_image(&business.id, bytes);
+                    }
+                }
+            }
+            self.send_image_request(cx, business, 0, true);
+            requested += 1;
+        }
+        if requested > 0 {
+            log!("Requesting {} restaurant images...", requested);
+        }
+    }
 
-        // Restore previous tab
-        self.ui.business_detail_screen(ids!(detail_screen)).set_visible(cx, false);
-        self.ui.search_screen(ids!(search_screen)).set_visible(cx, self.current_tab == Tab::Search);
-        self.ui.map_screen(ids!(map_screen)).set_visible(cx, self.current_tab == Tab::Map);
-        self.ui.yelp_tab_bar(ids!(tab_bar)).set_visible(cx, true);
+    /// Sends attempt `attempt` for a photo; `conditional` adds cache validators.
+    fn send_image_request(&mut self, cx: &mut Cx, business: Business, attempt: u32, conditional: bool) {
+        let url = business.photo_url();
+        let mut request = HttpRequest::new(url.clone(), HttpMethod::GET);
+        if conditional {
+            if let Some(cache) = self.image_cache.as_ref() {
+                for (name, value) in cache.revalidation_headers(&url) {
+                    request.set_header(name, value);
+                }
+            }
+        }
+        image_pipeline::clear_failure(&business.id);
+        // Attempt is part of the id so a late answer to a timed-out attempt is ignored
+        let request_id = LiveId::from_str(&format!("image/{}/{}", business.id, attempt));
+        cx.http_request(request_id, request);
+        self.image_requests.insert(request_id, ImageRequest { business, attempt, sent_at: Instant::now() });
+        self.images_pending += 1;
+        if self.image_watchdog.is_empty() {
+            self.image_watchdog = cx.start_interval(1.0);
+        }
         self.ui.redraw(cx);
     }
 
-    fn request_images(&mut self, cx: &mut Cx) {
-        let businesses = mock_businesses();
-        self.images_pending = businesses.len();
-        for business in &businesses {
-            let url = format!("https://picsum.photos/seed/{}/320/240", business.id);
-            let request = HttpRequest::new(url, HttpMethod::GET);
-            cx.http_request(LiveId::from_str(&business.id), request);
+    fn handle_image_response(&mut self, cx: &mut Cx, request: ImageRequest, response: &NetworkResponse) {
+        let business = &request.business;
+        let url = business.photo_url();
+        match response {
+            NetworkResponse::HttpResponse(response) if response.status_code == 304 => {
+                log!("Image for {} not modified", business.name);
+                self.revalidated_urls.insert(url.clone());
+                if let Some(cache) = self.image_cache.as_mut() {
+                    cache.touch(&url);
+                }
+            }
+            NetworkResponse::HttpResponse(response) if response.status_code == 200 => {
+                let body = response.body.clone().unwrap_or_default();
+                log!("Received image for {}: {} bytes", business.name, body.len());
+                self.revalidated_urls.insert(url.clone());
+                if let Some(cache) = self.image_cache.as_mut() {
+                    cache.put(
+                        &url,
+                        &body,
+                        image_cache::response_header(response, "ETag"),
+                        image_cache::response_header(response, "Last-Modified"),
+                    );
+                }
+                // Store in global map by business ID; cards redraw once it's decoded
+                store_image(&business.id, body);
+            }
+            NetworkResponse::HttpResponse(response) => {
+                log!("Image request for {} failed: HTTP {}", business.name, response.status_code);
+                // Only server-side and throttling errors are worth retrying
+                let transient = response.status_code >= 500 || matches!(response.status_code, 408 | 429);
+                let err = ImageLoadError::Http(response.status_code);
+                if transient {
+                    self.retry_image_later(cx, request.business.clone(), request.attempt, err);
+                } else {
+                    image_pipeline::mark_failed(&business.id, err);
+                }
+            }
+            NetworkResponse::HttpRequestError(err) => {
+                log!("Image request error for {}: {:?}", business.name, err);
+                let err = ImageLoadError::Network(format!("{:?}", err));
+                self.retry_image_later(cx, request.business.clone(), request.attempt, err);
+            }
+            // Only final responses are handed over
+            _ => {}
+        }
+        self.finish_image_request(cx);
+    }
+
+    /// Schedules the next attempt with exponential backoff, or gives up and shows tap-to-retry.
+    fn retry_image_later(&mut self, cx: &mut Cx, business: Business, attempt: u32, err: ImageLoadError) {
+        let next_attempt = attempt + 1;
+        if next_attempt >= IMAGE_MAX_ATTEMPTS {
+            log!("Giving up on image for {} after {} attempts: {}", business.name, next_attempt, err);
+            image_pipeline::mark_failed(&business.id, err);
+            self.ui.redraw(cx);
+            return;
+        }
+        let delay = IMAGE_RETRY_BASE_SECS * 2f64.powi(attempt as i32);
+        log!("Retrying image for {} in {:.0}s ({})", business.name, delay, err);
+        let timer = cx.start_timeout(delay);
+        self.image_retries.insert(business.id.clone(), ImageRetry { business, attempt: next_attempt, timer });
+    }
+
+    /// Track pending images, including failures
+    fn finish_image_request(&mut self, cx: &mut Cx) {
+        if self.images_pending > 0 {
+            self.images_pending -= 1;
+            if self.images_pending == 0 {
+                // All images loaded, stop refreshing
+                self.ui.search_screen(ids!(search_screen)).set_refreshing(cx, false);
+            }
+        }
+    }
+
+    fn handle_image_timers(&mut self, cx: &mut Cx, event: &Event) {
+        // Backoff timers firing
+        let due: Vec<String> = self.image_retries
+            .iter()
+            .filter(|(_, retry)| retry.timer.is_event(event).is_some())
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in due {
+            if let Some(retry) = self.image_retries.remove(&id) {
+                self.send_image_request(cx, retry.business, retry.attempt, false);
+            }
+        }
+
+        // Watchdog for requests that never answer
+        if self.image_watchdog.is_event(event).is_some() {
+            let timed_out: Vec<LiveId> = self.image_requests
+                .iter()
+                .filter(|(_, request)| request.sent_at.elapsed().as_secs_f64() > IMAGE_TIMEOUT_SECS)
+                .map(|(request_id, _)| *request_id)
+                .collect();
+            for request_id in timed_out {
+                if let Some(request) = self.image_requests.remove(&request_id) {
+                    log!("Image request for {} timed out", request.business.name);
+                    self.retry_image_later(cx, request.business, request.attempt, ImageLoadError::Timeout);
+                    self.finish_image_request(cx);
+                }
+            }
+            if self.image_requests.is_empty() {
+                cx.stop_timer(self.image_watchdog);
+                self.image_watchdog = Timer::empty();
+            }
         }
-        log!("Requesting {} restaurant images...", businesses.len());
     }
 
     fn refresh_images(&mut self, cx: &mut Cx) {
-        // Clear cached images and re-request
-        if let Ok(mut images) = RESTAURANT_IMAGES.lock() {
-            images.clear();
+        // Revalidate every visible photo; unchanged ones come back as 304 and keep their bytes
+        self.request_images(cx, self.businesses.clone(), true);
+        if self.images_pending == 0 {
+            self.ui.search_screen(ids!(search_screen)).set_refreshing(cx, false);
         }
-        self.request_images(cx);
     }
 }
@@ -0,0 +1,237 @@
+//! Local stand-in for the Yelp Fusion API, serving fixture JSON so the client
+//! and `YelpProvider` can be exercised offline.
+//!
+//! ```bash
+//! cargo run --bin yelp_stub
+//! YELP_API_URL=http://127.0.0.1:8787 YELP_API_KEY=stub cargo run
+//! ```
+//!
+//! Serves `/businesses/search`, `/businesses/{id}` and `/businesses/{id}/reviews`.
+//! Any bearer token is accepted; a missing one yields 401. Searching for the
+//! terms `stub:429` or `stub:500` returns that status so error paths can be tried.
+
+use makepad_yelp::makepad_widgets::makepad_micro_serde::*;
+use makepad_yelp::geo;
+use makepad_yelp::url;
+use makepad_yelp::yelp::{
+    YelpBusiness, YelpErrorBody, YelpErrorResponse, YelpReview, YelpReviewsResponse, YelpSearchResponse, MAX_LIMIT,
+    MAX_RESULTS,
+};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+
+const DEFAULT_ADDR: &str = "127.0.0.1:8787";
+const DEFAULT_FIXTURES: &str = "resources/fixtures/yelp";
+
+struct Request {
+    path: String,
+    query: Vec<(String, String)>,
+    authorized: bool,
+}
+
+struct Response {
+    status: u16,
+    body: String,
+}
+
+/// `reviews.json`: reviews grouped by business id.
+#[derive(DeJson)]
+struct ReviewFixture {
+    businesses: Vec<BusinessReviews>,
+}
+
+#[derive(DeJson)]
+struct BusinessReviews {
+    business_id: String,
+    reviews: Vec<YelpReview>,
+}
+
+struct Fixtures {
+    businesses: Vec<YelpBusiness>,
+    reviews: Vec<BusinessReviews>,
+}
+
+fn main() {
+    let addr = std::env::var("YELP_STUB_ADDR").unwrap_or_else(|_| DEFAULT_ADDR.to_string());
+    let fixtures = std::env::args().nth(1).unwrap_or_else(|| DEFAULT_FIXTURES.to_string());
+    let search_json = std::fs::read_to_string(format!("{}/search.json", fixtures))
+        .unwrap_or_else(|err| panic!("Could not read {}/search.json: {}", fixtures, err));
+    let fixture = YelpSearchResponse::deserialize_json(&search_json)
+        .unwrap_or_else(|err| panic!("Could not parse search fixture: {:?}", err));
+    // Reviews are optional so older fixture directories keep working
+    let reviews = std::fs::read_to_string(format!("{}/reviews.json", fixtures))
+        .ok()
+        .and_then(|json| ReviewFixture::deserialize_json(&json).ok())
+        .map(|fixture| fixture.businesses)
+        .unwrap_or_default();
+    let fixtures = Arc::new(Fixtures { businesses: fixture.businesses, reviews });
+
+    let listener = TcpListener::bind(&addr).unwrap_or_else(|err| panic!("Could not bind {}: {}", addr, err));
+    println!("yelp_stub serving {} businesses on http://{}", fixtures.businesses.len(), addr);
+    for stream in listener.incoming().flatten() {
+        let fixtures = fixtures.clone();
+        std::thread::spawn(move || handle_connection(stream, &fixtures));
+    }
+}
+
+fn handle_connection(mut stream: TcpStream, fixtures: &Fixtures) {
+    let response = match read_request(&stream) {
+        Some(request) => route(&request, fixtures),
+        None => error(400, "VALIDATION_ERROR", "Malformed request"),
+    };
+    let reason = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        429 => "Too Many Requests",
+        _ => "Internal Server Error",
+    };
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason,
+        response.body.len()
+    );
+    let _ = stream.write_all(head.as_bytes());
+    let _ = stream.write_all(response.body.as_bytes());
+}
+
+fn read_request(stream: &TcpStream) -> Option<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).ok()?;
+    let target = request_line.split_whitespace().nth(1)?;
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let mut authorized = false;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).ok()? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("authorization") && value.trim().starts_with("Bearer ") {
+                authorized = true;
+            }
+        }
+    }
+    println!("{} {}", request_line.split_whitespace().next().unwrap_or("?"), target);
+    Some(Request { path: path.to_string(), query: url::parse_query(query), authorized })
+}
+
+fn route(request: &Request, fixtures: &Fixtures) -> Response {
+    let businesses = &fixtures.businesses;
+    if !request.authorized {
+        return error(401, "TOKEN_MISSING", "An access token must be supplied in order to use this endpoint.");
+    }
+    if request.path == "/businesses/search" {
+        return search(request, businesses);
+    }
+    if let Some(id) = request.path.strip_prefix("/businesses/").and_then(|rest| rest.strip_suffix("/reviews")) {
+        let id = url::percent_decode(id);
+        if !businesses.iter().any(|business| business.id == id) {
+            return error(404, "BUSINESS_NOT_FOUND", "The requested business could not be found.");
+        }
+        return reviews(request, fixtures, &id);
+    }
+    if let Some(id) = request.path.strip_prefix("/businesses/") {
+        let id = url::percent_decode(id);
+        return match businesses.iter().find(|business| business.id == id) {
+            Some(business) => Response { status: 200, body: business.serialize_json() },
+            None => error(404, "BUSINESS_NOT_FOUND", "The requested business could not be found."),
+        };
+    }
+    error(404, "NOT_FOUND", "Resource could not be found.")
+}
+
+fn search(request: &Request, businesses: &[YelpBusiness]) -> Response {
+    let param = |name: &str| {
+        request.query.iter().find(|(key, _)| key == name).map(|(_, value)| value.clone())
+    };
+    let term = param("term").unwrap_or_default().to_lowercase();
+    match term.as_str() {
+        "stub:429" => return error(429, "TOO_MANY_REQUESTS_PER_SECOND", "You have exceeded the queries-per-second limit for this endpoint."),
+        "stub:500" => return error(500, "INTERNAL_ERROR", "Something went wrong internally, please try your request again"),
+        _ => {}
+    }
+    let offset: usize = param("offset").and_then(|v| v.parse().ok()).unwrap_or(0);
+    let limit: usize = param("limit").and_then(|v| v.parse().ok()).unwrap_or(20);
+    if limit > MAX_LIMIT {
+        return error(400, "VALIDATION_ERROR", &format!("{} is greater than the maximum of {} - 'limit'", limit, MAX_LIMIT));
+    }
+    if offset + limit > MAX_RESULTS {
+        return error(400, "VALIDATION_ERROR", "Too many results requested, limit+offset must be <= 240.");
+    }
+    let center = match (param("latitude"), param("longitude")) {
+        (Some(lat), Some(lng)) => lat.parse::<f64>().ok().zip(lng.parse::<f64>().ok()),
+        _ => None,
+    };
+    let radius: Option<f64> = param("radius").and_then(|v| v.parse().ok());
+
+    let matches: Vec<YelpBusiness> = businesses
+        .iter()
+        .filter(|business| {
+            term.is_empty()
+                || business.name.to_lowercase().contains(&term)
+                || business.categories.iter().any(|category| category.title.to_lowercase().contains(&term))
+        })
+        .filter_map(|business| {
+            let mut business = business.clone();
+            if let Some((lat, lng)) = center {
+                let distance = geo::haversine_meters(
+                    lat,
+                    lng,
+                    business.coordinates.latitude.unwrap_or(0.0),
+                    business.coordinates.longitude.unwrap_or(0.0),
+                );
+                if radius.is_some_and(|radius| distance > radius) {
+                    return None;
+                }
+                business.distance = Some(distance);
+            }
+            Some(business)
+        })
+        .collect();
+
+    let page = YelpSearchResponse {
+        total: matches.len() as u64,
+        businesses: matches.into_iter().skip(offset).take(limit).collect(),
+    };
+    Response { status: 200, body: page.serialize_json() }
+}
+
+fn reviews(request: &Request, fixtures: &Fixtures, id: &str) -> Response {
+    let param = |name: &str| {
+        request.query.iter().find(|(key, _)| key == name).map(|(_, value)| value.clone())
+    };
+    let offset: usize = param("offset").and_then(|v| v.parse().ok()).unwrap_or(0);
+    let limit: usize = param("limit").and_then(|v| v.parse().ok()).unwrap_or(20);
+    if limit > MAX_LIMIT {
+        return error(400, "VALIDATION_ERROR", &format!("{} is greater than the maximum of {} - 'limit'", limit, MAX_LIMIT));
+    }
+    let mut reviews: Vec<YelpReview> = fixtures.reviews
+        .iter()
+        .find(|entry| entry.business_id == id)
+        .map(|entry| entry.reviews.clone())
+        .unwrap_or_default();
+    match param("sort_by").as_deref() {
+        // Timestamps are zero-padded, so string order is chronological
+        Some("newest") => reviews.sort_by(|a, b| b.time_created.cmp(&a.time_created)),
+        Some("yelp_sort") | None => {}
+        Some(other) => return error(400, "VALIDATION_ERROR", &format!("'{}' is not a valid sort_by", other)),
+    }
+    let page = YelpReviewsResponse {
+        total: reviews.len() as u64,
+        reviews: reviews.into_iter().skip(offset).take(limit).collect(),
+    };
+    Response { status: 200, body: page.serialize_json() }
+}
+
+fn error(status: u16, code: &str, description: &str) -> Response {
+    let body = YelpErrorResponse {
+        error: YelpErrorBody { code: code.to_string(), description: description.to_string() },
+    };
+    Response { status, body: body.serialize_json() }
+}
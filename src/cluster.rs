@@ -0,0 +1,350 @@
+use makepad_widgets::*;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{LazyLock, Mutex};
+use crate::geo::{self, Bounds, TILE_SIZE};
+
+/// Deepest zoom level that still groups markers; past it every business gets its own pin.
+pub const MAX_CLUSTER_ZOOM: usize = 16;
+/// Markers closer than this many screen pixels merge into one bubble.
+pub const CLUSTER_RADIUS_PX: f64 = 60.0;
+
+// =====================
+// Cluster Index
+// =====================
+
+/// A business position to cluster.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MapPoint {
+    pub id: String,
+    pub lat: f64,
+    pub lng: f64,
+}
+
+/// One marker to draw: a single business or a bubble counting several.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClusterMarker {
+    /// Stable per zoom level; the business id for single businesses
+    pub key: String,
+    pub lat: f64,
+    pub lng: f64,
+    pub count: usize,
+    /// Set when the marker stands for exactly one business
+    pub business_id: Option<String>,
+    /// Zoom at which the bubble splits into smaller ones
+    pub expansion_zoom: usize,
+}
+
+#[derive(Clone, Copy)]
+struct Cluster {
+    /// Web Mercator position (0..1) of the members' centroid
+    x: f64,
+    y: f64,
+    count: usize,
+    /// Point index when the cluster holds a single business
+    point: Option<usize>,
+    expansion_zoom: usize,
+}
+
+/// Clusters for every zoom level from 0 to `MAX_CLUSTER_ZOOM`, built once per result set.
+///
+/// Each level greedily merges the clusters of the level below it, so a bubble
+/// always breaks up into the bubbles shown one zoom level deeper.
+pub struct ClusterIndex {
+    points: Vec<MapPoint>,
+    /// `levels[z]` is shown at zoom `z`; the last level holds the unmerged points
+    levels: Vec<Vec<Cluster>>,
+}
+
+impl ClusterIndex {
+    pub fn build(points: Vec<MapPoint>) -> Self {
+        let leaves: Vec<Cluster> = points
+            .iter()
+            .enumerate()
+            .map(|(index, point)| {
+                let (x, y) = geo::mercator_project(point.lat, point.lng);
+                Cluster { x, y, count: 1, point: Some(index), expansion_zoom: MAX_CLUSTER_ZOOM + 1 }
+            })
+            .collect();
+        let mut levels = vec![leaves];
+        for zoom in (0..=MAX_CLUSTER_ZOOM).rev() {
+            let merged = merge_level(levels.last().unwrap(), zoom);
+            levels.push(merged);
+        }
+        levels.reverse();
+        Self { points, levels }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Markers for the map at `zoom`, limited to `bounds` when given.
+    pub fn markers(&self, zoom: f64, bounds: Option<&Bounds>) -> Vec<ClusterMarker> {
+        let level = level_for_zoom(zoom);
+        self.levels[level]
+            .iter()
+            .enumerate()
+            .filter_map(|(index, cluster)| {
+                let (lat, lng) = geo::mercator_unproject(cluster.x, cluster.y);
+                if bounds.is_some_and(|bounds| !bounds.contains(lat, lng)) {
+                    return None;
+                }
+                let point = cluster.point.map(|point| &self.points[point]);
+                Some(ClusterMarker {
+                    key: point.map_or_else(|| format!("cluster/{}/{}", level, index), |point| point.id.clone()),
+                    lat: point.map_or(lat, |point| point.lat),
+                    lng: point.map_or(lng, |point| point.lng),
+                    count: cluster.count,
+                    business_id: point.map(|point| point.id.clone()),
+                    expansion_zoom: cluster.expansion_zoom,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Cluster level used at a (fractional) map zoom.
+pub fn level_for_zoom(zoom: f64) -> usize {
+    (zoom.max(0.0).floor() as usize).min(MAX_CLUSTER_ZOOM + 1)
+}
+
+/// Greedy merge of `below` (level `zoom + 1`) into the clusters shown at `zoom`.
+fn merge_level(below: &[Cluster], zoom: usize) -> Vec<Cluster> {
+    let radius = CLUSTER_RADIUS_PX / (TILE_SIZE * 2f64.powi(zoom as i32));
+    let cell_of = |cluster: &Cluster| ((cluster.x / radius).floor() as i64, (cluster.y / radius).floor() as i64);
+    // Grid with radius-sized cells, so neighbors are always in the 3x3 block around a cluster
+    let mut grid: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+    for (index, cluster) in below.iter().enumerate() {
+        grid.entry(cell_of(cluster)).or_default().push(index);
+    }
+    let mut taken = vec![false; below.len()];
+    let mut merged = Vec::new();
+    for (index, cluster) in below.iter().enumerate() {
+        if taken[index] { continue; }
+        taken[index] = true;
+        let (cell_x, cell_y) = cell_of(cluster);
+        let mut members = vec![index];
+        for neighbor_x in cell_x - 1..=cell_x + 1 {
+            for neighbor_y in cell_y - 1..=cell_y + 1 {
+                for &other in grid.get(&(neighbor_x, neighbor_y)).into_iter().flatten() {
+                    if taken[other] { continue; }
+                    let (dx, dy) = (below[other].x - cluster.x, below[other].y - cluster.y);
+                    if dx * dx + dy * dy <= radius * radius {
+                        taken[other] = true;
+                        members.push(other);
+                    }
+                }
+            }
+        }
+        if members.len() == 1 {
+            merged.push(*cluster);
+            continue;
+        }
+        // Centroid weighted by how many businesses each member holds
+        let count: usize = members.iter().map(|&member| below[member].count).sum();
+        let (x, y) = members.iter().fold((0.0, 0.0), |(x, y), &member| {
+            let weight = below[member].count as f64;
+            (x + below[member].x * weight, y + below[member].y * weight)
+        });
+        merged.push(Cluster {
+            x: x / count as f64,
+            y: y / count as f64,
+            count,
+            point: None,
+            expansion_zoom: zoom + 1,
+        });
+    }
+    merged
+}
+
+// =====================
+// Background Building
+// =====================
+
+/// Sent to the UI thread when a requested cluster index has been built.
+#[derive(Clone, Debug)]
+pub enum ClusterAction {
+    Ready,
+}
+
+struct BuildJob {
+    generation: u64,
+    points: Vec<MapPoint>,
+}
+
+static LATEST_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Single worker; only the newest queued job is built, older ones are already stale.
+static BUILD_QUEUE: LazyLock<Mutex<Sender<BuildJob>>> = LazyLock::new(|| {
+    let (sender, receiver) = mpsc::channel::<BuildJob>();
+    std::thread::spawn(move || {
+        while let Ok(job) = receiver.recv() {
+            let job = receiver.try_iter().last().unwrap_or(job);
+            if job.generation != LATEST_GENERATION.load(Ordering::Acquire) { continue; }
+            let index = ClusterIndex::build(job.points);
+            if let Ok(mut built) = BUILT.lock() {
+                *built = Some((job.generation, index));
+            }
+            Cx::post_action(ClusterAction::Ready);
+        }
+    });
+    Mutex::new(sender)
+});
+
+static BUILT: LazyLock<Mutex<Option<(u64, ClusterIndex)>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Queues an index build for `points`, superseding any build still in flight.
+pub fn build_in_background(points: Vec<MapPoint>) {
+    let generation = LATEST_GENERATION.fetch_add(1, Ordering::AcqRel) + 1;
+    if let Ok(queue) = BUILD_QUEUE.lock() {
+        let _ = queue.send(BuildJob { generation, points });
+    }
+}
+
+/// The finished index, if it was built from the most recently queued points.
+pub fn take_built() -> Option<ClusterIndex> {
+    let (generation, index) = BUILT.lock().ok()?.take()?;
+    (generation == LATEST_GENERATION.load(Ordering::Acquire)).then_some(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn point(id: &str, lat: f64, lng: f64) -> MapPoint {
+        MapPoint { id: id.to_string(), lat, lng }
+    }
+
+    /// Three businesses around `lng`: two a few meters apart and one a few hundred meters away.
+    fn trio(name: &str, lng: f64) -> Vec<MapPoint> {
+        vec![
+            point(&format!("{}-a", name), 37.76, lng),
+            point(&format!("{}-b", name), 37.76005, lng),
+            point(&format!("{}-c", name), 37.76, lng + 0.005),
+        ]
+    }
+
+    fn around(lng: f64) -> Bounds {
+        Bounds { south: 37.75, west: lng - 0.01, north: 37.77, east: lng + 0.01 }
+    }
+
+    /// Deterministic points scattered over the Bay Area.
+    fn scattered(count: usize) -> Vec<MapPoint> {
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = move || {
+            seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+            (seed >> 11) as f64 / (1u64 << 53) as f64
+        };
+        (0..count)
+            .map(|index| point(&format!("biz-{}", index), 37.3 + next() * 0.6, -122.6 + next() * 0.8))
+            .collect()
+    }
+
+    #[test]
+    fn level_for_zoom_floors_and_clamps() {
+        assert_eq!(level_for_zoom(-2.0), 0);
+        assert_eq!(level_for_zoom(0.9), 0);
+        assert_eq!(level_for_zoom(12.7), 12);
+        assert_eq!(level_for_zoom(MAX_CLUSTER_ZOOM as f64 + 0.5), MAX_CLUSTER_ZOOM);
+        assert_eq!(level_for_zoom(MAX_CLUSTER_ZOOM as f64 + 1.0), MAX_CLUSTER_ZOOM + 1);
+        assert_eq!(level_for_zoom(22.0), MAX_CLUSTER_ZOOM + 1);
+    }
+
+    #[test]
+    fn empty_index_has_no_markers() {
+        let index = ClusterIndex::build(Vec::new());
+        assert!(index.is_empty());
+        assert!(index.markers(12.0, None).is_empty());
+    }
+
+    #[test]
+    fn single_businesses_keep_their_id_and_position() {
+        let index = ClusterIndex::build(vec![point("lone", 37.76, -122.42)]);
+        assert_eq!(index.markers(3.0, None), vec![ClusterMarker {
+            key: "lone".to_string(),
+            lat: 37.76,
+            lng: -122.42,
+            count: 1,
+            business_id: Some("lone".to_string()),
+            expansion_zoom: MAX_CLUSTER_ZOOM + 1,
+        }]);
+    }
+
+    #[test]
+    fn nearby_businesses_merge_when_zoomed_out() {
+        let index = ClusterIndex::build(trio("mission", -122.42));
+        let markers = index.markers(10.0, None);
+        assert_eq!(markers.len(), 1);
+        assert_eq!(markers[0].count, 3);
+        assert_eq!(markers[0].business_id, None);
+        // Past the deepest cluster level every business has its own pin
+        let markers = index.markers(MAX_CLUSTER_ZOOM as f64 + 1.0, None);
+        assert_eq!(markers.len(), 3);
+        assert!(markers.iter().all(|marker| marker.count == 1 && marker.business_id.is_some()));
+    }
+
+    #[test]
+    fn markers_are_limited_to_the_bounds() {
+        let mut points = trio("mission", -122.42);
+        points.extend(trio("oakland", -122.27));
+        let index = ClusterIndex::build(points);
+        let markers = index.markers(12.0, Some(&around(-122.42)));
+        assert_eq!(markers.iter().map(|marker| marker.count).sum::<usize>(), 3);
+        assert!(markers.iter().all(|marker| around(-122.42).contains(marker.lat, marker.lng)));
+    }
+
+    #[test]
+    fn expansion_zoom_splits_the_bubble() {
+        let lngs = [-122.9, -122.4, -121.9];
+        let points = lngs.iter().enumerate().flat_map(|(index, &lng)| trio(&index.to_string(), lng)).collect();
+        let index = ClusterIndex::build(points);
+        for &lng in &lngs {
+            let bounds = around(lng);
+            for zoom in 8..=MAX_CLUSTER_ZOOM {
+                for bubble in index.markers(zoom as f64, Some(&bounds)).into_iter().filter(|marker| marker.count > 1) {
+                    assert!(bubble.expansion_zoom > zoom);
+                    // Still one bubble just before the expansion zoom...
+                    let before = index.markers(bubble.expansion_zoom as f64 - 1.0, Some(&bounds));
+                    assert!(before.iter().any(|marker| marker.count == bubble.count), "zoom {}", zoom);
+                    // ...and split into several that together hold the same businesses at it
+                    let after: Vec<ClusterMarker> = index.markers(bubble.expansion_zoom as f64, Some(&bounds))
+                        .into_iter()
+                        .filter(|marker| marker.count < bubble.count)
+                        .collect();
+                    assert!(after.len() >= 2, "zoom {}: {:?}", zoom, after);
+                    assert!(after.iter().map(|marker| marker.count).sum::<usize>() >= bubble.count);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn large_sets_count_every_business_once_per_level() {
+        let points = scattered(10_000);
+        let ids: HashSet<String> = points.iter().map(|point| point.id.clone()).collect();
+        let index = ClusterIndex::build(points);
+        assert_eq!(index.len(), 10_000);
+        let mut previous = 0;
+        for zoom in 0..=MAX_CLUSTER_ZOOM + 1 {
+            let markers = index.markers(zoom as f64, None);
+            assert_eq!(markers.iter().map(|marker| marker.count).sum::<usize>(), 10_000, "zoom {}", zoom);
+            let singles: Vec<&String> = markers.iter().filter_map(|marker| marker.business_id.as_ref()).collect();
+            let unique: HashSet<&String> = singles.iter().copied().collect();
+            assert_eq!(unique.len(), singles.len(), "zoom {}", zoom);
+            assert!(unique.iter().all(|id| ids.contains(*id)));
+            let keys: HashSet<&String> = markers.iter().map(|marker| &marker.key).collect();
+            assert_eq!(keys.len(), markers.len(), "zoom {}", zoom);
+            // Zooming in never merges
+            assert!(markers.len() >= previous, "zoom {}", zoom);
+            previous = markers.len();
+        }
+        assert_eq!(previous, 10_000);
+    }
+}
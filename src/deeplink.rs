@@ -0,0 +1,207 @@
+use makepad_widgets::*;
+use crate::url;
+
+/// URL scheme the app registers for its links.
+pub const SCHEME: &str = "makepadyelp";
+/// Deepest zoom a map link may ask for.
+const MAX_LINK_ZOOM: f64 = 20.0;
+
+// =====================
+// Deep Links
+// =====================
+
+/// A place in the app a `makepadyelp://` URL points at.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DeepLink {
+    /// `makepadyelp://biz/{id}`
+    Business(String),
+    /// `makepadyelp://search?q=tacos&loc=Mission`; without `loc` the current search center is kept
+    Search { query: String, location: Option<String> },
+    /// `makepadyelp://map?lat=37.76&lng=-122.42&z=15`; `z` is optional
+    Map { lat: f64, lng: f64, zoom: Option<f64> },
+}
+
+impl DeepLink {
+    /// Parses `makepadyelp://...` (or `makepadyelp:...`); the scheme is case-insensitive.
+    pub fn parse(link: &str) -> Result<Self, String> {
+        let link = link.trim();
+        let rest = strip_scheme(link).ok_or_else(|| format!("not a {} link: {}", SCHEME, link))?;
+        let rest = rest.split('#').next().unwrap_or(rest).trim_start_matches('/');
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let params = url::parse_query(query);
+        let param = |key: &str| params.iter().find(|(name, _)| name == key).map(|(_, value)| value.as_str());
+        let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+        match segments.as_slice() {
+            ["biz", id] => Ok(DeepLink::Business(url::percent_decode(id))),
+            ["search"] => Ok(DeepLink::Search {
+                query: param("q").unwrap_or("").trim().to_string(),
+                location: param("loc").map(str::trim).filter(|loc| !loc.is_empty()).map(String::from),
+            }),
+            ["map"] => {
+                let lat = number(param("lat"), "lat", 90.0)?;
+                let lng = number(param("lng"), "lng", 180.0)?;
+                let zoom = param("z")
+                    .map(|zoom| number(Some(zoom), "z", f64::MAX))
+                    .transpose()?
+                    .map(|zoom| zoom.clamp(0.0, MAX_LINK_ZOOM));
+                Ok(DeepLink::Map { lat, lng, zoom })
+            }
+            _ => Err(format!("unknown link: {}", link)),
+        }
+    }
+
+    /// The link as a URL; `parse` reads it back.
+    pub fn to_url(&self) -> String {
+        match self {
+            DeepLink::Business(id) => format!("{}://biz/{}", SCHEME, url::percent_encode(id)),
+            DeepLink::Search { query, location } => {
+                let mut params = vec![("q", query.clone())];
+                if let Some(location) = location {
+                    params.push(("loc", location.clone()));
+                }
+                format!("{}://search?{}", SCHEME, url::query_string(&params))
+            }
+            DeepLink::Map { lat, lng, zoom } => {
+                let mut params = vec![("lat", format!("{:.6}", lat)), ("lng", format!("{:.6}", lng))];
+                if let Some(zoom) = zoom {
+                    params.push(("z", format!("{}", zoom)));
+                }
+                format!("{}://map?{}", SCHEME, url::query_string(&params))
+            }
+        }
+    }
+}
+
+/// First deep link among command-line arguments; other arguments and malformed links are skipped.
+pub fn from_args(args: impl IntoIterator<Item = String>) -> Option<DeepLink> {
+    args.into_iter()
+        .filter(|arg| strip_scheme(arg.trim()).is_some())
+        .find_map(|arg| match DeepLink::parse(&arg) {
+            Ok(link) => Some(link),
+            Err(err) => {
+                log!("Ignoring deep link argument: {}", err);
+                None
+            }
+        })
+}
+
+/// What follows `makepadyelp:`, if the link has that scheme.
+fn strip_scheme(link: &str) -> Option<&str> {
+    let (scheme, rest) = link.split_once(':')?;
+    scheme.eq_ignore_ascii_case(SCHEME).then_some(rest)
+}
+
+/// A required number parameter no further from zero than `limit`.
+fn number(value: Option<&str>, name: &str, limit: f64) -> Result<f64, String> {
+    let value = value.ok_or_else(|| format!("missing {}", name))?;
+    match value.trim().parse::<f64>() {
+        Ok(number) if number.is_finite() && number.abs() <= limit => Ok(number),
+        _ => Err(format!("invalid {}: {}", name, value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(lat: f64, lng: f64, zoom: Option<f64>) -> DeepLink {
+        DeepLink::Map { lat, lng, zoom }
+    }
+
+    #[test]
+    fn parses_business_links() {
+        let id = "flour-water-san-francisco";
+        assert_eq!(DeepLink::parse("makepadyelp://biz/flour-water-san-francisco"), Ok(DeepLink::Business(id.into())));
+        // Scheme is case-insensitive and the slashes are optional
+        assert_eq!(DeepLink::parse(" MakepadYelp:biz/flour-water-san-francisco/ "), Ok(DeepLink::Business(id.into())));
+        assert_eq!(DeepLink::parse("makepadyelp://biz/tartine%20bakery"), Ok(DeepLink::Business("tartine bakery".into())));
+    }
+
+    #[test]
+    fn parses_search_links() {
+        assert_eq!(
+            DeepLink::parse("makepadyelp://search?q=tacos&loc=Mission"),
+            Ok(DeepLink::Search { query: "tacos".into(), location: Some("Mission".into()) })
+        );
+        // A blank location keeps the current search center
+        assert_eq!(
+            DeepLink::parse("makepadyelp://search?q=%20dim+sum%20&loc=%20"),
+            Ok(DeepLink::Search { query: "dim sum".into(), location: None })
+        );
+        assert_eq!(
+            DeepLink::parse("makepadyelp://search"),
+            Ok(DeepLink::Search { query: String::new(), location: None })
+        );
+    }
+
+    #[test]
+    fn parses_map_links() {
+        assert_eq!(
+            DeepLink::parse("makepadyelp://map?lat=37.7599&lng=-122.4148&z=15"),
+            Ok(map(37.7599, -122.4148, Some(15.0)))
+        );
+        assert_eq!(DeepLink::parse("makepadyelp://map?lng=-122.4148&lat=37.7599#pin"), Ok(map(37.7599, -122.4148, None)));
+        // Zoom is clamped rather than rejected
+        assert_eq!(DeepLink::parse("makepadyelp://map?lat=0&lng=0&z=25"), Ok(map(0.0, 0.0, Some(MAX_LINK_ZOOM))));
+        assert_eq!(DeepLink::parse("makepadyelp://map?lat=0&lng=0&z=-3"), Ok(map(0.0, 0.0, Some(0.0))));
+    }
+
+    #[test]
+    fn urls_round_trip() {
+        let links = [
+            DeepLink::Business("tartine bakery/san francisco".into()),
+            DeepLink::Search { query: "tacos & más".into(), location: Some("Mission, SF".into()) },
+            DeepLink::Search { query: "100% kona?".into(), location: None },
+            map(37.7599, -122.4148, Some(15.5)),
+            map(-33.8688, 151.2093, None),
+        ];
+        for link in links {
+            assert_eq!(DeepLink::parse(&link.to_url()), Ok(link.clone()), "{}", link.to_url());
+        }
+    }
+
+    #[test]
+    fn to_url_percent_encodes() {
+        let link = DeepLink::Search { query: "tacos & más".into(), location: Some("Mission, SF".into()) };
+        assert_eq!(link.to_url(), "makepadyelp://search?q=tacos%20%26%20m%C3%A1s&loc=Mission%2C%20SF");
+    }
+
+    #[test]
+    fn rejects_bad_coordinates() {
+        for link in [
+            "makepadyelp://map?lat=90.5&lng=0",
+            "makepadyelp://map?lat=0&lng=-180.5",
+            "makepadyelp://map?lat=north&lng=0",
+            "makepadyelp://map?lat=NaN&lng=0",
+            "makepadyelp://map?lat=0&lng=0&z=far",
+            "makepadyelp://map?lat=0&lng=0&z=inf",
+            "makepadyelp://map?lng=0",
+        ] {
+            assert!(DeepLink::parse(link).is_err(), "{}", link);
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_links_and_schemes() {
+        for link in [
+            "makepadyelp://photos/123",
+            "makepadyelp://biz",
+            "makepadyelp://biz/a/b",
+            "https://www.yelp.com/biz/tartine",
+            "makepadyelpx://biz/tartine",
+            "biz/tartine",
+            "",
+        ] {
+            assert!(DeepLink::parse(link).is_err(), "{}", link);
+        }
+    }
+
+    #[test]
+    fn from_args_takes_the_first_valid_link() {
+        let args = ["--verbose", "makepadyelp://nowhere", "makepadyelp://biz/first", "makepadyelp://biz/second"];
+        let link = from_args(args.iter().map(|arg| arg.to_string()));
+        assert_eq!(link, Some(DeepLink::Business("first".into())));
+        assert_eq!(from_args(["--verbose".to_string()]), None);
+        assert_eq!(from_args(Vec::new()), None);
+    }
+}
@@ -0,0 +1,71 @@
+// =====================
+// Geographic Helpers
+// =====================
+
+/// Mean Earth radius in meters.
+pub const EARTH_RADIUS_METERS: f64 = 6_371_008.8;
+
+pub const METERS_PER_MILE: f64 = 1609.34;
+
+/// Great-circle distance between two coordinates, in meters.
+pub fn haversine_meters(lat1: f64, lng1: f64, lat2: f64, lng2: f64) -> f64 {
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let d_phi = (lat2 - lat1).to_radians();
+    let d_lambda = (lng2 - lng1).to_radians();
+    let a = (d_phi * 0.5).sin().powi(2) + phi1.cos() * phi2.cos() * (d_lambda * 0.5).sin().powi(2);
+    2.0 * EARTH_RADIUS_METERS * a.sqrt().atan2((1.0 - a).sqrt())
+}
+
+/// Web Mercator tile edge in pixels; zoom `z` shows the world `TILE_SIZE * 2^z` pixels wide.
+pub const TILE_SIZE: f64 = 256.0;
+
+/// Latitude/longitude rectangle, such as the part of the map on screen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bounds {
+    pub south: f64,
+    pub west: f64,
+    pub north: f64,
+    pub east: f64,
+}
+
+impl Bounds {
+    /// Area shown by a `width` x `height` pixel Web Mercator viewport centered on `lat`/`lng`.
+    pub fn from_viewport(lat: f64, lng: f64, zoom: f64, width: f64, height: f64) -> Self {
+        let world = TILE_SIZE * 2f64.powf(zoom);
+        let (x, y) = mercator_project(lat, lng);
+        let (half_w, half_h) = (width * 0.5 / world, height * 0.5 / world);
+        let (north, west) = mercator_unproject(x - half_w, y - half_h);
+        let (south, east) = mercator_unproject(x + half_w, y + half_h);
+        Self { south, west, north, east }
+    }
+
+    pub fn contains(&self, lat: f64, lng: f64) -> bool {
+        (self.south..=self.north).contains(&lat) && (self.west..=self.east).contains(&lng)
+    }
+
+    pub fn center(&self) -> (f64, f64) {
+        ((self.south + self.north) * 0.5, (self.west + self.east) * 0.5)
+    }
+
+    /// Distance from the center to a corner, for services that only take a radius.
+    pub fn radius_meters(&self) -> f64 {
+        let (lat, lng) = self.center();
+        haversine_meters(lat, lng, self.north, self.east)
+    }
+}
+
+/// Position on the Web Mercator square, both axes 0..1 from the north-west corner.
+pub fn mercator_project(lat: f64, lng: f64) -> (f64, f64) {
+    let sin = lat.to_radians().sin().clamp(-0.9999, 0.9999);
+    let x = (lng + 180.0) / 360.0;
+    let y = 0.5 - ((1.0 + sin) / (1.0 - sin)).ln() / (4.0 * std::f64::consts::PI);
+    (x, y)
+}
+
+/// Inverse of `mercator_project`, returning latitude and longitude.
+pub fn mercator_unproject(x: f64, y: f64) -> (f64, f64) {
+    let lng = x * 360.0 - 180.0;
+    let n = std::f64::consts::PI * (1.0 - 2.0 * y);
+    let lat = n.sinh().atan().to_degrees();
+    (lat, lng)
+}
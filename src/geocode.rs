@@ -0,0 +1,142 @@
+// =====================
+// Geocoding
+// =====================
+
+/// A named location searches can be centered on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Place {
+    pub name: String,
+    pub lat: f64,
+    pub lng: f64,
+}
+
+/// Turns free text typed by the user into candidate places, best match first.
+pub trait Geocoder {
+    fn geocode(&self, query: &str, limit: usize) -> Vec<Place>;
+}
+
+struct GazetteerEntry {
+    name: &'static str,
+    /// Other names people type for the same place
+    aliases: &'static [&'static str],
+    lat: f64,
+    lng: f64,
+}
+
+/// Offline geocoder over a fixed list of places; the default is San Francisco's neighborhoods.
+pub struct Gazetteer {
+    entries: Vec<GazetteerEntry>,
+}
+
+impl Gazetteer {
+    pub fn san_francisco() -> Self {
+        let entries = SF_NEIGHBORHOODS
+            .iter()
+            .map(|&(name, aliases, lat, lng)| GazetteerEntry { name, aliases, lat, lng })
+            .collect();
+        Self { entries }
+    }
+}
+
+impl Default for Gazetteer {
+    fn default() -> Self {
+        Self::san_francisco()
+    }
+}
+
+impl Geocoder for Gazetteer {
+    fn geocode(&self, query: &str, limit: usize) -> Vec<Place> {
+        let query = normalize(query);
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let mut scored: Vec<(u8, &GazetteerEntry)> = self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                std::iter::once(entry.name)
+                    .chain(entry.aliases.iter().copied())
+                    .filter_map(|name| match_score(&normalize(name), &query))
+                    .max()
+                    .map(|score| (score, entry))
+            })
+            .collect();
+        // Stable, so equal scores keep the table order
+        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        scored
+            .into_iter()
+            .take(limit)
+            .map(|(_, entry)| Place { name: entry.name.to_string(), lat: entry.lat, lng: entry.lng })
+            .collect()
+    }
+}
+
+/// Exact name beats a prefix, which beats a word inside the name, which beats any substring.
+fn match_score(name: &str, query: &str) -> Option<u8> {
+    if name == query {
+        Some(3)
+    } else if name.starts_with(query) {
+        Some(2)
+    } else if name.split(' ').any(|word| word.starts_with(query)) {
+        Some(1)
+    } else if name.contains(query) {
+        Some(0)
+    } else {
+        None
+    }
+}
+
+/// Lowercase words separated by single spaces, without punctuation or a trailing ", San Francisco".
+fn normalize(text: &str) -> String {
+    let text = text.to_lowercase();
+    let text = text
+        .trim()
+        .trim_end_matches(", ca")
+        .trim_end_matches(", san francisco")
+        .trim_end_matches(", sf");
+    // "Fisherman's" and "fishermans" should match, "Haight-Ashbury" and "haight ashbury" too
+    text.chars()
+        .filter(|c| !matches!(c, '\'' | '’'))
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Neighborhood centers: name, aliases, latitude, longitude.
+const SF_NEIGHBORHOODS: &[(&str, &[&str], f64, f64)] = &[
+    ("San Francisco", &["SF", "Downtown"], 37.7749, -122.4194),
+    ("Mission District", &["Mission", "The Mission"], 37.7599, -122.4148),
+    ("SoMa", &["South of Market"], 37.7785, -122.4056),
+    ("Financial District", &["FiDi"], 37.7946, -122.3999),
+    ("Union Square", &[], 37.7880, -122.4075),
+    ("Chinatown", &[], 37.7941, -122.4078),
+    ("North Beach", &[], 37.8061, -122.4103),
+    ("Fisherman's Wharf", &["Wharf", "Pier 39"], 37.8080, -122.4177),
+    ("Russian Hill", &[], 37.8011, -122.4194),
+    ("Nob Hill", &[], 37.7930, -122.4161),
+    ("Tenderloin", &[], 37.7847, -122.4141),
+    ("Hayes Valley", &[], 37.7759, -122.4245),
+    ("Lower Haight", &[], 37.7717, -122.4316),
+    ("Haight-Ashbury", &["Haight", "Upper Haight"], 37.7692, -122.4481),
+    ("Castro", &["The Castro"], 37.7609, -122.4350),
+    ("Noe Valley", &[], 37.7502, -122.4337),
+    ("Bernal Heights", &["Bernal"], 37.7389, -122.4152),
+    ("Potrero Hill", &["Potrero"], 37.7605, -122.4009),
+    ("Dogpatch", &[], 37.7577, -122.3886),
+    ("Mission Bay", &[], 37.7706, -122.3918),
+    ("Embarcadero", &["Ferry Building"], 37.7955, -122.3937),
+    ("Marina", &["Marina District"], 37.8037, -122.4368),
+    ("Cow Hollow", &[], 37.7979, -122.4352),
+    ("Pacific Heights", &["Pac Heights"], 37.7925, -122.4382),
+    ("Japantown", &["Japan Town"], 37.7854, -122.4294),
+    ("Western Addition", &["Fillmore", "NoPa", "North of the Panhandle"], 37.7810, -122.4330),
+    ("Inner Richmond", &["Richmond"], 37.7800, -122.4650),
+    ("Outer Richmond", &[], 37.7775, -122.4950),
+    ("Inner Sunset", &["Sunset"], 37.7603, -122.4675),
+    ("Outer Sunset", &[], 37.7550, -122.4950),
+    ("Glen Park", &[], 37.7340, -122.4337),
+    ("Excelsior", &[], 37.7245, -122.4250),
+    ("Presidio", &[], 37.7989, -122.4662),
+];
@@ -0,0 +1,626 @@
+use makepad_widgets::makepad_micro_serde::*;
+
+const DAY_NAMES: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const MINUTES_PER_DAY: i64 = 24 * 60;
+/// How far ahead to look for the next opening; a week covers every regular schedule.
+const LOOKAHEAD_DAYS: i64 = 8;
+/// Below this many minutes an open business shows "Closes in N min".
+const CLOSING_SOON_MINUTES: i64 = 60;
+
+/// Zone used for businesses that don't report one (all sample listings are in San Francisco).
+pub const DEFAULT_TIME_ZONE: &str = "America/Los_Angeles";
+
+// =====================
+// Weekly Schedule
+// =====================
+
+/// One opening span in Yelp's format: `day` 0 is Monday, times are `HHMM`.
+/// Overnight spans end on the following day (e.g. 1800-0200). Several spans
+/// on the same day make a split shift.
+#[derive(Clone, Debug, PartialEq, SerJson, DeJson)]
+pub struct OpenInterval {
+    pub day: u8,
+    pub start: u16,
+    pub end: u16,
+    pub is_overnight: bool,
+}
+
+/// Replaces the regular hours on one date, e.g. a holiday closure.
+#[derive(Clone, Debug, PartialEq, SerJson, DeJson)]
+pub struct SpecialHours {
+    /// Local date as `YYYY-MM-DD`
+    pub date: String,
+    pub is_closed: bool,
+    pub start: Option<u16>,
+    pub end: Option<u16>,
+    pub is_overnight: bool,
+}
+
+fn hhmm_minutes(hhmm: u16) -> i64 {
+    (hhmm / 100) as i64 * 60 + (hhmm % 100) as i64
+}
+
+/// Span as local minutes since the epoch, for a span starting on local `day`.
+fn span_on(day: i64, start: u16, end: u16, is_overnight: bool) -> (i64, i64) {
+    let start = day * MINUTES_PER_DAY + hhmm_minutes(start);
+    let mut end = day * MINUTES_PER_DAY + hhmm_minutes(end);
+    if is_overnight || end <= start {
+        end += MINUTES_PER_DAY;
+    }
+    (start, end)
+}
+
+/// "11 AM", "10:30 PM" for a minute of the day.
+pub fn format_clock(minute_of_day: i64) -> String {
+    let minute_of_day = minute_of_day.rem_euclid(MINUTES_PER_DAY);
+    let (hour, minute) = (minute_of_day / 60, minute_of_day % 60);
+    let suffix = if hour < 12 { "AM" } else { "PM" };
+    let hour12 = match hour % 12 { 0 => 12, h => h };
+    if minute == 0 {
+        format!("{} {}", hour12, suffix)
+    } else {
+        format!("{}:{:02} {}", hour12, minute, suffix)
+    }
+}
+
+/// One line per weekday, e.g. "Mon  11 AM - 2 PM, 5 PM - 10 PM".
+pub fn weekly_text(regular: &[OpenInterval]) -> String {
+    if regular.is_empty() {
+        return "Hours not available".to_string();
+    }
+    DAY_NAMES
+        .iter()
+        .enumerate()
+        .map(|(day, name)| {
+            let mut spans: Vec<&OpenInterval> = regular.iter().filter(|span| span.day as usize == day).collect();
+            spans.sort_by_key(|span| span.start);
+            let text = if spans.is_empty() {
+                "Closed".to_string()
+            } else {
+                spans
+                    .iter()
+                    .map(|span| format!("{} - {}", format_clock(hhmm_minutes(span.start)), format_clock(hhmm_minutes(span.end))))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            format!("{}  {}", name, text)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// =====================
+// Civil Dates
+// =====================
+
+/// Days since 1970-01-01 for a proleptic Gregorian date (Howard Hinnant's algorithm).
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Inverse of `days_from_civil`: (year, month, day).
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// 0 is Monday; 1970-01-01 was a Thursday.
+pub fn weekday(days: i64) -> u8 {
+    (days + 3).rem_euclid(7) as u8
+}
+
+fn nth_sunday(year: i64, month: u32, n: i64) -> i64 {
+    let first = days_from_civil(year, month, 1);
+    first + (6 - weekday(first) as i64) + (n - 1) * 7
+}
+
+fn last_sunday(year: i64, month: u32) -> i64 {
+    let last = days_from_civil(year, month + 1, 1) - 1;
+    last - (weekday(last) as i64 + 1) % 7
+}
+
+/// Wall-clock time in a zone: local day since the epoch and minute of that day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LocalDateTime {
+    pub day: i64,
+    pub minute: i64,
+}
+
+impl LocalDateTime {
+    fn from_minutes(local_minutes: i64) -> Self {
+        Self { day: local_minutes.div_euclid(MINUTES_PER_DAY), minute: local_minutes.rem_euclid(MINUTES_PER_DAY) }
+    }
+
+    pub fn weekday(&self) -> u8 {
+        weekday(self.day)
+    }
+
+    /// `YYYY-MM-DD`, the format used by `SpecialHours::date`.
+    pub fn date_string(&self) -> String {
+        let (year, month, day) = civil_from_days(self.day);
+        format!("{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+// =====================
+// Time Zones
+// =====================
+
+#[derive(Clone, Copy, Debug)]
+pub enum DstRule {
+    /// Second Sunday of March to first Sunday of November, switching at 02:00 local
+    UnitedStates,
+    /// Last Sunday of March to last Sunday of October, switching at 01:00 UTC
+    European,
+}
+
+/// Fixed rules for the zones listings are likely to be in; no tz database is bundled.
+#[derive(Clone, Copy, Debug)]
+pub struct TimeZoneRule {
+    pub name: &'static str,
+    pub std_offset_minutes: i64,
+    pub dst: Option<DstRule>,
+}
+
+pub const TIME_ZONES: &[TimeZoneRule] = &[
+    TimeZoneRule { name: "America/Los_Angeles", std_offset_minutes: -8 * 60, dst: Some(DstRule::UnitedStates) },
+    TimeZoneRule { name: "America/Denver", std_offset_minutes: -7 * 60, dst: Some(DstRule::UnitedStates) },
+    TimeZoneRule { name: "America/Phoenix", std_offset_minutes: -7 * 60, dst: None },
+    TimeZoneRule { name: "America/Chicago", std_offset_minutes: -6 * 60, dst: Some(DstRule::UnitedStates) },
+    TimeZoneRule { name: "America/New_York", std_offset_minutes: -5 * 60, dst: Some(DstRule::UnitedStates) },
+    TimeZoneRule { name: "America/Anchorage", std_offset_minutes: -9 * 60, dst: Some(DstRule::UnitedStates) },
+    TimeZoneRule { name: "Pacific/Honolulu", std_offset_minutes: -10 * 60, dst: None },
+    TimeZoneRule { name: "Europe/London", std_offset_minutes: 0, dst: Some(DstRule::European) },
+    TimeZoneRule { name: "Europe/Paris", std_offset_minutes: 60, dst: Some(DstRule::European) },
+    TimeZoneRule { name: "Europe/Berlin", std_offset_minutes: 60, dst: Some(DstRule::European) },
+    TimeZoneRule { name: "UTC", std_offset_minutes: 0, dst: None },
+];
+
+/// Rule for an IANA zone name; unknown or missing names use `DEFAULT_TIME_ZONE`.
+pub fn time_zone(name: Option<&str>) -> &'static TimeZoneRule {
+    let name = name.unwrap_or(DEFAULT_TIME_ZONE);
+    TIME_ZONES
+        .iter()
+        .find(|zone| zone.name == name)
+        .or_else(|| TIME_ZONES.iter().find(|zone| zone.name == DEFAULT_TIME_ZONE))
+        .expect("default time zone is in the table")
+}
+
+impl TimeZoneRule {
+    /// UTC offset in effect at `unix_secs`.
+    pub fn offset_minutes(&self, unix_secs: i64) -> i64 {
+        let Some(dst) = self.dst else { return self.std_offset_minutes };
+        let unix_minutes = unix_secs.div_euclid(60);
+        let (year, _, _) = civil_from_days(unix_minutes.div_euclid(MINUTES_PER_DAY));
+        // Transition instants in UTC minutes
+        let (start, end) = match dst {
+            DstRule::UnitedStates => (
+                nth_sunday(year, 3, 2) * MINUTES_PER_DAY + 120 - self.std_offset_minutes,
+                nth_sunday(year, 11, 1) * MINUTES_PER_DAY + 120 - (self.std_offset_minutes + 60),
+            ),
+            DstRule::European => (
+                last_sunday(year, 3) * MINUTES_PER_DAY + 60,
+                last_sunday(year, 10) * MINUTES_PER_DAY + 60,
+            ),
+        };
+        if unix_minutes >= start && unix_minutes < end {
+            self.std_offset_minutes + 60
+        } else {
+            self.std_offset_minutes
+        }
+    }
+
+    pub fn to_local(&self, unix_secs: i64) -> LocalDateTime {
+        LocalDateTime::from_minutes(unix_secs.div_euclid(60) + self.offset_minutes(unix_secs))
+    }
+
+    /// Instant of a local wall-clock minute. Times skipped by spring-forward resolve
+    /// an hour later; times repeated by fall-back resolve to the first occurrence.
+    pub fn to_unix(&self, local_minutes: i64) -> i64 {
+        let guess = (local_minutes - self.std_offset_minutes - 60) * 60;
+        let offset = self.offset_minutes(guess);
+        (local_minutes - offset) * 60
+    }
+}
+
+pub fn now_unix() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+// =====================
+// Open / Closed
+// =====================
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum OpenState {
+    /// `closes` is None when no closing time falls within the lookahead (open 24/7)
+    Open { closes: Option<LocalDateTime>, minutes_left: Option<i64> },
+    /// `opens` is None when the business has no upcoming hours
+    Closed { opens: Option<LocalDateTime>, minutes_until: Option<i64> },
+}
+
+impl OpenState {
+    pub fn is_open(&self) -> bool {
+        matches!(self, OpenState::Open { .. })
+    }
+}
+
+/// A business's regular and special hours in its own time zone.
+pub struct Schedule<'a> {
+    pub regular: &'a [OpenInterval],
+    pub special: &'a [SpecialHours],
+    pub zone: &'static TimeZoneRule,
+}
+
+impl<'a> Schedule<'a> {
+    pub fn new(regular: &'a [OpenInterval], special: &'a [SpecialHours], zone: Option<&str>) -> Self {
+        Self { regular, special, zone: time_zone(zone) }
+    }
+
+    pub fn has_hours(&self) -> bool {
+        !self.regular.is_empty() || !self.special.is_empty()
+    }
+
+    /// Spans starting on local `day`, in local minutes since the epoch. A special
+    /// entry for the date replaces the regular hours of that weekday.
+    fn spans_starting_on(&self, day: i64) -> Vec<(i64, i64)> {
+        let date = LocalDateTime { day, minute: 0 }.date_string();
+        let special: Vec<&SpecialHours> = self.special.iter().filter(|special| special.date == date).collect();
+        if !special.is_empty() {
+            return special
+                .iter()
+                .filter(|special| !special.is_closed)
+                .filter_map(|special| Some(span_on(day, special.start?, special.end?, special.is_overnight)))
+                .collect();
+        }
+        let weekday = weekday(day);
+        self.regular
+            .iter()
+            .filter(|span| span.day == weekday)
+            .map(|span| span_on(day, span.start, span.end, span.is_overnight))
+            .collect()
+    }
+
+    /// Open or closed at `unix_secs`; None when there are no hours at all.
+    pub fn state_at(&self, unix_secs: i64) -> Option<OpenState> {
+        if !self.has_hours() {
+            return None;
+        }
+        let now = self.zone.to_local(unix_secs);
+        let now_minutes = now.day * MINUTES_PER_DAY + now.minute;
+        // Yesterday's overnight spans can still be running
+        let mut spans: Vec<(i64, i64)> = (-1..LOOKAHEAD_DAYS)
+            .flat_map(|offset| self.spans_starting_on(now.day + offset))
+            .collect();
+        spans.sort();
+
+        let minutes_until = |local_minutes: i64| (self.zone.to_unix(local_minutes) - unix_secs) / 60;
+        if let Some(&(_, mut close)) = spans.iter().find(|(start, end)| *start <= now_minutes && now_minutes < *end) {
+            // Follow spans that start before the previous one ends (back-to-back or overlapping)
+            for &(start, end) in &spans {
+                if start <= close && end > close {
+                    close = end;
+                }
+            }
+            let horizon = (now.day + LOOKAHEAD_DAYS - 1) * MINUTES_PER_DAY;
+            return Some(if close >= horizon {
+                OpenState::Open { closes: None, minutes_left: None }
+            } else {
+                OpenState::Open { closes: Some(LocalDateTime::from_minutes(close)), minutes_left: Some(minutes_until(close)) }
+            });
+        }
+        let next = spans.iter().map(|(start, _)| *start).find(|start| *start > now_minutes);
+        Some(OpenState::Closed {
+            opens: next.map(LocalDateTime::from_minutes),
+            minutes_until: next.map(minutes_until),
+        })
+    }
+
+    /// "Open until 10 PM", "Closes in 30 min", "Opens tomorrow at 9 AM", ...
+    pub fn describe_at(&self, unix_secs: i64) -> Option<String> {
+        let state = self.state_at(unix_secs)?;
+        let today = self.zone.to_local(unix_secs).day;
+        Some(match state {
+            OpenState::Open { closes: None, .. } => "Open 24 hours".to_string(),
+            OpenState::Open { minutes_left: Some(left), .. } if left <= CLOSING_SOON_MINUTES => {
+                format!("Closes in {} min", left.max(1))
+            }
+            OpenState::Open { closes: Some(closes), .. } => format!("Open until {}", format_clock(closes.minute)),
+            OpenState::Closed { opens: None, .. } => "Closed".to_string(),
+            OpenState::Closed { opens: Some(opens), .. } => {
+                let clock = format_clock(opens.minute);
+                match opens.day - today {
+                    0 => format!("Opens at {}", clock),
+                    1 => format!("Opens tomorrow at {}", clock),
+                    _ => format!("Opens {} at {}", DAY_NAMES[opens.weekday() as usize], clock),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Unix seconds of a UTC wall-clock time.
+    fn utc(year: i64, month: u32, day: u32, hour: i64, minute: i64) -> i64 {
+        (days_from_civil(year, month, day) * MINUTES_PER_DAY + hour * 60 + minute) * 60
+    }
+
+    /// Local minutes since the epoch of a wall-clock time, as `to_unix` takes them.
+    fn local(year: i64, month: u32, day: u32, hour: i64, minute: i64) -> i64 {
+        days_from_civil(year, month, day) * MINUTES_PER_DAY + hour * 60 + minute
+    }
+
+    fn zone(name: &str) -> &'static TimeZoneRule {
+        let zone = time_zone(Some(name));
+        assert_eq!(zone.name, name);
+        zone
+    }
+
+    fn interval(day: u8, start: u16, end: u16, is_overnight: bool) -> OpenInterval {
+        OpenInterval { day, start, end, is_overnight }
+    }
+
+    fn special(date: &str, hours: Option<(u16, u16)>) -> SpecialHours {
+        SpecialHours {
+            date: date.to_string(),
+            is_closed: hours.is_none(),
+            start: hours.map(|(start, _)| start),
+            end: hours.map(|(_, end)| end),
+            is_overnight: false,
+        }
+    }
+
+    /// Unix seconds of a local wall-clock time in Los Angeles (no DST ambiguity in the dates used).
+    fn in_la(year: i64, month: u32, day: u32, hour: i64, minute: i64) -> i64 {
+        zone("America/Los_Angeles").to_unix(local(year, month, day, hour, minute))
+    }
+
+    // Time zones
+
+    #[test]
+    fn us_offset_switches_at_spring_forward() {
+        let la = zone("America/Los_Angeles");
+        // 2026-03-08 02:00 PST is 10:00 UTC
+        assert_eq!(la.offset_minutes(utc(2026, 3, 8, 9, 59)), -480);
+        assert_eq!(la.offset_minutes(utc(2026, 3, 8, 10, 0)), -420);
+    }
+
+    #[test]
+    fn us_offset_switches_at_fall_back() {
+        let la = zone("America/Los_Angeles");
+        // 2026-11-01 02:00 PDT is 09:00 UTC
+        assert_eq!(la.offset_minutes(utc(2026, 11, 1, 8, 59)), -420);
+        assert_eq!(la.offset_minutes(utc(2026, 11, 1, 9, 0)), -480);
+    }
+
+    #[test]
+    fn us_transitions_follow_each_zones_local_clock() {
+        let new_york = zone("America/New_York");
+        // 02:00 EST is 07:00 UTC, three hours before Los Angeles switches
+        assert_eq!(new_york.offset_minutes(utc(2026, 3, 8, 6, 59)), -300);
+        assert_eq!(new_york.offset_minutes(utc(2026, 3, 8, 7, 0)), -240);
+        assert_eq!(new_york.offset_minutes(utc(2026, 11, 1, 5, 59)), -240);
+        assert_eq!(new_york.offset_minutes(utc(2026, 11, 1, 6, 0)), -300);
+    }
+
+    #[test]
+    fn zones_without_dst_keep_their_offset() {
+        let phoenix = zone("America/Phoenix");
+        assert_eq!(phoenix.offset_minutes(utc(2026, 1, 15, 12, 0)), -420);
+        assert_eq!(phoenix.offset_minutes(utc(2026, 7, 15, 12, 0)), -420);
+    }
+
+    #[test]
+    fn european_offset_switches_at_one_utc() {
+        let paris = zone("Europe/Paris");
+        let london = zone("Europe/London");
+        // Last Sundays of 2026: March 29 and October 25
+        assert_eq!(paris.offset_minutes(utc(2026, 3, 29, 0, 59)), 60);
+        assert_eq!(paris.offset_minutes(utc(2026, 3, 29, 1, 0)), 120);
+        assert_eq!(paris.offset_minutes(utc(2026, 10, 25, 0, 59)), 120);
+        assert_eq!(paris.offset_minutes(utc(2026, 10, 25, 1, 0)), 60);
+        assert_eq!(london.offset_minutes(utc(2026, 3, 29, 0, 59)), 0);
+        assert_eq!(london.offset_minutes(utc(2026, 3, 29, 1, 0)), 60);
+        assert_eq!(london.offset_minutes(utc(2026, 10, 25, 0, 59)), 60);
+        assert_eq!(london.offset_minutes(utc(2026, 10, 25, 1, 0)), 0);
+    }
+
+    #[test]
+    fn us_skipped_hour_resolves_an_hour_later() {
+        let la = zone("America/Los_Angeles");
+        // 02:30 doesn't exist on 2026-03-08; it reads as 03:30 PDT
+        assert_eq!(la.to_unix(local(2026, 3, 8, 2, 30)), utc(2026, 3, 8, 10, 30));
+        assert_eq!(
+            la.to_local(utc(2026, 3, 8, 10, 30)),
+            LocalDateTime { day: days_from_civil(2026, 3, 8), minute: 3 * 60 + 30 }
+        );
+        // Either side of the gap is unaffected
+        assert_eq!(la.to_unix(local(2026, 3, 8, 1, 59)), utc(2026, 3, 8, 9, 59));
+        assert_eq!(la.to_unix(local(2026, 3, 8, 3, 0)), utc(2026, 3, 8, 10, 0));
+    }
+
+    #[test]
+    fn us_repeated_hour_resolves_to_first_occurrence() {
+        let la = zone("America/Los_Angeles");
+        // 01:30 happens twice on 2026-11-01: at 08:30 UTC (PDT) and 09:30 UTC (PST)
+        assert_eq!(la.to_local(utc(2026, 11, 1, 8, 30)), la.to_local(utc(2026, 11, 1, 9, 30)));
+        assert_eq!(la.to_unix(local(2026, 11, 1, 1, 30)), utc(2026, 11, 1, 8, 30));
+        assert_eq!(la.to_unix(local(2026, 11, 1, 0, 59)), utc(2026, 11, 1, 7, 59));
+        assert_eq!(la.to_unix(local(2026, 11, 1, 2, 0)), utc(2026, 11, 1, 10, 0));
+    }
+
+    #[test]
+    fn european_skipped_and_repeated_hours() {
+        let paris = zone("Europe/Paris");
+        // 02:30 is skipped on 2026-03-29 and reads as 03:30 CEST
+        assert_eq!(paris.to_unix(local(2026, 3, 29, 2, 30)), utc(2026, 3, 29, 1, 30));
+        assert_eq!(paris.to_unix(local(2026, 3, 29, 1, 59)), utc(2026, 3, 29, 0, 59));
+        // 02:30 happens twice on 2026-10-25; the first is 00:30 UTC (CEST)
+        assert_eq!(paris.to_local(utc(2026, 10, 25, 0, 30)), paris.to_local(utc(2026, 10, 25, 1, 30)));
+        assert_eq!(paris.to_unix(local(2026, 10, 25, 2, 30)), utc(2026, 10, 25, 0, 30));
+        assert_eq!(paris.to_unix(local(2026, 10, 25, 3, 0)), utc(2026, 10, 25, 2, 0));
+    }
+
+    #[test]
+    fn local_times_round_trip_away_from_transitions() {
+        for name in ["America/Los_Angeles", "America/Anchorage", "Europe/Berlin", "Pacific/Honolulu", "UTC"] {
+            let zone = zone(name);
+            for unix in [utc(2026, 1, 10, 12, 0), utc(2026, 7, 4, 23, 45), utc(2026, 12, 31, 23, 59)] {
+                let local = zone.to_local(unix);
+                assert_eq!(zone.to_unix(local.day * MINUTES_PER_DAY + local.minute), unix, "{}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_zones_fall_back_to_the_default() {
+        assert_eq!(time_zone(Some("Mars/Olympus_Mons")).name, DEFAULT_TIME_ZONE);
+        assert_eq!(time_zone(None).name, DEFAULT_TIME_ZONE);
+    }
+
+    // Open / closed
+
+    #[test]
+    fn overnight_span_stays_open_past_midnight() {
+        // Fridays 6 PM to 2 AM; 2026-06-05 is a Friday
+        let regular = [interval(4, 1800, 200, true)];
+        let schedule = Schedule::new(&regular, &[], None);
+        assert_eq!(schedule.describe_at(in_la(2026, 6, 5, 17, 0)).as_deref(), Some("Opens at 6 PM"));
+        assert_eq!(schedule.describe_at(in_la(2026, 6, 5, 19, 0)).as_deref(), Some("Open until 2 AM"));
+        assert_eq!(
+            schedule.state_at(in_la(2026, 6, 6, 1, 30)),
+            Some(OpenState::Open {
+                closes: Some(LocalDateTime { day: days_from_civil(2026, 6, 6), minute: 120 }),
+                minutes_left: Some(30),
+            })
+        );
+        assert_eq!(schedule.describe_at(in_la(2026, 6, 6, 1, 30)).as_deref(), Some("Closes in 30 min"));
+        assert_eq!(schedule.describe_at(in_la(2026, 6, 6, 2, 0)).as_deref(), Some("Opens Fri at 6 PM"));
+    }
+
+    #[test]
+    fn split_shift_closes_between_spans() {
+        // Mondays 11 AM - 2 PM and 5 PM - 10 PM; 2026-06-01 is a Monday
+        let regular = [interval(0, 1700, 2200, false), interval(0, 1100, 1400, false)];
+        let schedule = Schedule::new(&regular, &[], None);
+        assert_eq!(schedule.describe_at(in_la(2026, 6, 1, 10, 0)).as_deref(), Some("Opens at 11 AM"));
+        assert_eq!(schedule.describe_at(in_la(2026, 6, 1, 12, 0)).as_deref(), Some("Open until 2 PM"));
+        assert_eq!(
+            schedule.state_at(in_la(2026, 6, 1, 15, 0)),
+            Some(OpenState::Closed {
+                opens: Some(LocalDateTime { day: days_from_civil(2026, 6, 1), minute: 17 * 60 }),
+                minutes_until: Some(120),
+            })
+        );
+        assert_eq!(schedule.describe_at(in_la(2026, 6, 1, 15, 0)).as_deref(), Some("Opens at 5 PM"));
+        assert_eq!(schedule.describe_at(in_la(2026, 6, 1, 21, 30)).as_deref(), Some("Closes in 30 min"));
+        assert_eq!(schedule.describe_at(in_la(2026, 6, 1, 22, 30)).as_deref(), Some("Opens Mon at 11 AM"));
+    }
+
+    #[test]
+    fn back_to_back_spans_close_at_the_last_end() {
+        let regular = [interval(0, 1100, 1400, false), interval(0, 1400, 2200, false)];
+        let schedule = Schedule::new(&regular, &[], None);
+        assert_eq!(schedule.describe_at(in_la(2026, 6, 1, 12, 0)).as_deref(), Some("Open until 10 PM"));
+    }
+
+    #[test]
+    fn always_open_has_no_closing_time() {
+        let regular: Vec<OpenInterval> = (0..7).map(|day| interval(day, 0, 0, false)).collect();
+        let schedule = Schedule::new(&regular, &[], None);
+        assert_eq!(schedule.describe_at(in_la(2026, 6, 3, 4, 0)).as_deref(), Some("Open 24 hours"));
+    }
+
+    #[test]
+    fn overnight_span_across_spring_forward_loses_an_hour() {
+        // Saturday 2026-03-07 10 PM to 4 AM; clocks skip 2 AM - 3 AM that night
+        let regular = [interval(5, 2200, 400, true)];
+        let schedule = Schedule::new(&regular, &[], None);
+        let state = schedule.state_at(utc(2026, 3, 8, 9, 0)); // 1 AM PST
+        assert!(matches!(state, Some(OpenState::Open { minutes_left: Some(120), .. })), "{:?}", state);
+        assert_eq!(schedule.describe_at(utc(2026, 3, 8, 9, 0)).as_deref(), Some("Open until 4 AM"));
+    }
+
+    #[test]
+    fn overnight_span_across_fall_back_gains_an_hour() {
+        // Saturday 2026-10-31 10 PM to 4 AM; 1 AM - 2 AM repeats that night
+        let regular = [interval(5, 2200, 400, true)];
+        let schedule = Schedule::new(&regular, &[], None);
+        let state = schedule.state_at(utc(2026, 11, 1, 7, 30)); // 12:30 AM PDT
+        assert!(matches!(state, Some(OpenState::Open { minutes_left: Some(270), .. })), "{:?}", state);
+        // During the second 1:30 AM there are 150 minutes left, not 210
+        let state = schedule.state_at(utc(2026, 11, 1, 9, 30));
+        assert!(matches!(state, Some(OpenState::Open { minutes_left: Some(150), .. })), "{:?}", state);
+    }
+
+    #[test]
+    fn european_zone_opens_on_local_time() {
+        // Mondays 9 AM - 5 PM in Paris; 9 AM CEST on 2026-06-01 is 07:00 UTC
+        let regular = [interval(0, 900, 1700, false)];
+        let schedule = Schedule::new(&regular, &[], Some("Europe/Paris"));
+        assert_eq!(schedule.state_at(utc(2026, 6, 1, 6, 59)).map(|state| state.is_open()), Some(false));
+        assert_eq!(schedule.state_at(utc(2026, 6, 1, 7, 0)).map(|state| state.is_open()), Some(true));
+    }
+
+    // Special hours
+
+    #[test]
+    fn holiday_closure_overrides_regular_hours() {
+        // Every day 9 AM - 5 PM, closed on Christmas (a Friday in 2026)
+        let regular: Vec<OpenInterval> = (0..7).map(|day| interval(day, 900, 1700, false)).collect();
+        let special = [special("2026-12-25", None)];
+        let schedule = Schedule::new(&regular, &special, None);
+        assert_eq!(schedule.state_at(in_la(2026, 12, 25, 12, 0)).map(|state| state.is_open()), Some(false));
+        assert_eq!(schedule.describe_at(in_la(2026, 12, 25, 12, 0)).as_deref(), Some("Opens tomorrow at 9 AM"));
+        assert_eq!(schedule.describe_at(in_la(2026, 12, 24, 16, 30)).as_deref(), Some("Closes in 30 min"));
+        // The closed date is skipped when looking for the next opening
+        assert_eq!(schedule.describe_at(in_la(2026, 12, 24, 18, 0)).as_deref(), Some("Opens Sat at 9 AM"));
+    }
+
+    #[test]
+    fn special_hours_replace_the_days_spans() {
+        let regular: Vec<OpenInterval> = (0..7).map(|day| interval(day, 900, 1700, false)).collect();
+        let special = [special("2026-12-24", Some((1000, 1400)))];
+        let schedule = Schedule::new(&regular, &special, None);
+        assert_eq!(schedule.describe_at(in_la(2026, 12, 24, 9, 30)).as_deref(), Some("Opens at 10 AM"));
+        assert_eq!(schedule.describe_at(in_la(2026, 12, 24, 11, 0)).as_deref(), Some("Open until 2 PM"));
+        assert_eq!(schedule.describe_at(in_la(2026, 12, 24, 15, 0)).as_deref(), Some("Opens tomorrow at 9 AM"));
+    }
+
+    #[test]
+    fn special_hours_open_a_regularly_closed_day() {
+        // Closed on Sundays except 2026-06-07
+        let regular: Vec<OpenInterval> = (0..6).map(|day| interval(day, 900, 1700, false)).collect();
+        let special = [special("2026-06-07", Some((1200, 1600)))];
+        let schedule = Schedule::new(&regular, &special, None);
+        assert_eq!(schedule.describe_at(in_la(2026, 6, 7, 13, 0)).as_deref(), Some("Open until 4 PM"));
+        assert_eq!(schedule.describe_at(in_la(2026, 6, 14, 13, 0)).as_deref(), Some("Opens tomorrow at 9 AM"));
+    }
+
+    #[test]
+    fn no_hours_has_no_state() {
+        let schedule = Schedule::new(&[], &[], None);
+        assert_eq!(schedule.state_at(utc(2026, 6, 1, 12, 0)), None);
+        assert_eq!(schedule.describe_at(utc(2026, 6, 1, 12, 0)), None);
+    }
+}
@@ -0,0 +1,289 @@
+use makepad_widgets::*;
+use makepad_widgets::makepad_micro_serde::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use crate::storage;
+
+/// Default on-disk budget for downloaded photos.
+pub const DEFAULT_MAX_BYTES: u64 = 64 * 1024 * 1024;
+
+const INDEX_FILE: &str = "index.json";
+
+// =====================
+// Disk Image Cache
+// =====================
+
+/// Metadata for one cached response, persisted in `index.json`.
+#[derive(Clone, Debug, SerJson, DeJson)]
+pub struct CacheEntry {
+    /// Full URL, checked on lookup since file names are only a hash of it
+    pub url: String,
+    pub file: String,
+    pub size: u64,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    /// Value of the cache clock when the entry was last read or written (for LRU).
+    pub last_used: u64,
+}
+
+#[derive(Clone, Debug, Default, SerJson, DeJson)]
+struct CacheIndex {
+    clock: u64,
+    entries: Vec<CacheEntry>,
+}
+
+/// Photo bytes keyed by URL, stored one file per URL with a size cap and LRU eviction.
+/// ETag / Last-Modified are kept so entries can be revalidated with conditional requests.
+pub struct DiskImageCache {
+    dir: PathBuf,
+    max_bytes: u64,
+    clock: u64,
+    /// Keyed by file name; two URLs whose hashes collide share one slot
+    entries: HashMap<String, CacheEntry>,
+    dirty: bool,
+}
+
+impl DiskImageCache {
+    /// Opens (or creates) the cache in `dir`; a missing or corrupt index starts empty.
+    pub fn open(dir: PathBuf, max_bytes: u64) -> Self {
+        let index = std::fs::read_to_string(dir.join(INDEX_FILE))
+            .ok()
+            .and_then(|json| CacheIndex::deserialize_json(&json).ok())
+            .unwrap_or_default();
+        let entries = index.entries
+            .into_iter()
+            .filter(|entry| entry.file == file_name(&entry.url) && dir.join(&entry.file).exists())
+            .map(|entry| (entry.file.clone(), entry))
+            .collect();
+        let mut cache = Self { dir, max_bytes, clock: index.clock, entries, dirty: false };
+        cache.evict();
+        log!("Image cache: {} entries, {} bytes", cache.entries.len(), cache.total_bytes());
+        cache
+    }
+
+    pub fn contains(&self, url: &str) -> bool {
+        self.entry(url).is_some()
+    }
+
+    /// Reads cached bytes and marks the entry as recently used.
+    pub fn get(&mut self, url: &str) -> Option<Vec<u8>> {
+        let path = self.dir.join(&self.entry(url)?.file);
+        match std::fs::read(&path) {
+            Ok(bytes) => {
+                self.touch(url);
+                Some(bytes)
+            }
+            Err(_) => {
+                // File vanished underneath us; forget the entry
+                self.entries.remove(&file_name(url));
+                self.dirty = true;
+                None
+            }
+        }
+    }
+
+    /// Stores a fresh response, evicting least recently used entries past the size cap.
+    /// A URL whose hash collides with a cached one replaces it.
+    pub fn put(&mut self, url: &str, bytes: &[u8], etag: Option<String>, last_modified: Option<String>) {
+        let file = file_name(url);
+        if let Err(err) = storage::write_atomic(&self.dir.join(&file), bytes) {
+            log!("Image cache write failed for {}: {}", url, err);
+            return;
+        }
+        self.clock += 1;
+        self.entries.insert(file.clone(), CacheEntry {
+            url: url.to_string(),
+            file,
+            size: bytes.len() as u64,
+            etag,
+            last_modified,
+            last_used: self.clock,
+        });
+        self.dirty = true;
+        self.evict();
+    }
+
+    /// Marks an entry as recently used (e.g. after a 304 Not Modified).
+    pub fn touch(&mut self, url: &str) {
+        if let Some(entry) = self.entries.get_mut(&file_name(url)).filter(|entry| entry.url == url) {
+            self.clock += 1;
+            entry.last_used = self.clock;
+            self.dirty = true;
+        }
+    }
+
+    /// Conditional request headers for revalidating a cached URL.
+    pub fn revalidation_headers(&self, url: &str) -> Vec<(String, String)> {
+        let mut headers = Vec::new();
+        if let Some(entry) = self.entry(url) {
+            if let Some(ref etag) = entry.etag {
+                headers.push(("If-None-Match".to_string(), etag.clone()));
+            }
+            if let Some(ref last_modified) = entry.last_modified {
+                headers.push(("If-Modified-Since".to_string(), last_modified.clone()));
+            }
+        }
+        headers
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.entries.values().map(|entry| entry.size).sum()
+    }
+
+    /// Persists the index if anything changed since the last flush.
+    pub fn flush(&mut self) {
+        if !self.dirty { return; }
+        let index = CacheIndex { clock: self.clock, entries: self.entries.values().cloned().collect() };
+        match storage::write_atomic(&self.dir.join(INDEX_FILE), index.serialize_json().as_bytes()) {
+            Ok(()) => self.dirty = false,
+            Err(err) => log!("Image cache index write failed: {}", err),
+        }
+    }
+
+    /// The entry for `url`, unless its slot holds a different URL with the same hash.
+    fn entry(&self, url: &str) -> Option<&CacheEntry> {
+        self.entries.get(&file_name(url)).filter(|entry| entry.url == url)
+    }
+
+    fn evict(&mut self) {
+        let mut total = self.total_bytes();
+        if total <= self.max_bytes { return; }
+        let mut by_age: Vec<(u64, String)> = self.entries
+            .values()
+            .map(|entry| (entry.last_used, entry.file.clone()))
+            .collect();
+        by_age.sort();
+        for (_, file) in by_age {
+            if total <= self.max_bytes { break; }
+            if let Some(entry) = self.entries.remove(&file) {
+                let _ = std::fs::remove_file(self.dir.join(&entry.file));
+                total -= entry.size;
+                self.dirty = true;
+            }
+        }
+    }
+}
+
+/// Cache file name for `url`: a 64-bit hash, so different URLs can collide.
+fn file_name(url: &str) -> String {
+    format!("{:016x}", LiveId::from_str(url).0)
+}
+
+/// Case-insensitive lookup of a response header.
+pub fn response_header(response: &HttpResponse, name: &str) -> Option<String> {
+    response.headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .and_then(|(_, values)| values.first().cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Empty directory unique to this test and process.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("makepad-yelp-image-cache-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn url(name: &str) -> String {
+        format!("https://photos.example.com/{}.jpg", name)
+    }
+
+    #[test]
+    fn evicts_least_recently_used_past_max_bytes() {
+        let dir = temp_dir("evict");
+        let mut cache = DiskImageCache::open(dir.clone(), 10);
+        cache.put(&url("a"), b"aaaa", None, None);
+        cache.put(&url("b"), b"bbbb", None, None);
+        cache.touch(&url("a"));
+        cache.put(&url("c"), b"cccc", None, None);
+        assert!(cache.contains(&url("a")) && cache.contains(&url("c")));
+        assert!(!cache.contains(&url("b")));
+        assert!(!dir.join(file_name(&url("b"))).exists());
+        assert_eq!(cache.total_bytes(), 8);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn touch_and_get_refresh_the_lru_order() {
+        let dir = temp_dir("touch");
+        let mut cache = DiskImageCache::open(dir.clone(), 9);
+        for name in ["a", "b", "c"] {
+            cache.put(&url(name), b"xyz", None, None);
+        }
+        assert_eq!(cache.get(&url("a")), Some(b"xyz".to_vec()));
+        cache.put(&url("d"), b"xyz", None, None);
+        assert!(!cache.contains(&url("b")));
+        cache.touch(&url("c"));
+        cache.put(&url("e"), b"xyz", None, None);
+        // "a" was read before "c" was touched, so it is now the oldest
+        assert!(!cache.contains(&url("a")));
+        assert!(cache.contains(&url("c")) && cache.contains(&url("d")) && cache.contains(&url("e")));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn index_persists_across_open() {
+        let dir = temp_dir("persist");
+        let mut cache = DiskImageCache::open(dir.clone(), 100);
+        cache.put(&url("a"), b"first", Some("\"v1\"".into()), None);
+        cache.put(&url("b"), b"second", None, None);
+        cache.touch(&url("a"));
+        cache.flush();
+        // Entries whose file is gone are dropped when the index is read back
+        std::fs::remove_file(dir.join(file_name(&url("b")))).unwrap();
+
+        let mut reopened = DiskImageCache::open(dir.clone(), 10);
+        assert_eq!(reopened.get(&url("a")), Some(b"first".to_vec()));
+        assert!(!reopened.contains(&url("b")));
+        assert_eq!(reopened.revalidation_headers(&url("a")), vec![("If-None-Match".to_string(), "\"v1\"".to_string())]);
+        // The LRU clock carries over, so the older entry is still evicted first
+        reopened.put(&url("c"), b"third", None, None);
+        assert!(reopened.contains(&url("a")) && reopened.contains(&url("c")));
+        reopened.put(&url("d"), b"fourth", None, None);
+        assert!(!reopened.contains(&url("a")));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn corrupt_index_starts_empty() {
+        let dir = temp_dir("corrupt");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(INDEX_FILE), "not json").unwrap();
+        let cache = DiskImageCache::open(dir.clone(), 100);
+        assert_eq!(cache.total_bytes(), 0);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn revalidation_headers_carry_the_validators() {
+        let dir = temp_dir("headers");
+        let mut cache = DiskImageCache::open(dir.clone(), 100);
+        cache.put(&url("both"), b"x", Some("\"abc\"".into()), Some("Wed, 21 Oct 2026 07:28:00 GMT".into()));
+        cache.put(&url("none"), b"x", None, None);
+        assert_eq!(cache.revalidation_headers(&url("both")), vec![
+            ("If-None-Match".to_string(), "\"abc\"".to_string()),
+            ("If-Modified-Since".to_string(), "Wed, 21 Oct 2026 07:28:00 GMT".to_string()),
+        ]);
+        assert!(cache.revalidation_headers(&url("none")).is_empty());
+        assert!(cache.revalidation_headers(&url("unknown")).is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn colliding_url_is_a_miss() {
+        let dir = temp_dir("collision");
+        let mut cache = DiskImageCache::open(dir.clone(), 100);
+        cache.put(&url("a"), b"photo a", Some("\"a\"".into()), None);
+        // Pretend "b" hashes to the same file as "a"
+        let entry = cache.entries.remove(&file_name(&url("a"))).unwrap();
+        cache.entries.insert(file_name(&url("b")), entry);
+        assert!(!cache.contains(&url("b")));
+        assert_eq!(cache.get(&url("b")), None);
+        assert!(cache.revalidation_headers(&url("b")).is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,125 @@
+use makepad_widgets::*;
+use crate::image_pipeline::DecodedImage;
+
+// =====================
+// Format Sniffing
+// =====================
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+    Jpeg,
+    Png,
+    WebP,
+    Gif,
+}
+
+/// Why a photo could not be shown, whether it failed to download or to decode.
+#[derive(Clone, Debug)]
+pub enum ImageLoadError {
+    Empty,
+    /// Magic bytes match none of the supported formats (often an HTML error page)
+    UnknownFormat,
+    Decode { format: ImageFormat, message: String },
+    Http(u16),
+    Network(String),
+    Timeout,
+}
+
+impl std::fmt::Display for ImageLoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImageLoadError::Empty => write!(f, "empty payload"),
+            ImageLoadError::UnknownFormat => write!(f, "unrecognized image format"),
+            ImageLoadError::Decode { format, message } => write!(f, "{:?} decode failed: {}", format, message),
+            ImageLoadError::Http(status) => write!(f, "HTTP {}", status),
+            ImageLoadError::Network(message) => write!(f, "network error: {}", message),
+            ImageLoadError::Timeout => write!(f, "timed out"),
+        }
+    }
+}
+
+/// Identifies the format from the leading magic bytes, ignoring any Content-Type.
+pub fn sniff_format(bytes: &[u8]) -> Option<ImageFormat> {
+    if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some(ImageFormat::Jpeg)
+    } else if bytes.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]) {
+        Some(ImageFormat::Png)
+    } else if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Some(ImageFormat::WebP)
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some(ImageFormat::Gif)
+    } else {
+        None
+    }
+}
+
+/// Decodes any supported format into BGRA pixels. Animated GIF/WebP yield their first frame.
+pub fn decode_image(bytes: &[u8]) -> Result<DecodedImage, ImageLoadError> {
+    if bytes.is_empty() {
+        return Err(ImageLoadError::Empty);
+    }
+    let format = sniff_format(bytes).ok_or(ImageLoadError::UnknownFormat)?;
+    let decoded = match format {
+        ImageFormat::Jpeg => ImageBuffer::from_jpg(bytes)
+            .map(into_decoded)
+            .map_err(|err| format!("{:?}", err)),
+        ImageFormat::Png => ImageBuffer::from_png(bytes)
+            .map(into_decoded)
+            .map_err(|err| format!("{:?}", err)),
+        ImageFormat::WebP => decode_webp(bytes),
+        ImageFormat::Gif => decode_gif_first_frame(bytes),
+    };
+    decoded.map_err(|message| ImageLoadError::Decode { format, message })
+}
+
+fn into_decoded(buffer: ImageBuffer) -> DecodedImage {
+    DecodedImage { width: buffer.width, height: buffer.height, pixels: buffer.data }
+}
+
+/// Packs RGBA channels into the BGRA `u32` layout Makepad textures expect.
+fn pack_pixel(r: u8, g: u8, b: u8, a: u8) -> u32 {
+    (a as u32) << 24 | (r as u32) << 16 | (g as u32) << 8 | b as u32
+}
+
+fn decode_webp(bytes: &[u8]) -> Result<DecodedImage, String> {
+    let mut decoder = image_webp::WebPDecoder::new(std::io::Cursor::new(bytes))
+        .map_err(|err| err.to_string())?;
+    let (width, height) = decoder.dimensions();
+    let channels = if decoder.has_alpha() { 4 } else { 3 };
+    let size = decoder.output_buffer_size().ok_or("image too large")?;
+    let mut buffer = vec![0u8; size];
+    decoder.read_image(&mut buffer).map_err(|err| err.to_string())?;
+    let pixels = buffer
+        .chunks_exact(channels)
+        .map(|px| pack_pixel(px[0], px[1], px[2], if channels == 4 { px[3] } else { 0xFF }))
+        .collect();
+    Ok(DecodedImage { width: width as usize, height: height as usize, pixels })
+}
+
+fn decode_gif_first_frame(bytes: &[u8]) -> Result<DecodedImage, String> {
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = options.read_info(bytes).map_err(|err| err.to_string())?;
+    let (width, height) = (decoder.width() as usize, decoder.height() as usize);
+    let frame = decoder
+        .read_next_frame()
+        .map_err(|err| err.to_string())?
+        .ok_or("no frames")?;
+    if width == 0 || height == 0 || frame.width == 0 || frame.height == 0 {
+        return Err("zero-sized frame".to_string());
+    }
+
+    // The first frame may cover only part of the logical screen
+    let mut pixels = vec![0u32; width * height];
+    let (left, top) = (frame.left as usize, frame.top as usize);
+    for (row, line) in frame.buffer.chunks_exact(frame.width as usize * 4).enumerate() {
+        let y = top + row;
+        if y >= height { break; }
+        for (col, px) in line.chunks_exact(4).enumerate() {
+            let x = left + col;
+            if x >= width { break; }
+            pixels[y * width + x] = pack_pixel(px[0], px[1], px[2], px[3]);
+        }
+    }
+    Ok(DecodedImage { width, height, pixels })
+}
@@ -0,0 +1,343 @@
+use makepad_widgets::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, LazyLock, Mutex};
+use crate::image_format::{self, ImageLoadError};
+
+/// Longest edge of the card thumbnail (110pt photo at up to 2x density).
+pub const THUMBNAIL_MAX_DIM: usize = 220;
+/// Longest edge of the detail hero image.
+pub const FULL_MAX_DIM: usize = 1024;
+/// GPU budget for photo textures; least recently drawn ones are dropped past it.
+pub const MAX_TEXTURE_BYTES: usize = 64 * 1024 * 1024;
+
+// =====================
+// Background Decoding
+// =====================
+
+/// Sent to the UI thread when decoded images are waiting to be uploaded as textures.
+#[derive(Clone, Debug)]
+pub enum ImageDecodeAction {
+    Ready,
+}
+
+#[derive(Clone, Debug)]
+pub enum ImageLoadState {
+    Loading,
+    Loaded,
+    Failed(ImageLoadError),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ImageSize {
+    Thumbnail,
+    Full,
+}
+
+impl ImageSize {
+    fn max_dim(self) -> usize {
+        match self {
+            ImageSize::Thumbnail => THUMBNAIL_MAX_DIM,
+            ImageSize::Full => FULL_MAX_DIM,
+        }
+    }
+}
+
+/// BGRA pixels as produced by the decoder, independent of any GPU resource.
+pub struct DecodedImage {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u32>,
+}
+
+struct DecodeJob {
+    key: String,
+    size: ImageSize,
+    generation: u64,
+    bytes: Arc<Vec<u8>>,
+}
+
+struct DecodeResult {
+    key: String,
+    size: ImageSize,
+    generation: u64,
+    image: Result<DecodedImage, ImageLoadError>,
+}
+
+/// Single long-lived worker; decoding is CPU bound so more threads mostly add contention with rendering.
+static DECODE_QUEUE: LazyLock<Mutex<Sender<DecodeJob>>> = LazyLock::new(|| {
+    let (sender, receiver) = mpsc::channel::<DecodeJob>();
+    std::thread::spawn(move || {
+        for job in receiver {
+            let (key, size, generation, bytes) = (job.key.clone(), job.size, job.generation, job.bytes.clone());
+            // A decoder panic fails this image only; the worker keeps serving the rest
+            let result = panic::catch_unwind(AssertUnwindSafe(|| decode_job(job)))
+                .unwrap_or_else(|_| panicked_job(key, size, generation, &bytes));
+            if let Ok(mut done) = DECODED.lock() {
+                done.push(result);
+            }
+            Cx::post_action(ImageDecodeAction::Ready);
+        }
+    });
+    Mutex::new(sender)
+});
+
+static DECODED: LazyLock<Mutex<Vec<DecodeResult>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+fn decode_job(job: DecodeJob) -> DecodeResult {
+    let image = image_format::decode_image(&job.bytes)
+        .map(|decoded| resize_to_fit(&decoded, job.size.max_dim()));
+    if let Err(ref err) = image {
+        log!("Could not decode image for {}: {}", job.key, err);
+    }
+    DecodeResult { key: job.key, size: job.size, generation: job.generation, image }
+}
+
+fn panicked_job(key: String, size: ImageSize, generation: u64, bytes: &[u8]) -> DecodeResult {
+    log!("Decoder panicked on image for {}", key);
+    let error = match image_format::sniff_format(bytes) {
+        Some(format) => ImageLoadError::Decode { format, message: "decoder panicked".to_string() },
+        None => ImageLoadError::UnknownFormat,
+    };
+    DecodeResult { key, size, generation, image: Err(error) }
+}
+
+/// Box-filter downscale so the longest edge is at most `max_dim`; smaller images are copied as is.
+fn resize_to_fit(image: &DecodedImage, max_dim: usize) -> DecodedImage {
+    let longest = image.width.max(image.height);
+    if longest <= max_dim || longest == 0 {
+        return DecodedImage { width: image.width, height: image.height, pixels: image.pixels.clone() };
+    }
+    let width = (image.width * max_dim / longest).max(1);
+    let height = (image.height * max_dim / longest).max(1);
+    let mut pixels = Vec::with_capacity(width * height);
+    for y in 0..height {
+        let (y0, y1) = source_span(y, height, image.height);
+        for x in 0..width {
+            let (x0, x1) = source_span(x, width, image.width);
+            let mut sum = [0u64; 4];
+            for sy in y0..y1 {
+                for sx in x0..x1 {
+                    let pixel = image.pixels[sy * image.width + sx];
+                    for (channel, total) in sum.iter_mut().enumerate() {
+                        *total += ((pixel >> (channel * 8)) & 0xff) as u64;
+                    }
+                }
+            }
+            let count = ((y1 - y0) * (x1 - x0)) as u64;
+            let averaged = sum
+                .iter()
+                .enumerate()
+                .fold(0u32, |acc, (channel, total)| acc | (((total / count) as u32) << (channel * 8)));
+            pixels.push(averaged);
+        }
+    }
+    DecodedImage { width, height, pixels }
+}
+
+/// Range of source pixels covered by destination pixel `i` (always at least one pixel).
+fn source_span(i: usize, dst_len: usize, src_len: usize) -> (usize, usize) {
+    let start = i * src_len / dst_len;
+    let end = ((i + 1) * src_len / dst_len).max(start + 1);
+    (start, end)
+}
+
+// =====================
+// Texture Cache (UI thread)
+// =====================
+
+struct CachedTexture {
+    generation: u64,
+    texture: Texture,
+    /// GPU bytes held by the texture
+    bytes: usize,
+    /// Value of the cache clock when the texture was last looked up (for LRU).
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct TextureCache {
+    textures: HashMap<(String, ImageSize), CachedTexture>,
+    /// Latest encoded bytes per key, kept to decode other sizes on demand.
+    sources: HashMap<String, Arc<Vec<u8>>>,
+    clock: u64,
+    /// Keys whose latest download or decode failed.
+    failed: HashMap<String, ImageLoadError>,
+    /// Sizes queued for decoding, with the generation they were queued at.
+    pending: HashMap<(String, ImageSize), u64>,
+    generations: HashMap<String, u64>,
+}
+
+impl TextureCache {
+    /// Marks `size` of `key` as pending and returns the job to queue, unless it is
+    /// already up to date, already queued, or can't be decoded.
+    fn decode_job(&mut self, key: &str, size: ImageSize) -> Option<DecodeJob> {
+        let generation = *self.generations.get(key)?;
+        let slot = (key.to_string(), size);
+        let current = self.textures.get(&slot).is_some_and(|cached| cached.generation == generation);
+        if current || self.pending.get(&slot) == Some(&generation) || self.failed.contains_key(key) {
+            return None;
+        }
+        let bytes = self.sources.get(key)?.clone();
+        self.pending.insert(slot, generation);
+        Some(DecodeJob { key: key.to_string(), size, generation, bytes })
+    }
+
+    fn total_bytes(&self) -> usize {
+        self.textures.values().map(|cached| cached.bytes).sum()
+    }
+
+    /// Drops least recently used textures until the total fits `MAX_TEXTURE_BYTES`.
+    fn evict(&mut self) {
+        let mut total = self.total_bytes();
+        if total <= MAX_TEXTURE_BYTES { return; }
+        let mut by_age: Vec<(u64, (String, ImageSize))> = self.textures
+            .iter()
+            .map(|(slot, cached)| (cached.last_used, slot.clone()))
+            .collect();
+        by_age.sort_by_key(|(last_used, _)| *last_used);
+        for (_, slot) in by_age {
+            if total <= MAX_TEXTURE_BYTES { break; }
+            if let Some(cached) = self.textures.remove(&slot) {
+                total -= cached.bytes;
+            }
+        }
+    }
+}
+
+thread_local! {
+    static TEXTURES: RefCell<TextureCache> = RefCell::new(TextureCache::default());
+}
+
+fn queue(job: DecodeJob) {
+    if let Ok(queue) = DECODE_QUEUE.lock() {
+        let _ = queue.send(job);
+    }
+}
+
+/// Keeps `bytes` under `key` and queues the thumbnail for decoding; the full size
+/// is decoded once something asks for it. Newer bytes for the same key supersede older ones.
+pub fn decode_in_background(key: &str, bytes: Arc<Vec<u8>>) {
+    let job = TEXTURES.with_borrow_mut(|cache| {
+        cache.generations
+            .entry(key.to_string())
+            .and_modify(|generation| *generation += 1)
+            .or_insert(0);
+        cache.sources.insert(key.to_string(), bytes);
+        cache.failed.remove(key);
+        cache.decode_job(key, ImageSize::Thumbnail)
+    });
+    if let Some(job) = job {
+        queue(job);
+    }
+}
+
+/// True once encoded bytes for `key` are held in memory.
+pub fn has_source(key: &str) -> bool {
+    TEXTURES.with_borrow(|cache| cache.sources.contains_key(key))
+}
+
+/// Uploads finished decodes as textures. Call on `ImageDecodeAction::Ready`;
+/// returns true when a texture or a decode failure became available.
+pub fn upload_decoded(cx: &mut Cx) -> bool {
+    let results: Vec<DecodeResult> = match DECODED.lock() {
+        Ok(mut done) => done.drain(..).collect(),
+        Err(_) => return false,
+    };
+    TEXTURES.with_borrow_mut(|cache| {
+        let mut uploaded = false;
+        for result in results {
+            // Skip decodes that were superseded while in flight
+            let slot = (result.key, result.size);
+            if cache.pending.get(&slot) != Some(&result.generation) { continue; }
+            cache.pending.remove(&slot);
+            match result.image {
+                Ok(image) => {
+                    cache.clock += 1;
+                    cache.textures.insert(slot, CachedTexture {
+                        generation: result.generation,
+                        bytes: image.pixels.len() * 4,
+                        texture: new_texture(cx, image),
+                        last_used: cache.clock,
+                    });
+                }
+                Err(err) => {
+                    cache.failed.insert(slot.0, err);
+                }
+            }
+            uploaded = true;
+        }
+        cache.evict();
+        uploaded
+    })
+}
+
+/// Decoded texture for `key` with its generation, if available. A missing or
+/// outdated size is queued for decoding from the kept bytes, so the full size is
+/// only decoded once the detail or photo screen draws it, and evicted textures
+/// come back when they are drawn again.
+pub fn texture(key: &str, size: ImageSize) -> Option<(Texture, u64)> {
+    let (texture, job) = TEXTURES.with_borrow_mut(|cache| {
+        let job = cache.decode_job(key, size);
+        cache.clock += 1;
+        let clock = cache.clock;
+        let texture = cache.textures.get_mut(&(key.to_string(), size)).map(|cached| {
+            cached.last_used = clock;
+            (cached.texture.clone(), cached.generation)
+        });
+        (texture, job)
+    });
+    if let Some(job) = job {
+        queue(job);
+    }
+    texture
+}
+
+/// Why the latest download or decode for `key` failed, if it did.
+pub fn load_error(key: &str) -> Option<ImageLoadError> {
+    TEXTURES.with_borrow(|cache| cache.failed.get(key).cloned())
+}
+
+/// Loaded once a texture exists (a stale one is still shown while refreshing),
+/// failed when the latest attempt failed, loading otherwise.
+pub fn load_state(key: &str) -> ImageLoadState {
+    TEXTURES.with_borrow(|cache| {
+        if cache.textures.keys().any(|(cached, _)| cached == key) {
+            ImageLoadState::Loaded
+        } else if let Some(err) = cache.failed.get(key) {
+            ImageLoadState::Failed(err.clone())
+        } else {
+            ImageLoadState::Loading
+        }
+    })
+}
+
+/// Records a download failure so widgets can stop spinning and offer a retry.
+pub fn mark_failed(key: &str, err: ImageLoadError) {
+    TEXTURES.with_borrow_mut(|cache| {
+        cache.failed.insert(key.to_string(), err);
+    });
+}
+
+/// Returns `key` to the loading state ahead of a retry.
+pub fn clear_failure(key: &str) {
+    TEXTURES.with_borrow_mut(|cache| {
+        cache.failed.remove(key);
+    });
+}
+
+/// True while `key` is queued or being decoded.
+pub fn is_decoding(key: &str) -> bool {
+    TEXTURES.with_borrow(|cache| cache.pending.keys().any(|(pending, _)| pending == key))
+}
+
+fn new_texture(cx: &mut Cx, image: DecodedImage) -> Texture {
+    Texture::new_with_format(cx, TextureFormat::VecBGRAu8_32 {
+        width: image.width,
+        height: image.height,
+        data: Some(image.pixels),
+        updated: TextureUpdated::Full,
+    })
+}
@@ -1,2 +1,21 @@
 pub use makepad_widgets;
 pub mod app;
+pub mod cluster;
+pub mod deeplink;
+pub mod geo;
+pub mod geocode;
+pub mod hours;
+pub mod image_cache;
+pub mod image_format;
+pub mod image_pipeline;
+pub mod location;
+pub mod navigation;
+pub mod phone;
+pub mod platform;
+pub mod provider;
+pub mod reviews;
+pub mod routing;
+pub mod search;
+pub mod storage;
+pub mod url;
+pub mod yelp;
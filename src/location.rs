@@ -0,0 +1,336 @@
+use makepad_widgets::*;
+use crate::app::Business;
+use crate::{geo, hours};
+
+/// Environment variable pointing at a GPX track to replay as the device location.
+pub const GPX_FILE_ENV: &str = "MAKEPAD_YELP_GPX";
+
+/// Accuracy reported for GPX points without an `<hdop>` element.
+const DEFAULT_ACCURACY_METERS: f64 = 10.0;
+/// Rough meters of error per unit of horizontal dilution of precision.
+const METERS_PER_HDOP: f64 = 5.0;
+
+// =====================
+// Location Provider Trait
+// =====================
+
+/// A position estimate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LocationFix {
+    pub lat: f64,
+    pub lng: f64,
+    /// Radius in meters the true position is likely within
+    pub accuracy_meters: f64,
+}
+
+impl LocationFix {
+    pub fn distance_to(&self, lat: f64, lng: f64) -> f64 {
+        geo::haversine_meters(self.lat, self.lng, lat, lng)
+    }
+}
+
+/// Source of the device position.
+///
+/// The app polls `updates` from a timer; each call returns the fixes that
+/// arrived since the previous one, oldest first.
+pub trait LocationProvider {
+    /// Latest known fix, if any.
+    fn current(&self) -> Option<LocationFix>;
+
+    /// Fixes that arrived since the last call; `elapsed_secs` is time since the app started.
+    fn updates(&mut self, elapsed_secs: f64) -> Vec<LocationFix>;
+}
+
+/// Replays a GPX track when `MAKEPAD_YELP_GPX` is set, otherwise stays at `lat`/`lng`.
+pub fn from_env(lat: f64, lng: f64) -> Box<dyn LocationProvider> {
+    if let Ok(path) = std::env::var(GPX_FILE_ENV) {
+        match GpxReplay::load(&path) {
+            Ok(replay) => return Box::new(replay),
+            Err(err) => log!("Falling back to a fixed location: {}", err),
+        }
+    }
+    Box::new(FixedLocation::new(LocationFix { lat, lng, accuracy_meters: DEFAULT_ACCURACY_METERS }))
+}
+
+/// Sets each business's `distance_meters` from `fix`.
+pub fn apply_distances(businesses: &mut [Business], fix: &LocationFix) {
+    for business in businesses {
+        business.distance_meters = Some(fix.distance_to(business.lat, business.lng));
+    }
+}
+
+// =====================
+// Fixed Location
+// =====================
+
+/// Always at the same place; reports it once.
+pub struct FixedLocation {
+    fix: LocationFix,
+    reported: bool,
+}
+
+impl FixedLocation {
+    pub fn new(fix: LocationFix) -> Self {
+        Self { fix, reported: false }
+    }
+}
+
+impl LocationProvider for FixedLocation {
+    fn current(&self) -> Option<LocationFix> {
+        Some(self.fix)
+    }
+
+    fn updates(&mut self, _elapsed_secs: f64) -> Vec<LocationFix> {
+        if std::mem::replace(&mut self.reported, true) {
+            Vec::new()
+        } else {
+            vec![self.fix]
+        }
+    }
+}
+
+// =====================
+// GPX Replay
+// =====================
+
+/// One track point, `offset_secs` after the first.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrackPoint {
+    pub offset_secs: f64,
+    pub fix: LocationFix,
+}
+
+/// Plays back the track points of a GPX file at their recorded pace, so a walk
+/// can be simulated on desktop and replayed identically in tests.
+pub struct GpxReplay {
+    points: Vec<TrackPoint>,
+    /// App time the replay started at, set by the first `updates` call
+    started_at: Option<f64>,
+    next: usize,
+}
+
+impl GpxReplay {
+    pub fn new(points: Vec<TrackPoint>) -> Self {
+        Self { points, started_at: None, next: 0 }
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let xml = std::fs::read_to_string(path)
+            .map_err(|err| format!("Could not read {}: {}", path, err))?;
+        let points = parse_gpx(&xml).map_err(|err| format!("Could not parse {}: {}", path, err))?;
+        log!("Replaying {} track points from {}", points.len(), path);
+        Ok(Self::new(points))
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.points.len()
+    }
+}
+
+impl LocationProvider for GpxReplay {
+    fn current(&self) -> Option<LocationFix> {
+        self.next.checked_sub(1).map(|index| self.points[index].fix)
+    }
+
+    fn updates(&mut self, elapsed_secs: f64) -> Vec<LocationFix> {
+        let started_at = *self.started_at.get_or_insert(elapsed_secs);
+        let replay_secs = elapsed_secs - started_at;
+        let mut fixes = Vec::new();
+        while let Some(point) = self.points.get(self.next) {
+            if point.offset_secs > replay_secs { break; }
+            fixes.push(point.fix);
+            self.next += 1;
+        }
+        fixes
+    }
+}
+
+/// Track points (`<trkpt>`, or `<rtept>`/`<wpt>` when there is no track) from a GPX document.
+///
+/// Points with a `<time>` are spaced by it; points without one follow a second apart.
+pub fn parse_gpx(xml: &str) -> Result<Vec<TrackPoint>, String> {
+    let mut points = Vec::new();
+    for tag in ["trkpt", "rtept", "wpt"] {
+        points = parse_points(xml, tag)?;
+        if !points.is_empty() { break; }
+    }
+    if points.is_empty() {
+        return Err("no track points".to_string());
+    }
+    Ok(points)
+}
+
+fn parse_points(xml: &str, tag: &str) -> Result<Vec<TrackPoint>, String> {
+    let open = format!("<{}", tag);
+    let mut points = Vec::new();
+    let mut first_time = None;
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        rest = &rest[start + open.len()..];
+        // `<trkpt ...>` followed by children up to `</trkpt>`, or a self-closing `<trkpt .../>`
+        let head_end = rest.find('>').ok_or("unterminated point")?;
+        let head = &rest[..head_end];
+        let body = if head.ends_with('/') {
+            ""
+        } else {
+            let close = format!("</{}>", tag);
+            let end = rest.find(&close).ok_or_else(|| format!("missing {}", close))?;
+            &rest[head_end + 1..end]
+        };
+        let lat = attribute(head, "lat").ok_or("point without lat")?;
+        let lng = attribute(head, "lon").ok_or("point without lon")?;
+        let accuracy_meters = element(body, "hdop")
+            .and_then(|hdop| hdop.parse::<f64>().ok())
+            .map_or(DEFAULT_ACCURACY_METERS, |hdop| hdop * METERS_PER_HDOP);
+        let time = element(body, "time").and_then(parse_iso8601);
+        let offset_secs = match (time, first_time) {
+            (Some(time), Some(first)) => (time - first) as f64,
+            (Some(time), None) => {
+                first_time = Some(time);
+                0.0
+            }
+            (None, _) => points.last().map_or(0.0, |last: &TrackPoint| last.offset_secs + 1.0),
+        };
+        points.push(TrackPoint { offset_secs, fix: LocationFix { lat, lng, accuracy_meters } });
+    }
+    Ok(points)
+}
+
+/// Numeric value of `name="..."` (or single-quoted) in a tag's attributes.
+fn attribute(head: &str, name: &str) -> Option<f64> {
+    let mut rest = head;
+    while let Some(index) = rest.find(name) {
+        let preceded_by_space = rest[..index].ends_with(char::is_whitespace);
+        rest = &rest[index + name.len()..];
+        let value = rest.trim_start().strip_prefix('=').map(str::trim_start);
+        if let (true, Some(value)) = (preceded_by_space, value) {
+            let quote = value.chars().next().filter(|quote| matches!(quote, '"' | '\''))?;
+            let value = &value[quote.len_utf8()..];
+            return value[..value.find(quote)?].trim().parse().ok();
+        }
+    }
+    None
+}
+
+/// Text of the first `<name>...</name>` child.
+fn element<'a>(body: &'a str, name: &str) -> Option<&'a str> {
+    let start = body.find(&format!("<{}>", name))? + name.len() + 2;
+    let end = start + body[start..].find(&format!("</{}>", name))?;
+    Some(body[start..end].trim())
+}
+
+/// Unix seconds of a UTC timestamp such as "2026-03-04T19:05:30Z" (fractions are dropped).
+fn parse_iso8601(text: &str) -> Option<i64> {
+    let text = text.trim().trim_end_matches('Z');
+    let (date, time) = text.split_once('T')?;
+    let mut date = date.splitn(3, '-').map(|part| part.parse::<i64>());
+    let (year, month, day) = (date.next()?.ok()?, date.next()?.ok()?, date.next()?.ok()?);
+    let mut time = time.splitn(3, ':');
+    let hour: i64 = time.next()?.parse().ok()?;
+    let minute: i64 = time.next()?.parse().ok()?;
+    let second: i64 = time.next().map_or(Some(0), |second| {
+        second.split('.').next().and_then(|whole| whole.parse().ok())
+    })?;
+    let days = hours::days_from_civil(year, month as u32, day as u32);
+    Some(days * 86_400 + hour * 3_600 + minute * 60 + second)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MISSION_WALK: &str = include_str!("../resources/fixtures/gpx/mission-walk.gpx");
+
+    fn replay() -> GpxReplay {
+        GpxReplay::new(parse_gpx(MISSION_WALK).expect("fixture parses"))
+    }
+
+    #[test]
+    fn parses_the_fixture_track() {
+        let points = parse_gpx(MISSION_WALK).unwrap();
+        assert_eq!(points.len(), 12);
+        assert_eq!(points[0].fix, LocationFix { lat: 37.76, lng: -122.4269, accuracy_meters: 10.0 });
+        assert_eq!(points[1].fix.accuracy_meters, 1.2 * METERS_PER_HDOP);
+        assert_eq!(points[11].fix.lat, 37.7621);
+        // Recorded every ten seconds
+        let offsets: Vec<f64> = points.iter().map(|point| point.offset_secs).collect();
+        assert_eq!(offsets, (0..12).map(|index| index as f64 * 10.0).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn replay_starts_at_the_first_poll() {
+        let mut replay = replay();
+        assert_eq!(replay.current(), None);
+        // The app has been running for a while when the replay starts
+        let first = replay.updates(42.0);
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].lat, 37.76);
+        assert_eq!(replay.current(), Some(first[0]));
+    }
+
+    #[test]
+    fn replay_follows_the_recorded_pace() {
+        let mut replay = replay();
+        replay.updates(100.0);
+        assert!(replay.updates(109.9).is_empty());
+        let second = replay.updates(110.0);
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].lat, 37.76015);
+        // A late poll delivers everything it missed, oldest first
+        let caught_up = replay.updates(135.0);
+        assert_eq!(caught_up.iter().map(|fix| fix.lat).collect::<Vec<_>>(), vec![37.7603, 37.76045]);
+        assert!(!replay.is_finished());
+        assert_eq!(replay.updates(1_000.0).len(), 8);
+        assert!(replay.is_finished());
+        assert_eq!(replay.current().map(|fix| fix.lng), Some(-122.4212));
+        assert!(replay.updates(2_000.0).is_empty());
+    }
+
+    #[test]
+    fn points_without_time_follow_a_second_apart() {
+        let xml = r#"<gpx><rte>
+            <rtept lat="37.1" lon="-122.1"/>
+            <rtept lat='37.2' lon='-122.2'></rtept>
+            <rtept lat="37.3" lon="-122.3"><hdop>3</hdop></rtept>
+        </rte></gpx>"#;
+        let points = parse_gpx(xml).unwrap();
+        assert_eq!(points.iter().map(|point| point.offset_secs).collect::<Vec<_>>(), vec![0.0, 1.0, 2.0]);
+        assert_eq!(points[1].fix.lat, 37.2);
+        assert_eq!(points[2].fix.accuracy_meters, 15.0);
+    }
+
+    #[test]
+    fn waypoints_are_used_without_a_track() {
+        let xml = r#"<gpx><wpt lat="37.5" lon="-122.5"><time>2026-01-01T00:00:00Z</time></wpt></gpx>"#;
+        assert_eq!(parse_gpx(xml).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn rejects_unquoted_and_oddly_quoted_coordinates() {
+        assert!(parse_gpx(r#"<gpx><trkpt lat=37.5 lon=-122.5/></gpx>"#).is_err());
+        // A multi-byte first character must not panic
+        assert!(parse_gpx("<gpx><trkpt lat=\u{201c}37.5\u{201d} lon=\u{201c}-122.5\u{201d}/></gpx>").is_err());
+    }
+
+    #[test]
+    fn rejects_documents_without_points() {
+        assert_eq!(parse_gpx("<gpx></gpx>"), Err("no track points".to_string()));
+        assert!(parse_gpx(r#"<gpx><trkpt lat="1" lon="2"><time>"#).is_err());
+    }
+
+    #[test]
+    fn parses_utc_timestamps() {
+        assert_eq!(parse_iso8601("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(parse_iso8601("2026-09-19T18:00:10.750Z"), Some(1_789_840_810));
+        assert_eq!(parse_iso8601("not a time"), None);
+    }
+
+    #[test]
+    fn fixed_location_reports_once() {
+        let fix = LocationFix { lat: 37.77, lng: -122.42, accuracy_meters: 5.0 };
+        let mut location = FixedLocation::new(fix);
+        assert_eq!(location.updates(0.0), vec![fix]);
+        assert!(location.updates(10.0).is_empty());
+        assert_eq!(location.current(), Some(fix));
+    }
+}
@@ -0,0 +1,143 @@
+use crate::app::{Business, Tab};
+
+// =====================
+// Navigation Stack
+// =====================
+
+/// One screen in a tab's history.
+#[derive(Clone, Debug)]
+pub enum Page {
+    /// The tab's own screen: the search list or the map
+    Root,
+    /// Filter sheet over the search list
+    Filters,
+    Detail(Business),
+    ComposeReview(Business),
+    Directions(Business),
+    /// Full-screen photo of a business
+    Photo(Business),
+}
+
+impl Page {
+    /// Business the page is about, if any.
+    pub fn business(&self) -> Option<&Business> {
+        match self {
+            Page::Root | Page::Filters => None,
+            Page::Detail(business)
+            | Page::ComposeReview(business)
+            | Page::Directions(business)
+            | Page::Photo(business) => Some(business),
+        }
+    }
+
+    /// Same kind of page about the same business; the business data itself may differ.
+    pub fn same_as(&self, other: &Page) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+            && self.business().map(|business| &business.id) == other.business().map(|business| &business.id)
+    }
+
+    fn business_mut(&mut self) -> Option<&mut Business> {
+        match self {
+            Page::Root | Page::Filters => None,
+            Page::Detail(business)
+            | Page::ComposeReview(business)
+            | Page::Directions(business)
+            | Page::Photo(business) => Some(business),
+        }
+    }
+}
+
+/// A history per tab; switching tabs keeps each tab where it was left.
+///
+/// Every stack starts at `Page::Root`, which can't be popped.
+#[derive(Debug)]
+pub struct Navigator {
+    tab: Tab,
+    search: Vec<Page>,
+    map: Vec<Page>,
+}
+
+impl Default for Navigator {
+    fn default() -> Self {
+        Self { tab: Tab::Search, search: vec![Page::Root], map: vec![Page::Root] }
+    }
+}
+
+impl Navigator {
+    pub fn tab(&self) -> Tab {
+        self.tab
+    }
+
+    /// Page on screen.
+    pub fn top(&self) -> &Page {
+        self.stack().last().expect("navigation stacks always hold their root")
+    }
+
+    pub fn depth(&self) -> usize {
+        self.stack().len()
+    }
+
+    /// The tab bar only shows on a tab's root screen (and the sheet drawn over it).
+    pub fn shows_tab_bar(&self) -> bool {
+        matches!(self.top(), Page::Root | Page::Filters)
+    }
+
+    pub fn push(&mut self, page: Page) {
+        self.stack_mut().push(page);
+    }
+
+    /// Goes back one page; false when already at the root.
+    pub fn pop(&mut self) -> bool {
+        let stack = self.stack_mut();
+        if stack.len() > 1 {
+            stack.pop();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Swaps the page on screen for `page`; replacing the root pushes instead.
+    pub fn replace(&mut self, page: Page) {
+        if self.depth() > 1 {
+            self.stack_mut().pop();
+        }
+        self.push(page);
+    }
+
+    pub fn pop_to_root(&mut self) {
+        self.stack_mut().truncate(1);
+    }
+
+    /// Shows `tab` as it was left; false when it was already showing.
+    pub fn switch_tab(&mut self, tab: Tab) -> bool {
+        let changed = self.tab != tab;
+        self.tab = tab;
+        changed
+    }
+
+    /// Refreshes every page about `business` with newer data, in both tabs.
+    pub fn update_business(&mut self, business: &Business) {
+        for page in self.search.iter_mut().chain(self.map.iter_mut()) {
+            if let Some(existing) = page.business_mut() {
+                if existing.id == business.id {
+                    *existing = business.clone();
+                }
+            }
+        }
+    }
+
+    fn stack(&self) -> &Vec<Page> {
+        match self.tab {
+            Tab::Search => &self.search,
+            Tab::Map => &self.map,
+        }
+    }
+
+    fn stack_mut(&mut self) -> &mut Vec<Page> {
+        match self.tab {
+            Tab::Search => &mut self.search,
+            Tab::Map => &mut self.map,
+        }
+    }
+}
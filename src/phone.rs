@@ -0,0 +1,151 @@
+// =====================
+// Phone Numbers
+// =====================
+
+/// Country calling code assumed for numbers written without one.
+pub const DEFAULT_COUNTRY_CODE: &str = "1";
+
+/// E.164 allows at most 15 digits after the "+".
+const MAX_E164_DIGITS: usize = 15;
+/// Shortest international number worth dialing (country code plus subscriber number).
+const MIN_E164_DIGITS: usize = 8;
+
+/// Normalizes a number as people write it ("(415) 826-7000", "415.826.7000 ext 2",
+/// "+44 20 7946 0958", "0044 20 7946 0958") to E.164, e.g. "+14158267000".
+///
+/// Numbers without a country code get `default_country_code`. Extensions are
+/// dropped, since `tel:` can't carry them reliably. None when it isn't a dialable number.
+pub fn normalize_e164(raw: &str, default_country_code: &str) -> Option<String> {
+    let raw = strip_extension(raw.trim());
+    let mut digits = String::new();
+    for c in raw.chars() {
+        match c {
+            '0'..='9' => digits.push(c),
+            '+' if digits.is_empty() => {}
+            ' ' | '-' | '.' | '(' | ')' | '/' | '\u{a0}' => {}
+            _ => return None,
+        }
+    }
+    let international = if raw.starts_with('+') {
+        digits
+    } else if let Some(rest) = digits.strip_prefix("00") {
+        // International call prefix used outside North America
+        rest.to_string()
+    } else if default_country_code == "1" {
+        match digits.len() {
+            10 => format!("1{}", digits),
+            11 if digits.starts_with('1') => digits,
+            _ => return None,
+        }
+    } else {
+        // Drop the trunk prefix of a national number, e.g. "020 7946 0958" in the UK
+        format!("{}{}", default_country_code, digits.strip_prefix('0').unwrap_or(&digits))
+    };
+    if !(MIN_E164_DIGITS..=MAX_E164_DIGITS).contains(&international.len()) || international.starts_with('0') {
+        return None;
+    }
+    if let Some(national) = international.strip_prefix('1') {
+        if !is_valid_nanp(national) {
+            return None;
+        }
+    }
+    Some(format!("+{}", international))
+}
+
+/// How a normalized number is shown: "(415) 826-7000" within North America,
+/// the E.164 form elsewhere since grouping rules vary by country.
+pub fn format_display(e164: &str) -> String {
+    match e164.strip_prefix("+1") {
+        Some(national) if is_valid_nanp(national) => {
+            format!("({}) {}-{}", &national[..3], &national[3..6], &national[6..])
+        }
+        _ => e164.to_string(),
+    }
+}
+
+/// `tel:` URI for an E.164 number.
+pub fn tel_uri(e164: &str) -> String {
+    format!("tel:{}", e164)
+}
+
+/// Ten digits where neither the area code nor the exchange starts with 0 or 1.
+fn is_valid_nanp(national: &str) -> bool {
+    let bytes = national.as_bytes();
+    bytes.len() == 10
+        && bytes.iter().all(u8::is_ascii_digit)
+        && bytes[0] >= b'2'
+        && bytes[3] >= b'2'
+}
+
+/// Cuts "x12", "ext. 12" or "extension 12" off the end.
+fn strip_extension(raw: &str) -> &str {
+    let lower = raw.to_ascii_lowercase();
+    let cut = ["ext", "x", "#"]
+        .iter()
+        .filter_map(|marker| lower.find(marker))
+        .min();
+    match cut {
+        Some(index) => raw[..index].trim_end_matches(|c: char| c.is_whitespace() || c == ','),
+        None => raw,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn us(raw: &str) -> Option<String> {
+        normalize_e164(raw, DEFAULT_COUNTRY_CODE)
+    }
+
+    #[test]
+    fn normalizes_north_american_numbers() {
+        assert_eq!(us("(415) 826-7000").as_deref(), Some("+14158267000"));
+        assert_eq!(us("415.826.7000").as_deref(), Some("+14158267000"));
+        assert_eq!(us("1-415-826-7000").as_deref(), Some("+14158267000"));
+        assert_eq!(us("+1 415 826 7000").as_deref(), Some("+14158267000"));
+        assert_eq!(us("  415\u{a0}826\u{a0}7000 ").as_deref(), Some("+14158267000"));
+    }
+
+    #[test]
+    fn drops_extensions() {
+        assert_eq!(us("415.826.7000 ext 2").as_deref(), Some("+14158267000"));
+        assert_eq!(us("(415) 826-7000, ext. 12").as_deref(), Some("+14158267000"));
+        assert_eq!(us("415-826-7000 x12").as_deref(), Some("+14158267000"));
+        assert_eq!(us("415-826-7000 #3").as_deref(), Some("+14158267000"));
+    }
+
+    #[test]
+    fn keeps_international_country_codes() {
+        assert_eq!(us("+44 20 7946 0958").as_deref(), Some("+442079460958"));
+        assert_eq!(us("0044 20 7946 0958").as_deref(), Some("+442079460958"));
+        // National numbers take the default country code without their trunk prefix
+        assert_eq!(normalize_e164("020 7946 0958", "44").as_deref(), Some("+442079460958"));
+    }
+
+    #[test]
+    fn rejects_undialable_numbers() {
+        assert_eq!(us(""), None);
+        assert_eq!(us("826-7000"), None);
+        assert_eq!(us("call for hours"), None);
+        assert_eq!(us("415-826-700O"), None);
+        assert_eq!(us("2-415-826-7000"), None);
+        // Area codes and exchanges can't start with 0 or 1
+        assert_eq!(us("(015) 826-7000"), None);
+        assert_eq!(us("(415) 126-7000"), None);
+        assert_eq!(us("+1 234 567 890 123 456"), None);
+        assert_eq!(us("+44 20"), None);
+    }
+
+    #[test]
+    fn formats_north_american_numbers_for_display() {
+        assert_eq!(format_display("+14158267000"), "(415) 826-7000");
+        assert_eq!(format_display("+442079460958"), "+442079460958");
+        assert_eq!(format_display("+1415826"), "+1415826");
+    }
+
+    #[test]
+    fn tel_uri_wraps_the_number() {
+        assert_eq!(tel_uri("+14158267000"), "tel:+14158267000");
+    }
+}
@@ -0,0 +1,102 @@
+use makepad_widgets::*;
+use std::process::Command;
+use crate::phone;
+
+// =====================
+// Platform Actions
+// =====================
+
+/// Things only the operating system can do for the app: open a URL in
+/// whatever app handles it, place a phone call.
+pub trait PlatformActions {
+    fn open_url(&mut self, url: &str) -> Result<(), String>;
+
+    /// Starts a call to an E.164 number through the `tel:` handler.
+    fn dial(&mut self, e164: &str) -> Result<(), String> {
+        self.open_url(&phone::tel_uri(e164))
+    }
+}
+
+/// Logs and records what would have been opened instead of doing it.
+///
+/// Used on Linux desktop, where there is no dependable handler for `geo:` or
+/// `tel:`, and anywhere the dispatched URLs need checking afterwards.
+#[derive(Default)]
+pub struct RecordingActions {
+    pub opened: Vec<String>,
+}
+
+impl PlatformActions for RecordingActions {
+    fn open_url(&mut self, url: &str) -> Result<(), String> {
+        log!("Opening {}", url);
+        self.opened.push(url.to_string());
+        Ok(())
+    }
+}
+
+/// Opens URLs with the desktop's default handler.
+pub struct SystemActions;
+
+impl PlatformActions for SystemActions {
+    fn open_url(&mut self, url: &str) -> Result<(), String> {
+        let mut command = if cfg!(target_os = "windows") {
+            let mut command = Command::new("rundll32");
+            command.arg("url.dll,FileProtocolHandler");
+            command
+        } else {
+            Command::new("open")
+        };
+        let mut child = command
+            .arg(url)
+            .spawn()
+            .map_err(|err| format!("Could not open {}: {}", url, err))?;
+        // The handler returns quickly but must still be waited on, or it lingers as a zombie
+        std::thread::spawn(move || {
+            let _ = child.wait();
+        });
+        Ok(())
+    }
+}
+
+/// Sent to the UI thread with a URL for Makepad to open.
+#[derive(Clone, Debug)]
+pub enum PlatformAction {
+    OpenUrl(String),
+}
+
+/// Opens URLs through Makepad, which starts an intent on Android and asks
+/// UIApplication on iOS; apps there can't spawn a handler themselves.
+pub struct MobileActions;
+
+impl PlatformActions for MobileActions {
+    fn open_url(&mut self, url: &str) -> Result<(), String> {
+        // Opening needs the Cx, which the App has when the action arrives
+        Cx::post_action(PlatformAction::OpenUrl(url.to_string()));
+        Ok(())
+    }
+}
+
+/// Platform actions for the platform the app is running on.
+pub fn actions() -> Box<dyn PlatformActions> {
+    if cfg!(any(target_os = "android", target_os = "ios")) {
+        Box::new(MobileActions)
+    } else if cfg!(any(target_os = "macos", target_os = "windows")) {
+        Box::new(SystemActions)
+    } else {
+        Box::new(RecordingActions::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recording_dials_through_tel() {
+        let mut actions = RecordingActions::default();
+        let number = phone::normalize_e164("(415) 826-7000", phone::DEFAULT_COUNTRY_CODE).unwrap();
+        actions.dial(&number).unwrap();
+        actions.open_url("geo:37.761400,-122.424100").unwrap();
+        assert_eq!(actions.opened, vec!["tel:+14158267000", "geo:37.761400,-122.424100"]);
+    }
+}
@@ -0,0 +1,424 @@
+use makepad_widgets::*;
+use makepad_widgets::makepad_micro_serde::*;
+use std::collections::HashMap;
+use crate::app::{Business, mock_businesses};
+use crate::reviews::{self, NewReview, Review, ReviewPage, ReviewSort, ReviewsRequest};
+use crate::geo::{self, Bounds};
+use crate::{search, url, yelp};
+
+/// Environment variable pointing at a JSON file of businesses.
+pub const DATA_FILE_ENV: &str = "MAKEPAD_YELP_DATA";
+/// Environment variable with the base URL of an HTTP business service.
+pub const API_URL_ENV: &str = "MAKEPAD_YELP_API";
+
+const DEFAULT_PAGE_SIZE: usize = 20;
+/// How far from a chosen center searches reach.
+pub const SEARCH_RADIUS_METERS: f64 = 3_000.0;
+
+// =====================
+// Provider Trait
+// =====================
+
+/// Parameters for a business search.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchRequest {
+    pub term: String,
+    pub offset: usize,
+    pub limit: usize,
+    /// Latitude and longitude to search around; None searches the default area
+    pub center: Option<(f64, f64)>,
+}
+
+impl SearchRequest {
+    pub fn new(term: &str) -> Self {
+        Self { term: term.to_string(), offset: 0, limit: DEFAULT_PAGE_SIZE, center: None }
+    }
+
+    pub fn with_center(self, center: Option<(f64, f64)>) -> Self {
+        Self { center, ..self }
+    }
+
+    pub fn next_page(&self) -> Self {
+        Self { offset: self.offset + self.limit, ..self.clone() }
+    }
+}
+
+/// Search limited to the part of the map on screen.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AreaRequest {
+    pub term: String,
+    pub bounds: Bounds,
+}
+
+/// Data produced by a provider, either returned directly from a call
+/// or delivered later through `handle_network_response`.
+#[derive(Clone, Debug)]
+pub enum ProviderResponse {
+    /// One page of results; `has_more` is false once the last page has been served
+    Search { request: SearchRequest, businesses: Vec<Business>, has_more: bool },
+    /// A search page could not be loaded; the request can be sent again to retry
+    SearchFailed { request: SearchRequest, message: String },
+    Business(Business),
+    Nearby(Vec<Business>),
+    /// Businesses inside `request.bounds`
+    Area { request: AreaRequest, businesses: Vec<Business> },
+    Reviews { request: ReviewsRequest, page: ReviewPage },
+    /// A reviews page could not be loaded; the request can be sent again to retry
+    ReviewsFailed { request: ReviewsRequest, message: String },
+    /// Outcome of `submit_review`; `local_id` names the optimistic copy to replace or drop
+    ReviewSubmitted { local_id: String, business_id: String, result: Result<Review, String> },
+    Error(String),
+}
+
+/// Source of business data shared by every screen.
+///
+/// Each call returns `Some` when the answer is available immediately and
+/// `None` when it will arrive as a network response later.
+pub trait BusinessProvider {
+    fn search(&mut self, cx: &mut Cx, request: &SearchRequest) -> Option<ProviderResponse>;
+
+    fn business(&mut self, cx: &mut Cx, id: &str) -> Option<ProviderResponse>;
+
+    fn nearby(&mut self, cx: &mut Cx, lat: f64, lng: f64, radius_meters: f64) -> Option<ProviderResponse>;
+
+    /// Businesses matching the term inside a bounding box ("Search this area").
+    fn search_area(&mut self, cx: &mut Cx, request: &AreaRequest) -> Option<ProviderResponse>;
+
+    /// One page of reviews for a business, in the requested order.
+    fn reviews(&mut self, cx: &mut Cx, request: &ReviewsRequest) -> Option<ProviderResponse>;
+
+    /// Orders `reviews` keeps consistent across pages; the detail screen only offers these.
+    fn review_sorts(&self) -> &'static [ReviewSort] {
+        &ReviewSort::ALL
+    }
+
+    fn submit_review(&mut self, cx: &mut Cx, review: &NewReview) -> Option<ProviderResponse>;
+
+    /// Turns a network response belonging to this provider into a `ProviderResponse`.
+    fn handle_network_response(&mut self, _item: &NetworkResponseItem) -> Option<ProviderResponse> {
+        None
+    }
+}
+
+/// Picks a provider from the environment: a JSON file, an HTTP service,
+/// the Yelp Fusion API, or the built-in mock data.
+pub fn from_env() -> Box<dyn BusinessProvider> {
+    if let Ok(path) = std::env::var(DATA_FILE_ENV) {
+        match LocalProvider::load(&path) {
+            Ok(provider) => return Box::new(provider),
+            Err(err) => log!("Falling back to mock data: {}", err),
+        }
+    }
+    if let Ok(base_url) = std::env::var(API_URL_ENV) {
+        return Box::new(HttpProvider::new(&base_url));
+    }
+    if let Some(client) = yelp::YelpClient::from_env() {
+        return Box::new(yelp::YelpProvider::new(client));
+    }
+    Box::new(LocalProvider::mock())
+}
+
+// =====================
+// Local Provider
+// =====================
+
+/// Sample reviews for every business; local data has no real review source.
+fn sample_reviews_for(businesses: &[Business]) -> HashMap<String, Vec<Review>> {
+    businesses
+        .iter()
+        .map(|business| {
+            let count = (business.review_count as usize).min(12);
+            (business.id.clone(), reviews::sample_reviews(&business.id, count))
+        })
+        .collect()
+}
+
+/// Serves businesses held in memory: the built-in sample San Francisco
+/// restaurants, or a list loaded once from a JSON file (see `resources/businesses.json`).
+pub struct LocalProvider {
+    businesses: Vec<Business>,
+    reviews: HashMap<String, Vec<Review>>,
+}
+
+impl LocalProvider {
+    pub fn new(businesses: Vec<Business>) -> Self {
+        Self { reviews: sample_reviews_for(&businesses), businesses }
+    }
+
+    pub fn mock() -> Self {
+        Self::new(mock_businesses())
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|err| format!("Could not read {}: {}", path, err))?;
+        let list = BusinessList::deserialize_json(&json)
+            .map_err(|err| format!("Could not parse {}: {:?}", path, err))?;
+        log!("Loaded {} businesses from {}", list.businesses.len(), path);
+        Ok(Self::new(list.businesses))
+    }
+}
+
+impl BusinessProvider for LocalProvider {
+    fn search(&mut self, _cx: &mut Cx, request: &SearchRequest) -> Option<ProviderResponse> {
+        let mut matches = search::filter_businesses(&self.businesses, &request.term);
+        if let Some((lat, lng)) = request.center {
+            matches.retain(|business| geo::haversine_meters(lat, lng, business.lat, business.lng) <= SEARCH_RADIUS_METERS);
+        }
+        let has_more = request.offset + request.limit < matches.len();
+        let businesses = matches
+            .into_iter()
+            .skip(request.offset)
+            .take(request.limit)
+            .collect();
+        Some(ProviderResponse::Search { request: request.clone(), businesses, has_more })
+    }
+
+    fn business(&mut self, _cx: &mut Cx, id: &str) -> Option<ProviderResponse> {
+        Some(match self.businesses.iter().find(|business| business.id == id) {
+            Some(business) => ProviderResponse::Business(business.clone()),
+            None => ProviderResponse::Error(format!("Unknown business id {}", id)),
+        })
+    }
+
+    fn nearby(&mut self, _cx: &mut Cx, lat: f64, lng: f64, radius_meters: f64) -> Option<ProviderResponse> {
+        Some(ProviderResponse::Nearby(
+            self.businesses
+                .iter()
+                .filter(|business| geo::haversine_meters(lat, lng, business.lat, business.lng) <= radius_meters)
+                .cloned()
+                .collect(),
+        ))
+    }
+
+    fn search_area(&mut self, _cx: &mut Cx, request: &AreaRequest) -> Option<ProviderResponse> {
+        let businesses = search::filter_businesses(&self.businesses, &request.term)
+            .into_iter()
+            .filter(|business| request.bounds.contains(business.lat, business.lng))
+            .collect();
+        Some(ProviderResponse::Area { request: request.clone(), businesses })
+    }
+
+    fn reviews(&mut self, _cx: &mut Cx, request: &ReviewsRequest) -> Option<ProviderResponse> {
+        let page = self.reviews
+            .get(&request.business_id)
+            .map(|reviews| reviews::page_local(reviews, request))
+            .unwrap_or_default();
+        Some(ProviderResponse::Reviews { request: request.clone(), page })
+    }
+
+    /// Accepts the review into memory; it lasts until the app quits.
+    fn submit_review(&mut self, _cx: &mut Cx, review: &NewReview) -> Option<ProviderResponse> {
+        let existing = self.reviews.entry(review.business_id.clone()).or_default();
+        let accepted = Review {
+            id: format!("{}-user-{}", review.business_id, existing.len() + 1),
+            ..review.optimistic()
+        };
+        existing.push(accepted.clone());
+        Some(ProviderResponse::ReviewSubmitted {
+            local_id: review.local_id.clone(),
+            business_id: review.business_id.clone(),
+            result: Ok(accepted),
+        })
+    }
+}
+
+/// On-disk format shared by `LocalProvider::load` and `HttpProvider` search responses.
+#[derive(Clone, Debug, SerJson, DeJson)]
+pub struct BusinessList {
+    pub businesses: Vec<Business>,
+    /// Matches across all pages, when the service reports it
+    pub total: Option<usize>,
+}
+
+// =====================
+// HTTP Provider
+// =====================
+
+enum PendingRequest {
+    Search(SearchRequest),
+    Business,
+    Nearby,
+    Area(AreaRequest),
+    Reviews(ReviewsRequest),
+    SubmitReview(NewReview),
+}
+
+/// Fetches businesses from a service speaking the app's own JSON format:
+/// `GET /businesses/search` returns a `BusinessList`, `GET /businesses/{id}` a `Business`,
+/// `GET /businesses/{id}/reviews` a `ReviewPage`, and `POST /businesses/{id}/reviews`
+/// takes a `NewReview` and returns the stored `Review`.
+pub struct HttpProvider {
+    base_url: String,
+    pending: HashMap<LiveId, PendingRequest>,
+}
+
+impl HttpProvider {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            pending: HashMap::new(),
+        }
+    }
+
+    fn send(&mut self, cx: &mut Cx, path: &str, pending: PendingRequest) {
+        let request = HttpRequest::new(format!("{}{}", self.base_url, path), HttpMethod::GET);
+        self.send_request(cx, request, pending);
+    }
+
+    fn send_request(&mut self, cx: &mut Cx, request: HttpRequest, pending: PendingRequest) {
+        let request_id = LiveId::unique();
+        cx.http_request(request_id, request);
+        self.pending.insert(request_id, pending);
+    }
+}
+
+impl BusinessProvider for HttpProvider {
+    fn search(&mut self, cx: &mut Cx, request: &SearchRequest) -> Option<ProviderResponse> {
+        let mut params = vec![
+            ("term", request.term.clone()),
+            ("offset", request.offset.to_string()),
+            ("limit", request.limit.to_string()),
+        ];
+        if let Some((lat, lng)) = request.center {
+            params.push(("latitude", lat.to_string()));
+            params.push(("longitude", lng.to_string()));
+            params.push(("radius", (SEARCH_RADIUS_METERS as u64).to_string()));
+        }
+        let query = url::query_string(&params);
+        self.send(cx, &format!("/businesses/search?{}", query), PendingRequest::Search(request.clone()));
+        None
+    }
+
+    fn business(&mut self, cx: &mut Cx, id: &str) -> Option<ProviderResponse> {
+        self.send(cx, &format!("/businesses/{}", url::percent_encode(id)), PendingRequest::Business);
+        None
+    }
+
+    fn nearby(&mut self, cx: &mut Cx, lat: f64, lng: f64, radius_meters: f64) -> Option<ProviderResponse> {
+        let query = url::query_string(&[
+            ("latitude", lat.to_string()),
+            ("longitude", lng.to_string()),
+            ("radius", (radius_meters.round() as u64).to_string()),
+        ]);
+        self.send(cx, &format!("/businesses/search?{}", query), PendingRequest::Nearby);
+        None
+    }
+
+    /// The service searches by radius, so the circle around the box is fetched and trimmed to it.
+    fn search_area(&mut self, cx: &mut Cx, request: &AreaRequest) -> Option<ProviderResponse> {
+        let (lat, lng) = request.bounds.center();
+        let query = url::query_string(&[
+            ("term", request.term.clone()),
+            ("latitude", lat.to_string()),
+            ("longitude", lng.to_string()),
+            ("radius", (request.bounds.radius_meters().round() as u64).to_string()),
+        ]);
+        self.send(cx, &format!("/businesses/search?{}", query), PendingRequest::Area(request.clone()));
+        None
+    }
+
+    fn reviews(&mut self, cx: &mut Cx, request: &ReviewsRequest) -> Option<ProviderResponse> {
+        let sort = match request.sort {
+            ReviewSort::Newest => "newest",
+            ReviewSort::Highest => "highest",
+            ReviewSort::Lowest => "lowest",
+        };
+        let query = url::query_string(&[
+            ("offset", request.offset.to_string()),
+            ("limit", request.limit.to_string()),
+            ("sort", sort.to_string()),
+        ]);
+        let path = format!("/businesses/{}/reviews?{}", url::percent_encode(&request.business_id), query);
+        self.send(cx, &path, PendingRequest::Reviews(request.clone()));
+        None
+    }
+
+    fn submit_review(&mut self, cx: &mut Cx, review: &NewReview) -> Option<ProviderResponse> {
+        let url = format!("{}/businesses/{}/reviews", self.base_url, url::percent_encode(&review.business_id));
+        let mut request = HttpRequest::new(url, HttpMethod::POST);
+        request.set_header("Content-Type".into(), "application/json".into());
+        request.set_body(review.serialize_json().into_bytes());
+        self.send_request(cx, request, PendingRequest::SubmitReview(review.clone()));
+        None
+    }
+
+    fn handle_network_response(&mut self, item: &NetworkResponseItem) -> Option<ProviderResponse> {
+        // Progress and other interim events leave the request pending until its final response
+        if !matches!(item.response, NetworkResponse::HttpResponse(_) | NetworkResponse::HttpRequestError(_)) {
+            return None;
+        }
+        let pending = self.pending.remove(&item.request_id)?;
+        let body = ok_body(&item.response);
+        Some(match pending {
+            PendingRequest::Search(request) => searched(request, body),
+            PendingRequest::SubmitReview(review) => submitted_review(review, body),
+            PendingRequest::Business => match body.and_then(|body| {
+                Business::deserialize_json(&body).map_err(|err| format!("Bad business response: {:?}", err))
+            }) {
+                Ok(business) => ProviderResponse::Business(business),
+                Err(message) => ProviderResponse::Error(message),
+            },
+            PendingRequest::Nearby => match body.and_then(|body| {
+                BusinessList::deserialize_json(&body).map_err(|err| format!("Bad nearby response: {:?}", err))
+            }) {
+                Ok(list) => ProviderResponse::Nearby(list.businesses),
+                Err(message) => ProviderResponse::Error(message),
+            },
+            PendingRequest::Area(request) => match body.and_then(|body| {
+                BusinessList::deserialize_json(&body).map_err(|err| format!("Bad area response: {:?}", err))
+            }) {
+                Ok(list) => area_response(request, list.businesses),
+                Err(message) => ProviderResponse::Error(message),
+            },
+            PendingRequest::Reviews(request) => match body.and_then(|body| {
+                ReviewPage::deserialize_json(&body).map_err(|err| format!("Bad reviews response: {:?}", err))
+            }) {
+                Ok(page) => ProviderResponse::Reviews { request, page },
+                Err(message) => ProviderResponse::ReviewsFailed { request, message },
+            },
+        })
+    }
+}
+
+/// Body of a successful final response (201 for a stored review), or why there is none.
+fn ok_body(response: &NetworkResponse) -> Result<String, String> {
+    match response {
+        NetworkResponse::HttpResponse(response) if matches!(response.status_code, 200 | 201) => {
+            Ok(response.get_string_body().unwrap_or_default())
+        }
+        NetworkResponse::HttpResponse(response) => Err(format!("HTTP {}", response.status_code)),
+        NetworkResponse::HttpRequestError(err) => Err(format!("Request failed: {:?}", err)),
+        _ => Err("Unexpected response".to_string()),
+    }
+}
+
+/// Trims businesses fetched for the circle around an area down to the area itself.
+pub fn area_response(request: AreaRequest, mut businesses: Vec<Business>) -> ProviderResponse {
+    businesses.retain(|business| request.bounds.contains(business.lat, business.lng));
+    ProviderResponse::Area { request, businesses }
+}
+
+fn searched(request: SearchRequest, body: Result<String, String>) -> ProviderResponse {
+    let list = body.and_then(|body| {
+        BusinessList::deserialize_json(&body).map_err(|err| format!("Bad search response: {:?}", err))
+    });
+    match list {
+        Ok(list) => {
+            // Without a total, a short page is taken to be the last one
+            let has_more = match list.total {
+                Some(total) => request.offset + list.businesses.len() < total,
+                None => list.businesses.len() >= request.limit,
+            };
+            ProviderResponse::Search { request, businesses: list.businesses, has_more }
+        }
+        Err(message) => ProviderResponse::SearchFailed { request, message },
+    }
+}
+
+fn submitted_review(review: NewReview, body: Result<String, String>) -> ProviderResponse {
+    let result = body.and_then(|body| {
+        Review::deserialize_json(&body).map_err(|err| format!("Bad review response: {:?}", err))
+    });
+    ProviderResponse::ReviewSubmitted { local_id: review.local_id, business_id: review.business_id, result }
+}
@@ -0,0 +1,417 @@
+use makepad_widgets::*;
+use makepad_widgets::makepad_micro_serde::*;
+use std::path::{Path, PathBuf};
+use crate::{hours, storage};
+
+/// Reviews fetched per page in the detail screen.
+pub const REVIEWS_PAGE_SIZE: usize = 5;
+/// Collapsed reviews show roughly this many characters before "Read more".
+pub const PREVIEW_CHARS: usize = 180;
+/// Shortest review text accepted for submission.
+pub const MIN_REVIEW_CHARS: usize = 50;
+pub const MAX_REVIEW_CHARS: usize = 5000;
+/// Highest rating the star picker can produce.
+pub const MAX_RATING: f32 = 5.0;
+
+const MONTH_NAMES: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+// =====================
+// Review Model
+// =====================
+
+#[derive(Clone, Debug, PartialEq, SerJson, DeJson)]
+pub struct Review {
+    pub id: String,
+    pub author: String,
+    pub rating: f32,
+    pub text: String,
+    /// Unix seconds when the review was written
+    pub created: i64,
+    pub photos: Vec<String>,
+}
+
+impl Review {
+    /// "Mar 4, 2026"
+    pub fn date_text(&self) -> String {
+        let (year, month, day) = hours::civil_from_days(self.created.div_euclid(SECONDS_PER_DAY));
+        format!("{} {}, {}", MONTH_NAMES[month as usize - 1], day, year)
+    }
+
+    /// Text cut at a word boundary near `PREVIEW_CHARS`, or None when it is short enough to show in full.
+    pub fn preview(&self) -> Option<String> {
+        if self.text.chars().count() <= PREVIEW_CHARS {
+            return None;
+        }
+        let cut = self.text.char_indices().nth(PREVIEW_CHARS).map_or(self.text.len(), |(index, _)| index);
+        let head = &self.text[..cut];
+        let head = head.rfind(char::is_whitespace).map_or(head, |space| &head[..space]);
+        Some(format!("{}…", head.trim_end_matches(|c: char| c.is_whitespace() || c.is_ascii_punctuation())))
+    }
+
+    /// First letter of the author's name, for the avatar.
+    pub fn initial(&self) -> String {
+        self.author.chars().next().map(|c| c.to_uppercase().to_string()).unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReviewSort {
+    #[default]
+    Newest,
+    Highest,
+    Lowest,
+}
+
+impl ReviewSort {
+    pub const ALL: [ReviewSort; 3] = [ReviewSort::Newest, ReviewSort::Highest, ReviewSort::Lowest];
+}
+
+/// Stable sort; ties fall back to newest first, then id, so paging never reshuffles.
+pub fn sort_reviews(reviews: &mut [Review], sort: ReviewSort) {
+    reviews.sort_by(|a, b| {
+        let by_rating = match sort {
+            ReviewSort::Newest => std::cmp::Ordering::Equal,
+            ReviewSort::Highest => b.rating.total_cmp(&a.rating),
+            ReviewSort::Lowest => a.rating.total_cmp(&b.rating),
+        };
+        by_rating
+            .then_with(|| b.created.cmp(&a.created))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+}
+
+// =====================
+// Paging
+// =====================
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReviewsRequest {
+    pub business_id: String,
+    pub sort: ReviewSort,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl ReviewsRequest {
+    pub fn new(business_id: &str, sort: ReviewSort) -> Self {
+        Self { business_id: business_id.to_string(), sort, offset: 0, limit: REVIEWS_PAGE_SIZE }
+    }
+
+    pub fn next_page(&self) -> Self {
+        Self { offset: self.offset + self.limit, ..self.clone() }
+    }
+}
+
+/// One page of reviews plus the number available in total.
+#[derive(Clone, Debug, Default, SerJson, DeJson)]
+pub struct ReviewPage {
+    pub reviews: Vec<Review>,
+    pub total: usize,
+}
+
+/// Serves a page from reviews held in memory.
+pub fn page_local(reviews: &[Review], request: &ReviewsRequest) -> ReviewPage {
+    let mut sorted = reviews.to_vec();
+    sort_reviews(&mut sorted, request.sort);
+    ReviewPage {
+        total: sorted.len(),
+        reviews: sorted.into_iter().skip(request.offset).take(request.limit).collect(),
+    }
+}
+
+// =====================
+// Writing Reviews
+// =====================
+
+/// A review as composed by the user, before the provider has accepted it.
+#[derive(Clone, Debug, PartialEq, SerJson, DeJson)]
+pub struct NewReview {
+    /// Id of the optimistic copy shown while the submission is in flight
+    pub local_id: String,
+    pub business_id: String,
+    pub author: String,
+    pub rating: f32,
+    pub text: String,
+}
+
+impl NewReview {
+    /// The placeholder review shown until the provider answers.
+    pub fn optimistic(&self) -> Review {
+        Review {
+            id: self.local_id.clone(),
+            author: self.author.clone(),
+            rating: self.rating,
+            text: self.text.clone(),
+            created: hours::now_unix(),
+            photos: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReviewValidationError {
+    MissingRating,
+    InvalidRating(f32),
+    TooShort { min: usize, len: usize },
+    TooLong { max: usize, len: usize },
+}
+
+impl std::fmt::Display for ReviewValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReviewValidationError::MissingRating => write!(f, "Tap the stars to pick a rating"),
+            ReviewValidationError::InvalidRating(rating) => write!(f, "Ratings go up to {} stars, not {}", MAX_RATING, rating),
+            ReviewValidationError::TooShort { min, len } => {
+                write!(f, "Reviews need at least {} characters ({} more to go)", min, min - len)
+            }
+            ReviewValidationError::TooLong { max, len } => {
+                write!(f, "Reviews can be at most {} characters ({} too many)", max, len - max)
+            }
+        }
+    }
+}
+
+/// Checks a rating (0 means none picked, at most `MAX_RATING`) and text before submission.
+pub fn validate(rating: f32, text: &str) -> Result<(), ReviewValidationError> {
+    if rating <= 0.0 {
+        return Err(ReviewValidationError::MissingRating);
+    }
+    if rating > MAX_RATING || rating.is_nan() {
+        return Err(ReviewValidationError::InvalidRating(rating));
+    }
+    let len = text.trim().chars().count();
+    if len < MIN_REVIEW_CHARS {
+        return Err(ReviewValidationError::TooShort { min: MIN_REVIEW_CHARS, len });
+    }
+    if len > MAX_REVIEW_CHARS {
+        return Err(ReviewValidationError::TooLong { max: MAX_REVIEW_CHARS, len });
+    }
+    Ok(())
+}
+
+/// Unsent review kept on disk so closing the composer (or the app) loses nothing.
+#[derive(Clone, Debug, Default, PartialEq, SerJson, DeJson)]
+pub struct ReviewDraft {
+    pub business_id: String,
+    pub rating: f32,
+    pub text: String,
+    /// Unix seconds of the last edit
+    pub updated: i64,
+}
+
+impl ReviewDraft {
+    pub fn is_empty(&self) -> bool {
+        self.rating <= 0.0 && self.text.trim().is_empty()
+    }
+}
+
+fn drafts_dir() -> PathBuf {
+    storage::data_dir().join("drafts")
+}
+
+fn draft_path(dir: &Path, business_id: &str) -> PathBuf {
+    dir.join(format!("{:016x}.json", LiveId::from_str(business_id).0))
+}
+
+pub fn load_draft(business_id: &str) -> Option<ReviewDraft> {
+    load_draft_in(&drafts_dir(), business_id)
+}
+
+/// Saves the draft, or removes it once it has been cleared.
+pub fn save_draft(draft: &ReviewDraft) {
+    save_draft_in(&drafts_dir(), draft)
+}
+
+pub fn delete_draft(business_id: &str) {
+    delete_draft_in(&drafts_dir(), business_id)
+}
+
+fn load_draft_in(dir: &Path, business_id: &str) -> Option<ReviewDraft> {
+    let json = std::fs::read_to_string(draft_path(dir, business_id)).ok()?;
+    ReviewDraft::deserialize_json(&json)
+        .ok()
+        .filter(|draft| draft.business_id == business_id)
+}
+
+fn save_draft_in(dir: &Path, draft: &ReviewDraft) {
+    if draft.is_empty() {
+        delete_draft_in(dir, &draft.business_id);
+        return;
+    }
+    if let Err(err) = storage::write_atomic(&draft_path(dir, &draft.business_id), draft.serialize_json().as_bytes()) {
+        log!("Could not save review draft for {}: {}", draft.business_id, err);
+    }
+}
+
+fn delete_draft_in(dir: &Path, business_id: &str) {
+    let _ = std::fs::remove_file(draft_path(dir, business_id));
+}
+
+// =====================
+// Sample Reviews
+// =====================
+
+const SAMPLE_AUTHORS: [&str; 8] = ["Maya R.", "Jordan K.", "Priya S.", "Tom W.", "Alex C.", "Sam L.", "Dana P.", "Chris M."];
+
+const SAMPLE_REVIEWS: [(f32, &str); 8] = [
+    (5.0, "Absolutely worth the wait. Everything we ordered was thoughtfully prepared and the staff walked us through the menu without rushing us. The dessert alone would bring me back, and the room has a warm, lively buzz that makes it feel like a neighborhood spot even on a busy Saturday night."),
+    (4.0, "Great food and friendly service. It gets loud at peak hours, so come early if you want a conversation."),
+    (5.0, "One of my favorite places in the city. Consistent, generous portions and fair prices for the quality."),
+    (3.0, "The food was good but not quite what the hype had me expecting. We waited almost forty minutes for a table even with a reservation, and a couple of dishes arrived lukewarm. Service recovered nicely once we mentioned it, and the manager comped a drink, so I would give it another try on a weeknight."),
+    (4.0, "Solid choice for a group dinner. Share a few plates and save room for dessert."),
+    (2.0, "Disappointing visit this time. Portions seemed smaller than before and the room was cold."),
+    (5.0, "Came here for a birthday and they made it special. Attentive staff, beautiful plating, and a wine list with plenty of options by the glass. Parking nearby is tough, so plan on transit or a rideshare."),
+    (4.0, "Reliable and tasty. Takeout travels well too."),
+];
+
+/// Deterministic sample reviews for the built-in providers.
+pub fn sample_reviews(business_id: &str, count: usize) -> Vec<Review> {
+    let seed = LiveId::from_str(business_id).0 as usize;
+    // Newest review in the second half of September 2026, older ones a few days apart
+    let newest = hours::days_from_civil(2026, 9, 14 + (seed % 14) as u32) * SECONDS_PER_DAY + 19 * 60 * 60;
+    (0..count)
+        .map(|index| {
+            let (rating, text) = SAMPLE_REVIEWS[(seed + index * 3) % SAMPLE_REVIEWS.len()];
+            Review {
+                id: format!("{}-review-{}", business_id, index + 1),
+                author: SAMPLE_AUTHORS[(seed / 7 + index) % SAMPLE_AUTHORS.len()].to_string(),
+                rating,
+                text: text.to_string(),
+                created: newest - (index as i64 * (3 + (seed % 5) as i64)) * SECONDS_PER_DAY,
+                photos: if index % 3 == 0 {
+                    (1..=2).map(|photo| format!("https://picsum.photos/seed/{}-r{}-{}/320/240", business_id, index + 1, photo)).collect()
+                } else {
+                    Vec::new()
+                },
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Empty directory unique to this test and process.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("makepad-yelp-drafts-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn draft(business_id: &str, rating: f32, text: &str) -> ReviewDraft {
+        ReviewDraft { business_id: business_id.to_string(), rating, text: text.to_string(), updated: 1_790_000_000 }
+    }
+
+    #[test]
+    fn validate_requires_a_rating_within_bounds() {
+        let text = "x".repeat(MIN_REVIEW_CHARS);
+        assert_eq!(validate(0.0, &text), Err(ReviewValidationError::MissingRating));
+        assert_eq!(validate(-1.0, &text), Err(ReviewValidationError::MissingRating));
+        assert_eq!(validate(5.5, &text), Err(ReviewValidationError::InvalidRating(5.5)));
+        assert!(matches!(validate(f32::NAN, &text), Err(ReviewValidationError::InvalidRating(_))));
+        assert_eq!(validate(0.5, &text), Ok(()));
+        assert_eq!(validate(MAX_RATING, &text), Ok(()));
+    }
+
+    #[test]
+    fn validate_checks_the_rating_before_the_text() {
+        assert_eq!(validate(0.0, ""), Err(ReviewValidationError::MissingRating));
+    }
+
+    #[test]
+    fn validate_enforces_min_and_max_length() {
+        let short = "x".repeat(MIN_REVIEW_CHARS - 1);
+        assert_eq!(
+            validate(4.0, &short),
+            Err(ReviewValidationError::TooShort { min: MIN_REVIEW_CHARS, len: MIN_REVIEW_CHARS - 1 })
+        );
+        assert_eq!(validate(4.0, &"x".repeat(MIN_REVIEW_CHARS)), Ok(()));
+        assert_eq!(validate(4.0, &"x".repeat(MAX_REVIEW_CHARS)), Ok(()));
+        assert_eq!(
+            validate(4.0, &"x".repeat(MAX_REVIEW_CHARS + 1)),
+            Err(ReviewValidationError::TooLong { max: MAX_REVIEW_CHARS, len: MAX_REVIEW_CHARS + 1 })
+        );
+    }
+
+    #[test]
+    fn validate_counts_trimmed_characters_not_bytes() {
+        let padded = format!("   {}\n\n", "x".repeat(MIN_REVIEW_CHARS - 1));
+        assert!(matches!(validate(3.0, &padded), Err(ReviewValidationError::TooShort { len, .. }) if len == MIN_REVIEW_CHARS - 1));
+        // Two bytes per character, but only the characters count
+        assert_eq!(validate(3.0, &"é".repeat(MIN_REVIEW_CHARS)), Ok(()));
+        assert_eq!(validate(3.0, &"é".repeat(MAX_REVIEW_CHARS)), Ok(()));
+    }
+
+    #[test]
+    fn validation_messages_report_what_is_left() {
+        let short = ReviewValidationError::TooShort { min: 50, len: 42 };
+        assert_eq!(short.to_string(), "Reviews need at least 50 characters (8 more to go)");
+        let long = ReviewValidationError::TooLong { max: 5000, len: 5003 };
+        assert_eq!(long.to_string(), "Reviews can be at most 5000 characters (3 too many)");
+    }
+
+    #[test]
+    fn drafts_round_trip_through_disk() {
+        let dir = temp_dir("round-trip");
+        let saved = draft("flour-water", 4.5, "Pasta was great, dessert even better.");
+        save_draft_in(&dir, &saved);
+        assert_eq!(load_draft_in(&dir, "flour-water"), Some(saved));
+        assert_eq!(load_draft_in(&dir, "tartine"), None);
+
+        let edited = draft("flour-water", 3.0, "Second visit was slower.");
+        save_draft_in(&dir, &edited);
+        assert_eq!(load_draft_in(&dir, "flour-water"), Some(edited));
+
+        delete_draft_in(&dir, "flour-water");
+        assert_eq!(load_draft_in(&dir, "flour-water"), None);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn saving_a_cleared_draft_removes_it() {
+        let dir = temp_dir("cleared");
+        save_draft_in(&dir, &draft("tartine", 5.0, "Morning bun."));
+        save_draft_in(&dir, &draft("tartine", 0.0, "   "));
+        assert_eq!(load_draft_in(&dir, "tartine"), None);
+        assert!(!draft_path(&dir, "tartine").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn drafts_for_another_business_are_ignored() {
+        let dir = temp_dir("mismatch");
+        let stray = draft("tartine", 4.0, "Written for a different place.");
+        storage::write_atomic(&draft_path(&dir, "flour-water"), stray.serialize_json().as_bytes()).unwrap();
+        assert_eq!(load_draft_in(&dir, "flour-water"), None);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn draft_is_empty_only_without_rating_and_text() {
+        assert!(draft("a", 0.0, "").is_empty());
+        assert!(draft("a", 0.0, " \n ").is_empty());
+        assert!(!draft("a", 1.0, "").is_empty());
+        assert!(!draft("a", 0.0, "Half written").is_empty());
+    }
+
+    #[test]
+    fn optimistic_copy_mirrors_the_new_review() {
+        let review = NewReview {
+            local_id: "local-1".to_string(),
+            business_id: "flour-water".to_string(),
+            author: "You".to_string(),
+            rating: 4.5,
+            text: "Loved the agnolotti.".to_string(),
+        };
+        let before = hours::now_unix();
+        let copy = review.optimistic();
+        assert_eq!(copy.id, "local-1");
+        assert_eq!(copy.author, "You");
+        assert_eq!(copy.rating, 4.5);
+        assert_eq!(copy.text, "Loved the agnolotti.");
+        assert!(copy.photos.is_empty());
+        assert!(copy.created >= before && copy.created <= hours::now_unix());
+    }
+}
@@ -0,0 +1,438 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use crate::geo::{self, Bounds, METERS_PER_MILE};
+use crate::url;
+
+// =====================
+// Routes
+// =====================
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TravelMode {
+    #[default]
+    Walk,
+    Drive,
+    Transit,
+}
+
+impl TravelMode {
+    pub const ALL: [TravelMode; 3] = [TravelMode::Walk, TravelMode::Drive, TravelMode::Transit];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TravelMode::Walk => "Walk",
+            TravelMode::Drive => "Drive",
+            TravelMode::Transit => "Transit",
+        }
+    }
+
+    /// Typical city speed, stops and lights included.
+    pub fn meters_per_second(&self) -> f64 {
+        match self {
+            TravelMode::Walk => 1.4,
+            TravelMode::Drive => 8.0,
+            TravelMode::Transit => 5.5,
+        }
+    }
+
+    /// Fixed time on top of moving: parking for a car, waiting for transit.
+    pub fn overhead_secs(&self) -> f64 {
+        match self {
+            TravelMode::Walk => 0.0,
+            TravelMode::Drive => 120.0,
+            TravelMode::Transit => 300.0,
+        }
+    }
+}
+
+/// A path from origin to destination with its length and travel time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Route {
+    pub mode: TravelMode,
+    /// Latitude/longitude pairs from origin to destination
+    pub points: Vec<(f64, f64)>,
+    pub distance_meters: f64,
+    pub duration_secs: f64,
+}
+
+impl Route {
+    /// Measures `points` and estimates the travel time for `mode`.
+    pub fn along(mode: TravelMode, points: Vec<(f64, f64)>) -> Self {
+        let distance_meters = points
+            .windows(2)
+            .map(|pair| geo::haversine_meters(pair[0].0, pair[0].1, pair[1].0, pair[1].1))
+            .sum::<f64>();
+        let duration_secs = distance_meters / mode.meters_per_second() + mode.overhead_secs();
+        Self { mode, points, distance_meters, duration_secs }
+    }
+
+    /// "0.8 mi"
+    pub fn distance_text(&self) -> String {
+        format!("{:.1} mi", self.distance_meters / METERS_PER_MILE)
+    }
+
+    /// "12 min", "1 hr 5 min"
+    pub fn eta_text(&self) -> String {
+        let minutes = (self.duration_secs / 60.0).ceil().max(1.0) as u64;
+        match (minutes / 60, minutes % 60) {
+            (0, minutes) => format!("{} min", minutes),
+            (hours, 0) => format!("{} hr", hours),
+            (hours, minutes) => format!("{} hr {} min", hours, minutes),
+        }
+    }
+}
+
+/// Finds a route between two latitude/longitude points.
+pub trait Router {
+    fn route(&self, from: (f64, f64), to: (f64, f64), mode: TravelMode) -> Result<Route, String>;
+}
+
+// =====================
+// External Navigation
+// =====================
+
+/// Navigation apps directions can be handed off to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExternalMap {
+    /// Whatever app handles `geo:` URIs (Android and most Linux phones)
+    Geo,
+    Google,
+    Apple,
+}
+
+impl ExternalMap {
+    pub const ALL: [ExternalMap; 3] = [ExternalMap::Geo, ExternalMap::Google, ExternalMap::Apple];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExternalMap::Geo => "Maps app",
+            ExternalMap::Google => "Google Maps",
+            ExternalMap::Apple => "Apple Maps",
+        }
+    }
+
+    /// Directions to `lat`/`lng`, labelled `name`, in `mode` where the app takes one.
+    pub fn directions_url(&self, lat: f64, lng: f64, name: &str, mode: TravelMode) -> String {
+        let coordinates = format!("{:.6},{:.6}", lat, lng);
+        match self {
+            // geo: has no travel mode; the label goes in parentheses after the query point
+            ExternalMap::Geo => format!("geo:{}?q={}({})", coordinates, coordinates, url::percent_encode(name)),
+            ExternalMap::Google => {
+                let travel_mode = match mode {
+                    TravelMode::Walk => "walking",
+                    TravelMode::Drive => "driving",
+                    TravelMode::Transit => "transit",
+                };
+                format!("https://www.google.com/maps/dir/?{}", url::query_string(&[
+                    ("api", "1".to_string()),
+                    ("destination", coordinates),
+                    ("travelmode", travel_mode.to_string()),
+                ]))
+            }
+            ExternalMap::Apple => {
+                let flag = match mode {
+                    TravelMode::Walk => "w",
+                    TravelMode::Drive => "d",
+                    TravelMode::Transit => "r",
+                };
+                format!("https://maps.apple.com/?{}", url::query_string(&[
+                    ("daddr", coordinates),
+                    ("q", name.to_string()),
+                    ("dirflg", flag.to_string()),
+                ]))
+            }
+        }
+    }
+}
+
+// =====================
+// Straight Line
+// =====================
+
+/// Goes directly from origin to destination; the fallback when nothing better is known.
+pub struct StraightLineRouter;
+
+impl Router for StraightLineRouter {
+    fn route(&self, from: (f64, f64), to: (f64, f64), mode: TravelMode) -> Result<Route, String> {
+        Ok(Route::along(mode, vec![from, to]))
+    }
+}
+
+// =====================
+// Offline Street Graph
+// =====================
+
+/// Shortest paths over an in-memory street graph, so routing works without a network.
+///
+/// Origin and destination snap to their nearest nodes; the route starts and
+/// ends at the exact points.
+#[derive(Default)]
+pub struct GraphRouter {
+    bounds: Option<Bounds>,
+    nodes: Vec<(f64, f64)>,
+    /// Neighbors of each node with the edge length in meters
+    edges: Vec<Vec<(usize, f64)>>,
+}
+
+impl GraphRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Streets every `spacing_meters` in both directions across `bounds`.
+    pub fn street_grid(bounds: Bounds, spacing_meters: f64) -> Self {
+        let height = geo::haversine_meters(bounds.south, bounds.west, bounds.north, bounds.west);
+        let width = geo::haversine_meters(bounds.south, bounds.west, bounds.south, bounds.east);
+        let rows = ((height / spacing_meters).ceil() as usize).max(1) + 1;
+        let cols = ((width / spacing_meters).ceil() as usize).max(1) + 1;
+        let mut router = Self::new();
+        for row in 0..rows {
+            for col in 0..cols {
+                let lat = bounds.south + (bounds.north - bounds.south) * row as f64 / (rows - 1) as f64;
+                let lng = bounds.west + (bounds.east - bounds.west) * col as f64 / (cols - 1) as f64;
+                let node = router.add_node(lat, lng);
+                if col > 0 { router.add_edge(node - 1, node); }
+                if row > 0 { router.add_edge(node - cols, node); }
+            }
+        }
+        router.bounds = Some(bounds);
+        router
+    }
+
+    pub fn add_node(&mut self, lat: f64, lng: f64) -> usize {
+        self.nodes.push((lat, lng));
+        self.edges.push(Vec::new());
+        self.nodes.len() - 1
+    }
+
+    /// Two-way street between nodes `a` and `b`.
+    pub fn add_edge(&mut self, a: usize, b: usize) {
+        let ((lat_a, lng_a), (lat_b, lng_b)) = (self.nodes[a], self.nodes[b]);
+        let length = geo::haversine_meters(lat_a, lng_a, lat_b, lng_b);
+        self.edges[a].push((b, length));
+        self.edges[b].push((a, length));
+    }
+
+    fn nearest_node(&self, (lat, lng): (f64, f64)) -> Option<usize> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (index, geo::haversine_meters(lat, lng, node.0, node.1)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(index, _)| index)
+    }
+
+    /// Dijkstra from `start` to `goal`, returning the nodes along the way.
+    fn shortest_path(&self, start: usize, goal: usize) -> Option<Vec<usize>> {
+        let mut best = vec![f64::INFINITY; self.nodes.len()];
+        let mut previous = vec![None; self.nodes.len()];
+        let mut queue = BinaryHeap::new();
+        best[start] = 0.0;
+        queue.push(Visit { cost: 0.0, node: start });
+        while let Some(Visit { cost, node }) = queue.pop() {
+            if node == goal { break; }
+            if cost > best[node] { continue; }
+            for &(next, length) in &self.edges[node] {
+                let next_cost = cost + length;
+                if next_cost < best[next] {
+                    best[next] = next_cost;
+                    previous[next] = Some(node);
+                    queue.push(Visit { cost: next_cost, node: next });
+                }
+            }
+        }
+        if best[goal].is_infinite() {
+            return None;
+        }
+        let mut path = vec![goal];
+        while let Some(node) = previous[*path.last().unwrap()] {
+            path.push(node);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl Router for GraphRouter {
+    fn route(&self, from: (f64, f64), to: (f64, f64), mode: TravelMode) -> Result<Route, String> {
+        if let Some(bounds) = &self.bounds {
+            if !bounds.contains(from.0, from.1) || !bounds.contains(to.0, to.1) {
+                return Err("outside the street graph".to_string());
+            }
+        }
+        let start = self.nearest_node(from).ok_or("empty street graph")?;
+        let goal = self.nearest_node(to).ok_or("empty street graph")?;
+        let path = self.shortest_path(start, goal).ok_or("no path between the points")?;
+        let points = std::iter::once(from)
+            .chain(path.into_iter().map(|node| self.nodes[node]))
+            .chain(std::iter::once(to))
+            .collect();
+        Ok(Route::along(mode, points))
+    }
+}
+
+/// Queue entry ordered so the cheapest visit pops first from the max-heap.
+struct Visit {
+    cost: f64,
+    node: usize,
+}
+
+impl PartialEq for Visit {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost && self.node == other.node
+    }
+}
+
+impl Eq for Visit {}
+
+impl PartialOrd for Visit {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Visit {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost).then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A few hundred meters of Mission District streets.
+    fn bounds() -> Bounds {
+        Bounds { south: 37.755, west: -122.425, north: 37.765, east: -122.41 }
+    }
+
+    fn route_secs(duration_secs: f64) -> Route {
+        Route { mode: TravelMode::Walk, points: Vec::new(), distance_meters: 0.0, duration_secs }
+    }
+
+    #[test]
+    fn along_measures_and_times_the_points() {
+        let points = vec![(37.76, -122.42), (37.77, -122.42)];
+        let length = geo::haversine_meters(37.76, -122.42, 37.77, -122.42);
+        let walk = Route::along(TravelMode::Walk, points.clone());
+        assert!((walk.distance_meters - length).abs() < 1e-6);
+        assert!((walk.duration_secs - length / 1.4).abs() < 1e-6);
+        // Driving pays for parking on top of moving
+        let drive = Route::along(TravelMode::Drive, points);
+        assert!((drive.duration_secs - (length / 8.0 + 120.0)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn eta_text_rounds_up_to_whole_minutes() {
+        assert_eq!(route_secs(0.0).eta_text(), "1 min");
+        assert_eq!(route_secs(30.0).eta_text(), "1 min");
+        assert_eq!(route_secs(600.0).eta_text(), "10 min");
+        assert_eq!(route_secs(601.0).eta_text(), "11 min");
+        assert_eq!(route_secs(3_600.0).eta_text(), "1 hr");
+        assert_eq!(route_secs(3_900.0).eta_text(), "1 hr 5 min");
+        assert_eq!(route_secs(7_260.0).eta_text(), "2 hr 1 min");
+    }
+
+    #[test]
+    fn distance_text_is_in_miles() {
+        let route = Route { distance_meters: METERS_PER_MILE * 0.8, ..route_secs(0.0) };
+        assert_eq!(route.distance_text(), "0.8 mi");
+    }
+
+    #[test]
+    fn straight_line_goes_directly() {
+        let route = StraightLineRouter.route((37.76, -122.42), (37.77, -122.41), TravelMode::Transit).unwrap();
+        assert_eq!(route.points, vec![(37.76, -122.42), (37.77, -122.41)]);
+        assert_eq!(route.mode, TravelMode::Transit);
+    }
+
+    #[test]
+    fn geo_directions_carry_the_label() {
+        let url = ExternalMap::Geo.directions_url(37.7614, -122.4241, "Tartine & Co", TravelMode::Drive);
+        assert_eq!(url, "geo:37.761400,-122.424100?q=37.761400,-122.424100(Tartine%20%26%20Co)");
+    }
+
+    #[test]
+    fn google_directions_use_the_travel_mode() {
+        let url = ExternalMap::Google.directions_url(37.7614, -122.4241, "Tartine", TravelMode::Walk);
+        assert_eq!(url, "https://www.google.com/maps/dir/?api=1&destination=37.761400%2C-122.424100&travelmode=walking");
+        let url = ExternalMap::Google.directions_url(37.7614, -122.4241, "Tartine", TravelMode::Transit);
+        assert!(url.ends_with("&travelmode=transit"), "{}", url);
+    }
+
+    #[test]
+    fn apple_directions_use_the_travel_flag() {
+        let url = ExternalMap::Apple.directions_url(37.7614, -122.4241, "Tartine Bakery", TravelMode::Drive);
+        assert_eq!(url, "https://maps.apple.com/?daddr=37.761400%2C-122.424100&q=Tartine%20Bakery&dirflg=d");
+        let url = ExternalMap::Apple.directions_url(37.7614, -122.4241, "Tartine Bakery", TravelMode::Transit);
+        assert!(url.ends_with("&dirflg=r"), "{}", url);
+    }
+
+    #[test]
+    fn graph_router_takes_the_shortest_path() {
+        let mut router = GraphRouter::new();
+        let corner = router.add_node(37.760, -122.420);
+        let east = router.add_node(37.760, -122.415);
+        let far_corner = router.add_node(37.765, -122.415);
+        let detour = router.add_node(37.800, -122.500);
+        router.add_edge(corner, east);
+        router.add_edge(east, far_corner);
+        router.add_edge(corner, detour);
+        router.add_edge(detour, far_corner);
+
+        let route = router.route((37.760, -122.420), (37.765, -122.415), TravelMode::Walk).unwrap();
+        // Origin, the three nodes along the streets, destination
+        assert_eq!(route.points, vec![
+            (37.760, -122.420),
+            (37.760, -122.420),
+            (37.760, -122.415),
+            (37.765, -122.415),
+            (37.765, -122.415),
+        ]);
+        let streets = geo::haversine_meters(37.760, -122.420, 37.760, -122.415)
+            + geo::haversine_meters(37.760, -122.415, 37.765, -122.415);
+        assert!((route.distance_meters - streets).abs() < 1e-6);
+    }
+
+    #[test]
+    fn graph_router_snaps_to_the_nearest_nodes() {
+        let mut router = GraphRouter::new();
+        let a = router.add_node(37.760, -122.420);
+        let b = router.add_node(37.760, -122.415);
+        router.add_edge(a, b);
+        let route = router.route((37.7601, -122.4199), (37.7599, -122.4151), TravelMode::Walk).unwrap();
+        // Starts and ends at the exact points, through the snapped nodes
+        assert_eq!(route.points.first(), Some(&(37.7601, -122.4199)));
+        assert_eq!(route.points[1..3], [(37.760, -122.420), (37.760, -122.415)]);
+        assert_eq!(route.points.last(), Some(&(37.7599, -122.4151)));
+    }
+
+    #[test]
+    fn graph_router_reports_missing_paths() {
+        assert!(GraphRouter::new().route((37.76, -122.42), (37.77, -122.41), TravelMode::Walk).is_err());
+        let mut router = GraphRouter::new();
+        router.add_node(37.760, -122.420);
+        router.add_node(37.770, -122.410); // No street between them
+        let result = router.route((37.760, -122.420), (37.770, -122.410), TravelMode::Walk);
+        assert_eq!(result, Err("no path between the points".to_string()));
+    }
+
+    #[test]
+    fn street_grid_routes_around_blocks() {
+        let router = GraphRouter::street_grid(bounds(), 150.0);
+        let (from, to) = ((37.755, -122.425), (37.765, -122.41));
+        let route = router.route(from, to, TravelMode::Walk).unwrap();
+        // Corner to corner along the grid is the width plus the height
+        let blocks = geo::haversine_meters(37.755, -122.425, 37.765, -122.425)
+            + geo::haversine_meters(37.755, -122.425, 37.755, -122.41);
+        assert!((route.distance_meters - blocks).abs() < blocks * 0.01, "{} vs {}", route.distance_meters, blocks);
+        assert!(route.distance_meters > geo::haversine_meters(from.0, from.1, to.0, to.1));
+    }
+
+    #[test]
+    fn street_grid_refuses_points_outside_it() {
+        let router = GraphRouter::street_grid(bounds(), 150.0);
+        let result = router.route((37.76, -122.42), (37.80, -122.42), TravelMode::Drive);
+        assert_eq!(result, Err("outside the street graph".to_string()));
+    }
+}
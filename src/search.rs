@@ -0,0 +1,574 @@
+use std::ops::Range;
+use crate::app::Business;
+use crate::geo::METERS_PER_MILE;
+
+// =====================
+// Query Matching
+// =====================
+
+/// Splits a raw query into lowercase, whitespace-separated terms.
+pub fn query_terms(query: &str) -> Vec<String> {
+    query
+        .split_whitespace()
+        .map(|term| term.to_lowercase())
+        .collect()
+}
+
+/// True when every query term matches the business name or one of its categories.
+/// An empty query matches everything.
+pub fn matches_query(business: &Business, query: &str) -> bool {
+    query_terms(query).iter().all(|term| {
+        !find_ignore_case(&business.name, term).is_empty()
+            || business
+                .categories
+                .iter()
+                .any(|category| !find_ignore_case(category, term).is_empty())
+    })
+}
+
+/// Returns the businesses matching `query`, preserving their order.
+pub fn filter_businesses(businesses: &[Business], query: &str) -> Vec<Business> {
+    businesses
+        .iter()
+        .filter(|business| matches_query(business, query))
+        .cloned()
+        .collect()
+}
+
+/// Byte ranges of `text` matched by any query term, sorted and merged.
+pub fn match_ranges(text: &str, query: &str) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = query_terms(query)
+        .iter()
+        .flat_map(|term| find_ignore_case(text, term))
+        .collect();
+    ranges.sort_by_key(|range| range.start);
+
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Builds Html markup for `text` with the given ranges wrapped in `<b>` tags.
+pub fn highlight_markup(text: &str, ranges: &[Range<usize>]) -> String {
+    let mut markup = String::with_capacity(text.len() + ranges.len() * 7);
+    let mut pos = 0;
+    for range in ranges {
+        markup.push_str(&escape_html(&text[pos..range.start]));
+        markup.push_str("<b>");
+        markup.push_str(&escape_html(&text[range.clone()]));
+        markup.push_str("</b>");
+        pos = range.end;
+    }
+    markup.push_str(&escape_html(&text[pos..]));
+    markup
+}
+
+/// All byte ranges where `term` (already lowercase) occurs in `text`, ignoring case.
+fn find_ignore_case(text: &str, term: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    if term.is_empty() {
+        return ranges;
+    }
+    for (start, _) in text.char_indices() {
+        let mut haystack = text[start..].char_indices();
+        let mut end = start;
+        let matched = term.chars().all(|tc| match haystack.next() {
+            Some((offset, hc)) if hc.to_lowercase().eq(tc.to_lowercase()) => {
+                end = start + offset + hc.len_utf8();
+                true
+            }
+            _ => false,
+        });
+        if matched {
+            ranges.push(start..end);
+        }
+    }
+    ranges
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+// =====================
+// Filters
+// =====================
+
+/// Minimum ratings offered in the filter sheet.
+pub const RATING_OPTIONS: [f32; 3] = [3.5, 4.0, 4.5];
+
+/// Distance limits offered in the filter sheet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DistanceLimit {
+    Walking,
+    Biking,
+    Driving,
+}
+
+impl DistanceLimit {
+    pub const ALL: [DistanceLimit; 3] = [DistanceLimit::Walking, DistanceLimit::Biking, DistanceLimit::Driving];
+
+    pub fn max_meters(self) -> f64 {
+        let miles = match self {
+            DistanceLimit::Walking => 1.0,
+            DistanceLimit::Biking => 2.0,
+            DistanceLimit::Driving => 5.0,
+        };
+        miles * METERS_PER_MILE
+    }
+}
+
+/// Result filters shared by the list and the map.
+///
+/// Each group narrows the results on its own, so groups combine with AND;
+/// several prices or categories inside a group combine with OR.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchFilters {
+    /// Accepted price levels, 1 ("$") to 4 ("$$$$"); empty accepts any price
+    pub prices: Vec<u8>,
+    pub min_rating: Option<f32>,
+    pub distance: Option<DistanceLimit>,
+    /// Accepted categories; empty accepts any category
+    pub categories: Vec<String>,
+    pub open_now: bool,
+}
+
+impl SearchFilters {
+    /// Number of filter groups in use, shown on the filter button badge.
+    pub fn active_count(&self) -> usize {
+        [
+            !self.prices.is_empty(),
+            self.min_rating.is_some(),
+            self.distance.is_some(),
+            !self.categories.is_empty(),
+            self.open_now,
+        ]
+        .iter()
+        .filter(|active| **active)
+        .count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.active_count() == 0
+    }
+
+    pub fn toggle_price(&mut self, level: u8) {
+        toggle(&mut self.prices, level);
+        self.prices.sort_unstable();
+    }
+
+    pub fn toggle_category(&mut self, category: &str) {
+        toggle(&mut self.categories, category.to_string());
+    }
+
+    /// True when `business` passes every active filter; `is_open` answers the open-now check.
+    pub fn matches(&self, business: &Business, is_open: impl Fn(&Business) -> bool) -> bool {
+        let price_ok = self.prices.is_empty()
+            || business.price.as_deref().and_then(price_level).is_some_and(|level| self.prices.contains(&level));
+        let rating_ok = self.min_rating.is_none_or(|min| business.rating >= min);
+        // Businesses with no known distance are kept rather than hidden
+        let distance_ok = match (self.distance, business.distance_meters) {
+            (Some(limit), Some(meters)) => meters <= limit.max_meters(),
+            _ => true,
+        };
+        let category_ok = self.categories.is_empty()
+            || business.categories.iter().any(|category| self.categories.contains(category));
+        price_ok && rating_ok && distance_ok && category_ok && (!self.open_now || is_open(business))
+    }
+
+    /// Businesses passing every active filter, in their original order.
+    pub fn apply(&self, businesses: &[Business], is_open: impl Fn(&Business) -> bool) -> Vec<Business> {
+        businesses
+            .iter()
+            .filter(|business| self.matches(business, &is_open))
+            .cloned()
+            .collect()
+    }
+}
+
+/// "$$" -> 2; None for anything that isn't one to four dollar signs.
+pub fn price_level(price: &str) -> Option<u8> {
+    let price = price.trim();
+    let level = price.len();
+    (1..=4).contains(&level).then_some(level as u8).filter(|_| price.chars().all(|c| c == '$'))
+}
+
+/// Categories found in `businesses`, most common first, then alphabetical.
+pub fn category_options(businesses: &[Business]) -> Vec<String> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for category in businesses.iter().flat_map(|business| &business.categories) {
+        match counts.iter_mut().find(|(name, _)| name == category) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((category.clone(), 1)),
+        }
+    }
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts.into_iter().map(|(name, _)| name).collect()
+}
+
+fn toggle<T: PartialEq>(values: &mut Vec<T>, value: T) {
+    if let Some(index) = values.iter().position(|existing| *existing == value) {
+        values.remove(index);
+    } else {
+        values.push(value);
+    }
+}
+
+// =====================
+// Sorting
+// =====================
+
+/// Mean rating a business is assumed to have before its reviews are counted.
+const PRIOR_RATING: f32 = 3.5;
+/// How many reviews the prior is worth; fewer real reviews pull a rating toward it.
+const PRIOR_REVIEWS: f32 = 25.0;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortMode {
+    /// Provider order, which is its relevance ranking
+    #[default]
+    BestMatch,
+    HighestRated,
+    MostReviewed,
+    Nearest,
+    PriceLowToHigh,
+    PriceHighToLow,
+}
+
+impl SortMode {
+    /// In the order shown in the sort selector.
+    pub const ALL: [SortMode; 6] = [
+        SortMode::BestMatch,
+        SortMode::HighestRated,
+        SortMode::MostReviewed,
+        SortMode::Nearest,
+        SortMode::PriceLowToHigh,
+        SortMode::PriceHighToLow,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortMode::BestMatch => "Best Match",
+            SortMode::HighestRated => "Highest Rated",
+            SortMode::MostReviewed => "Most Reviewed",
+            SortMode::Nearest => "Nearest",
+            SortMode::PriceLowToHigh => "Price: Low to High",
+            SortMode::PriceHighToLow => "Price: High to Low",
+        }
+    }
+}
+
+/// Rating shrunk toward `PRIOR_RATING`, so 4.9 from 8 reviews doesn't outrank 4.6 from 3,000.
+pub fn weighted_rating(business: &Business) -> f32 {
+    let count = business.review_count as f32;
+    (PRIOR_RATING * PRIOR_REVIEWS + business.rating * count) / (PRIOR_REVIEWS + count)
+}
+
+/// Sorts in place. `BestMatch` keeps the provider order; other modes break ties
+/// by id, so re-sorting the same results never moves a card.
+pub fn sort_businesses(businesses: &mut [Business], mode: SortMode) {
+    use std::cmp::Ordering;
+    // Businesses missing the sort key go last in either direction
+    fn missing_last<T>(a: Option<T>, b: Option<T>, cmp: impl Fn(T, T) -> Ordering) -> Ordering {
+        match (a, b) {
+            (Some(a), Some(b)) => cmp(a, b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+    let price = |business: &Business| business.price.as_deref().and_then(price_level);
+    businesses.sort_by(|a, b| {
+        let by_key = match mode {
+            SortMode::BestMatch => return Ordering::Equal,
+            SortMode::HighestRated => weighted_rating(b).total_cmp(&weighted_rating(a)),
+            SortMode::MostReviewed => b.review_count.cmp(&a.review_count),
+            SortMode::Nearest => missing_last(a.distance_meters, b.distance_meters, |a, b| a.total_cmp(&b)),
+            SortMode::PriceLowToHigh => missing_last(price(a), price(b), |a, b| a.cmp(&b)),
+            SortMode::PriceHighToLow => missing_last(price(a), price(b), |a, b| b.cmp(&a)),
+        };
+        by_key.then_with(|| a.id.cmp(&b.id))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn business(id: &str) -> Business {
+        Business {
+            id: id.to_string(),
+            name: format!("Business {}", id),
+            rating: 4.0,
+            review_count: 100,
+            price: None,
+            categories: Vec::new(),
+            city: "San Francisco".to_string(),
+            distance_meters: None,
+            lat: 37.76,
+            lng: -122.42,
+            image_url: None,
+            phone: None,
+            display_phone: None,
+            address: Vec::new(),
+            hours: Vec::new(),
+            photos: Vec::new(),
+            url: None,
+            special_hours: Vec::new(),
+            timezone: None,
+            is_closed: false,
+            description: None,
+        }
+    }
+
+    fn ids(businesses: &[Business]) -> Vec<&str> {
+        businesses.iter().map(|business| business.id.as_str()).collect()
+    }
+
+    fn sorted(mut businesses: Vec<Business>, mode: SortMode) -> Vec<Business> {
+        sort_businesses(&mut businesses, mode);
+        businesses
+    }
+
+    #[test]
+    fn query_matches_names_and_categories_ignoring_case() {
+        let flour = Business {
+            name: "Flour + Water".into(),
+            categories: vec!["Italian".into(), "Pizza".into()],
+            ..business("1")
+        };
+        assert!(matches_query(&flour, ""));
+        assert!(matches_query(&flour, "  WATER "));
+        assert!(matches_query(&flour, "pizza flour"));
+        assert!(!matches_query(&flour, "pizza tacos"));
+        let tartine = Business { name: "Tartine Bakery".into(), ..business("2") };
+        let pizzeria = Business { name: "Tony's Pizza".into(), ..business("3") };
+        let matches = filter_businesses(&[pizzeria, tartine, flour], "pizza");
+        assert_eq!(ids(&matches), ["3", "1"]);
+    }
+
+    #[test]
+    fn match_ranges_ignore_case() {
+        assert_eq!(match_ranges("Flour + Water", "WATER"), vec![8..13]);
+        assert_eq!(match_ranges("Flour + Water", "fl wa"), vec![0..2, 8..10]);
+        assert!(match_ranges("Flour + Water", "tacos").is_empty());
+        assert!(match_ranges("Flour + Water", "   ").is_empty());
+    }
+
+    #[test]
+    fn match_ranges_finds_every_occurrence_and_merges_overlaps() {
+        assert_eq!(match_ranges("Taco Taco", "taco"), vec![0..4, 5..9]);
+        // "ta" and "aco" overlap, so each word is one range
+        assert_eq!(match_ranges("Taco Taco", "aco ta"), vec![0..4, 5..9]);
+        // Touching ranges become one
+        assert_eq!(match_ranges("abc", "a b"), vec![0..2]);
+    }
+
+    #[test]
+    fn match_ranges_are_byte_ranges_on_char_boundaries() {
+        let text = "Café Ñandú";
+        assert_eq!(match_ranges(text, "CAFÉ"), vec![0..5]);
+        assert_eq!(match_ranges(text, "ñandú"), vec![6..13]);
+        assert_eq!(match_ranges(text, "é ñ"), vec![3..5, 6..8]);
+        for range in match_ranges(text, "a é ú") {
+            assert!(text.is_char_boundary(range.start) && text.is_char_boundary(range.end));
+        }
+        assert_eq!(match_ranges("ÉCOLE", "école"), vec![0..6]);
+    }
+
+    #[test]
+    fn highlight_markup_wraps_ranges() {
+        let text = "Flour + Water";
+        assert_eq!(highlight_markup(text, &match_ranges(text, "flour water")), "<b>Flour</b> + <b>Water</b>");
+        assert_eq!(highlight_markup(text, &[]), "Flour + Water");
+        let text = "Café Ñandú";
+        assert_eq!(highlight_markup(text, &match_ranges(text, "ñan")), "Café <b>Ñan</b>dú");
+    }
+
+    #[test]
+    fn highlight_markup_escapes_html() {
+        let text = "Tom & Jerry's <Diner>";
+        assert_eq!(
+            highlight_markup(text, &match_ranges(text, "jerry")),
+            "Tom &amp; <b>Jerry</b>'s &lt;Diner&gt;"
+        );
+        assert_eq!(highlight_markup(text, &match_ranges(text, "&")), "Tom <b>&amp;</b> Jerry's &lt;Diner&gt;");
+        assert_eq!(highlight_markup("<b>", &[]), "&lt;b&gt;");
+    }
+
+    fn restaurant(id: &str, price: &str, categories: &[&str], rating: f32) -> Business {
+        Business {
+            price: Some(price.to_string()),
+            categories: categories.iter().map(|category| category.to_string()).collect(),
+            rating,
+            ..business(id)
+        }
+    }
+
+    /// Open-now answer that treats the listed ids as open.
+    fn open_ids<'a>(open: &'a [&'a str]) -> impl Fn(&Business) -> bool + 'a {
+        move |business: &Business| open.contains(&business.id.as_str())
+    }
+
+    #[test]
+    fn price_level_counts_dollar_signs() {
+        assert_eq!(price_level("$"), Some(1));
+        assert_eq!(price_level(" $$$$ "), Some(4));
+        assert_eq!(price_level(""), None);
+        assert_eq!(price_level("$$$$$"), None);
+        assert_eq!(price_level("€€"), None);
+        assert_eq!(price_level("$-$$"), None);
+    }
+
+    #[test]
+    fn empty_filters_match_everything() {
+        let filters = SearchFilters::default();
+        assert!(filters.is_empty());
+        assert!(filters.matches(&business("unpriced"), |_| false));
+    }
+
+    #[test]
+    fn prices_and_categories_combine_with_or_inside_a_group() {
+        let mut filters = SearchFilters::default();
+        filters.toggle_price(3);
+        filters.toggle_price(1);
+        assert_eq!(filters.prices, vec![1, 3]);
+        assert!(filters.matches(&restaurant("a", "$", &["Tacos"], 4.0), |_| true));
+        assert!(filters.matches(&restaurant("b", "$$$", &["Italian"], 4.0), |_| true));
+        assert!(!filters.matches(&restaurant("c", "$$", &["Tacos"], 4.0), |_| true));
+        // A business without a price can't match a price filter
+        assert!(!filters.matches(&business("d"), |_| true));
+
+        let mut filters = SearchFilters::default();
+        filters.toggle_category("Pizza");
+        filters.toggle_category("Bakeries");
+        assert!(filters.matches(&restaurant("a", "$$", &["Italian", "Pizza"], 4.0), |_| true));
+        assert!(filters.matches(&restaurant("b", "$", &["Bakeries"], 4.0), |_| true));
+        assert!(!filters.matches(&restaurant("c", "$", &["Burmese"], 4.0), |_| true));
+        // Toggling again removes it
+        filters.toggle_category("Pizza");
+        assert_eq!(filters.categories, vec!["Bakeries"]);
+    }
+
+    #[test]
+    fn groups_combine_with_and() {
+        let filters = SearchFilters {
+            prices: vec![2],
+            min_rating: Some(4.0),
+            distance: None,
+            categories: vec!["Cafes".into()],
+            open_now: true,
+        };
+        assert_eq!(filters.active_count(), 4);
+        let is_open = open_ids(&["match", "low", "pricey", "other"]);
+        assert!(filters.matches(&restaurant("match", "$$", &["Cafes"], 4.0), &is_open));
+        assert!(!filters.matches(&restaurant("low", "$$", &["Cafes"], 3.5), &is_open));
+        assert!(!filters.matches(&restaurant("pricey", "$$$", &["Cafes"], 4.5), &is_open));
+        assert!(!filters.matches(&restaurant("other", "$$", &["Bakeries"], 4.5), &is_open));
+        assert!(!filters.matches(&restaurant("closed", "$$", &["Cafes"], 4.5), &is_open));
+    }
+
+    #[test]
+    fn distance_keeps_businesses_without_one() {
+        let filters = SearchFilters { distance: Some(DistanceLimit::Walking), ..SearchFilters::default() };
+        let near = Business { distance_meters: Some(METERS_PER_MILE * 0.9), ..business("near") };
+        let far = Business { distance_meters: Some(METERS_PER_MILE * 1.1), ..business("far") };
+        assert!(filters.matches(&near, |_| true));
+        assert!(!filters.matches(&far, |_| true));
+        assert!(filters.matches(&business("unknown"), |_| true));
+    }
+
+    #[test]
+    fn apply_keeps_the_input_order() {
+        let businesses = vec![
+            restaurant("z", "$", &["Tacos"], 4.5),
+            restaurant("m", "$$$$", &["Steak"], 4.5),
+            restaurant("a", "$", &["Tacos"], 3.0),
+            restaurant("k", "$", &["Tacos"], 4.0),
+        ];
+        let filters = SearchFilters { prices: vec![1], min_rating: Some(4.0), ..SearchFilters::default() };
+        assert_eq!(ids(&filters.apply(&businesses, |_| true)), ["z", "k"]);
+        let open_now = SearchFilters { open_now: true, ..SearchFilters::default() };
+        assert_eq!(ids(&open_now.apply(&businesses, open_ids(&["k", "m", "z"]))), ["z", "m", "k"]);
+    }
+
+    #[test]
+    fn category_options_are_most_common_first() {
+        let businesses = vec![
+            restaurant("a", "$", &["Tacos", "Mexican"], 4.0),
+            restaurant("b", "$", &["Mexican"], 4.0),
+            restaurant("c", "$", &["Bakeries"], 4.0),
+            restaurant("d", "$", &["Cafes", "Tacos"], 4.0),
+        ];
+        assert_eq!(category_options(&businesses), ["Mexican", "Tacos", "Bakeries", "Cafes"]);
+        assert!(category_options(&[]).is_empty());
+    }
+
+    #[test]
+    fn weighted_rating_without_reviews_is_the_prior() {
+        let unreviewed = Business { rating: 5.0, review_count: 0, ..business("a") };
+        assert_eq!(weighted_rating(&unreviewed), PRIOR_RATING);
+        let rated = Business { rating: 4.5, review_count: PRIOR_REVIEWS as u32, ..business("b") };
+        assert!((weighted_rating(&rated) - (PRIOR_RATING + 4.5) / 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn highest_rated_favors_many_reviews() {
+        let few = Business { rating: 4.9, review_count: 8, ..business("few") };
+        let many = Business { rating: 4.6, review_count: 3_000, ..business("many") };
+        assert_eq!(ids(&sorted(vec![few, many], SortMode::HighestRated)), ["many", "few"]);
+    }
+
+    #[test]
+    fn best_match_keeps_the_provider_order() {
+        let businesses = vec![
+            Business { rating: 3.0, ..business("c") },
+            Business { rating: 5.0, ..business("a") },
+            Business { rating: 4.0, ..business("b") },
+        ];
+        assert_eq!(ids(&sorted(businesses, SortMode::BestMatch)), ["c", "a", "b"]);
+    }
+
+    #[test]
+    fn ties_break_by_id() {
+        let businesses = vec![business("c"), business("a"), business("b")];
+        for mode in SortMode::ALL.into_iter().filter(|mode| *mode != SortMode::BestMatch) {
+            assert_eq!(ids(&sorted(businesses.clone(), mode)), ["a", "b", "c"], "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn sorting_is_stable_across_resorts() {
+        let businesses = vec![
+            Business { review_count: 50, ..business("d") },
+            Business { review_count: 900, ..business("b") },
+            Business { review_count: 50, ..business("a") },
+            Business { review_count: 900, ..business("c") },
+        ];
+        let once = sorted(businesses.clone(), SortMode::MostReviewed);
+        assert_eq!(ids(&once), ["b", "c", "a", "d"]);
+        let mut reversed = businesses;
+        reversed.reverse();
+        assert_eq!(ids(&sorted(reversed, SortMode::MostReviewed)), ids(&once));
+        assert_eq!(ids(&sorted(once.clone(), SortMode::MostReviewed)), ids(&once));
+    }
+
+    #[test]
+    fn missing_keys_sort_last_in_both_directions() {
+        let businesses = vec![
+            business("unpriced"),
+            Business { price: Some("$$$".into()), distance_meters: Some(900.0), ..business("pricey") },
+            Business { price: Some("$".into()), distance_meters: Some(200.0), ..business("cheap") },
+        ];
+        assert_eq!(ids(&sorted(businesses.clone(), SortMode::PriceLowToHigh)), ["cheap", "pricey", "unpriced"]);
+        assert_eq!(ids(&sorted(businesses.clone(), SortMode::PriceHighToLow)), ["pricey", "cheap", "unpriced"]);
+        assert_eq!(ids(&sorted(businesses, SortMode::Nearest)), ["cheap", "pricey", "unpriced"]);
+    }
+}
@@ -0,0 +1,52 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+// =====================
+// App Directories
+// =====================
+
+/// Overrides the cache directory (useful for tests and sandboxes).
+pub const CACHE_DIR_ENV: &str = "MAKEPAD_YELP_CACHE_DIR";
+/// Overrides the data directory.
+pub const DATA_DIR_ENV: &str = "MAKEPAD_YELP_DATA_DIR";
+
+const APP_DIR_NAME: &str = "makepad-yelp";
+
+/// Root for data that can be rebuilt from the network, such as downloaded photos.
+pub fn cache_dir() -> PathBuf {
+    if let Ok(dir) = std::env::var(CACHE_DIR_ENV) {
+        return PathBuf::from(dir);
+    }
+    if let Ok(dir) = std::env::var("XDG_CACHE_HOME") {
+        return PathBuf::from(dir).join(APP_DIR_NAME);
+    }
+    if let Ok(home) = std::env::var("HOME") {
+        return PathBuf::from(home).join(".cache").join(APP_DIR_NAME);
+    }
+    std::env::temp_dir().join(APP_DIR_NAME)
+}
+
+/// Root for data the user created and would lose if deleted, such as review drafts.
+pub fn data_dir() -> PathBuf {
+    if let Ok(dir) = std::env::var(DATA_DIR_ENV) {
+        return PathBuf::from(dir);
+    }
+    if let Ok(dir) = std::env::var("XDG_DATA_HOME") {
+        return PathBuf::from(dir).join(APP_DIR_NAME);
+    }
+    if let Ok(home) = std::env::var("HOME") {
+        return PathBuf::from(home).join(".local").join("share").join(APP_DIR_NAME);
+    }
+    std::env::temp_dir().join(APP_DIR_NAME).join("data")
+}
+
+/// Writes `bytes` to a sibling temp file and renames it into place,
+/// so a crash never leaves a half-written file behind.
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, bytes)?;
+    std::fs::rename(&tmp, path)
+}
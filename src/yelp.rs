@@ -0,0 +1,761 @@
+use makepad_widgets::*;
+use makepad_widgets::makepad_micro_serde::*;
+use std::collections::HashMap;
+use crate::app::Business;
+use crate::hours::{self, OpenInterval, SpecialHours};
+use crate::provider::{self, AreaRequest, BusinessProvider, ProviderResponse, SearchRequest, SEARCH_RADIUS_METERS};
+use crate::reviews::{NewReview, Review, ReviewPage, ReviewSort, ReviewsRequest};
+use crate::url;
+
+pub const YELP_API_URL: &str = "https://api.yelp.com/v3";
+/// Bearer token for the Fusion API; enables `YelpProvider` when set.
+pub const API_KEY_ENV: &str = "YELP_API_KEY";
+/// Overrides `YELP_API_URL`, e.g. to point at the bundled `yelp_stub` server.
+pub const API_URL_ENV: &str = "YELP_API_URL";
+
+/// Fusion caps `limit` at 50 and `offset + limit` at 240.
+pub const MAX_LIMIT: usize = 50;
+pub const MAX_RESULTS: usize = 240;
+/// Fusion rejects radii above 40 km.
+pub const MAX_RADIUS_METERS: u32 = 40_000;
+
+const DEFAULT_LOCATION: &str = "San Francisco, CA";
+
+// =====================
+// Fusion JSON Types
+// =====================
+
+#[derive(Clone, Debug, Default, SerJson, DeJson)]
+pub struct YelpSearchResponse {
+    pub businesses: Vec<YelpBusiness>,
+    pub total: u64,
+}
+
+#[derive(Clone, Debug, Default, SerJson, DeJson)]
+pub struct YelpBusiness {
+    pub id: String,
+    pub name: String,
+    pub image_url: Option<String>,
+    pub url: Option<String>,
+    pub review_count: u32,
+    pub categories: Vec<YelpCategory>,
+    pub rating: f32,
+    pub coordinates: YelpCoordinates,
+    pub price: Option<String>,
+    pub location: YelpLocation,
+    pub phone: Option<String>,
+    pub display_phone: Option<String>,
+    pub distance: Option<f64>,
+    pub is_closed: Option<bool>,
+    /// Only returned by the business details endpoint
+    pub hours: Option<Vec<YelpHours>>,
+    pub special_hours: Option<Vec<YelpSpecialHours>>,
+    pub photos: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, Default, SerJson, DeJson)]
+pub struct YelpHours {
+    pub open: Vec<YelpOpen>,
+    pub hours_type: Option<String>,
+    pub is_open_now: Option<bool>,
+}
+
+/// Holiday or one-off hours for a single `YYYY-MM-DD` date.
+#[derive(Clone, Debug, Default, SerJson, DeJson)]
+pub struct YelpSpecialHours {
+    pub date: String,
+    pub is_closed: Option<bool>,
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub is_overnight: Option<bool>,
+}
+
+/// `day` 0 is Monday; `start` / `end` are "HHMM" strings.
+#[derive(Clone, Debug, Default, SerJson, DeJson)]
+pub struct YelpOpen {
+    pub is_overnight: bool,
+    pub start: String,
+    pub end: String,
+    pub day: u8,
+}
+
+#[derive(Clone, Debug, Default, SerJson, DeJson)]
+pub struct YelpCategory {
+    pub alias: String,
+    pub title: String,
+}
+
+#[derive(Clone, Debug, Default, SerJson, DeJson)]
+pub struct YelpCoordinates {
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+#[derive(Clone, Debug, Default, SerJson, DeJson)]
+pub struct YelpLocation {
+    pub address1: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub zip_code: Option<String>,
+    pub display_address: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, Default, SerJson, DeJson)]
+pub struct YelpReviewsResponse {
+    pub reviews: Vec<YelpReview>,
+    pub total: u64,
+}
+
+#[derive(Clone, Debug, Default, SerJson, DeJson)]
+pub struct YelpReview {
+    pub id: String,
+    pub text: String,
+    pub rating: f32,
+    /// "YYYY-MM-DD HH:MM:SS"
+    pub time_created: String,
+    pub user: YelpUser,
+}
+
+#[derive(Clone, Debug, Default, SerJson, DeJson)]
+pub struct YelpUser {
+    pub name: String,
+    pub image_url: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, SerJson, DeJson)]
+pub struct YelpErrorResponse {
+    pub error: YelpErrorBody,
+}
+
+#[derive(Clone, Debug, Default, SerJson, DeJson)]
+pub struct YelpErrorBody {
+    pub code: String,
+    pub description: String,
+}
+
+impl From<YelpBusiness> for Business {
+    fn from(yelp: YelpBusiness) -> Self {
+        Business {
+            id: yelp.id,
+            name: yelp.name,
+            rating: yelp.rating,
+            review_count: yelp.review_count,
+            price: yelp.price,
+            categories: yelp.categories.into_iter().map(|category| category.title).collect(),
+            city: yelp.location.city.unwrap_or_default(),
+            distance_meters: yelp.distance,
+            lat: yelp.coordinates.latitude.unwrap_or(0.0),
+            lng: yelp.coordinates.longitude.unwrap_or(0.0),
+            image_url: yelp.image_url.filter(|url| !url.is_empty()),
+            phone: yelp.phone.filter(|phone| !phone.is_empty()),
+            display_phone: yelp.display_phone.filter(|phone| !phone.is_empty()),
+            address: yelp.location.display_address.unwrap_or_default(),
+            hours: yelp.hours
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|hours| hours.hours_type.as_deref().is_none_or(|kind| kind == "REGULAR"))
+                .flat_map(|hours| hours.open)
+                .filter_map(|open| {
+                    Some(OpenInterval {
+                        day: open.day,
+                        start: open.start.parse().ok()?,
+                        end: open.end.parse().ok()?,
+                        is_overnight: open.is_overnight,
+                    })
+                })
+                .collect(),
+            special_hours: yelp.special_hours
+                .unwrap_or_default()
+                .into_iter()
+                .map(|special| SpecialHours {
+                    date: special.date,
+                    is_closed: special.is_closed.unwrap_or(false),
+                    start: special.start.and_then(|start| start.parse().ok()),
+                    end: special.end.and_then(|end| end.parse().ok()),
+                    is_overnight: special.is_overnight.unwrap_or(false),
+                })
+                .collect(),
+            timezone: None,
+            photos: yelp.photos.unwrap_or_default(),
+            url: yelp.url.filter(|url| !url.is_empty()),
+            is_closed: yelp.is_closed.unwrap_or(false),
+            description: None,
+        }
+    }
+}
+
+impl From<YelpReview> for Review {
+    fn from(yelp: YelpReview) -> Self {
+        Review {
+            id: yelp.id,
+            author: yelp.user.name,
+            rating: yelp.rating,
+            text: yelp.text,
+            created: parse_time_created(&yelp.time_created).unwrap_or(0),
+            // Fusion doesn't expose review photos
+            photos: Vec::new(),
+        }
+    }
+}
+
+/// Unix seconds for Fusion's "YYYY-MM-DD HH:MM:SS" timestamps.
+fn parse_time_created(text: &str) -> Option<i64> {
+    let (date, time) = text.split_once(' ').unwrap_or((text, "00:00:00"));
+    let mut date = date.split('-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+    let mut time = time.split(':').map(|part| part.parse::<i64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next().flatten().unwrap_or(0));
+    let days = hours::days_from_civil(year, month as u32, day as u32);
+    Some(days * 86_400 + hour * 3600 + minute * 60 + second)
+}
+
+// =====================
+// Errors
+// =====================
+
+#[derive(Clone, Debug)]
+pub enum YelpError {
+    /// 400: a parameter was rejected (e.g. limit above 50)
+    BadRequest(String),
+    /// 401/403: missing or invalid API key
+    Unauthorized(String),
+    /// 404: unknown business id
+    NotFound(String),
+    /// 429: daily or per-second quota exhausted
+    RateLimited,
+    /// 5xx or any other unexpected status
+    Server(u16),
+    /// The body did not match the Fusion schema
+    Parse(String),
+    /// The request never produced an HTTP response
+    Network(String),
+}
+
+impl YelpError {
+    /// Maps a non-200 status and its body (Fusion's `{"error": {...}}`) to an error.
+    pub fn from_status(status_code: u16, body: &str) -> Self {
+        let description = YelpErrorResponse::deserialize_json(body)
+            .map(|response| format!("{}: {}", response.error.code, response.error.description))
+            .unwrap_or_else(|_| body.to_string());
+        match status_code {
+            400 => YelpError::BadRequest(description),
+            401 | 403 => YelpError::Unauthorized(description),
+            404 => YelpError::NotFound(description),
+            429 => YelpError::RateLimited,
+            _ => YelpError::Server(status_code),
+        }
+    }
+}
+
+impl std::fmt::Display for YelpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            YelpError::BadRequest(description) => write!(f, "Bad request ({})", description),
+            YelpError::Unauthorized(description) => write!(f, "Unauthorized ({})", description),
+            YelpError::NotFound(description) => write!(f, "Not found ({})", description),
+            YelpError::RateLimited => write!(f, "Rate limited"),
+            YelpError::Server(status) => write!(f, "Server error (HTTP {})", status),
+            YelpError::Parse(message) => write!(f, "Unexpected response ({})", message),
+            YelpError::Network(message) => write!(f, "Network error ({})", message),
+        }
+    }
+}
+
+// =====================
+// Client
+// =====================
+
+/// Query parameters for `/businesses/search`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchParams {
+    pub term: String,
+    pub location: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub radius_meters: Option<u32>,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl SearchParams {
+    fn to_query(&self) -> String {
+        let mut params = vec![("term", self.term.clone())];
+        if let (Some(lat), Some(lng)) = (self.latitude, self.longitude) {
+            params.push(("latitude", lat.to_string()));
+            params.push(("longitude", lng.to_string()));
+        } else {
+            // Fusion requires either coordinates or a location
+            params.push(("location", self.location.clone().unwrap_or_else(|| DEFAULT_LOCATION.to_string())));
+        }
+        if let Some(radius) = self.radius_meters {
+            params.push(("radius", radius.min(MAX_RADIUS_METERS).to_string()));
+        }
+        let limit = self.limit.clamp(1, MAX_LIMIT);
+        params.push(("offset", self.offset.to_string()));
+        params.push(("limit", limit.to_string()));
+        url::query_string(&params)
+    }
+}
+
+/// One page of search results.
+#[derive(Clone, Debug)]
+pub struct SearchPage {
+    pub businesses: Vec<Business>,
+    pub offset: usize,
+    pub total: usize,
+}
+
+impl SearchPage {
+    /// Offset of the following page, or `None` once the results (or Fusion's 240 cap) are exhausted.
+    pub fn next_offset(&self) -> Option<usize> {
+        let next = self.offset + self.businesses.len();
+        let available = self.total.min(MAX_RESULTS);
+        (!self.businesses.is_empty() && next < available).then_some(next)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum YelpEvent {
+    Search { params: SearchParams, result: Result<SearchPage, YelpError> },
+    Business { id: String, result: Result<Business, YelpError> },
+    Reviews { request: ReviewsRequest, result: Result<ReviewPage, YelpError> },
+}
+
+enum PendingCall {
+    Search(SearchParams),
+    Business(String),
+    Reviews(ReviewsRequest),
+}
+
+/// Issues Fusion requests through `cx.http_request` and parses the responses.
+pub struct YelpClient {
+    base_url: String,
+    api_key: String,
+    pending: HashMap<LiveId, PendingCall>,
+}
+
+impl YelpClient {
+    pub fn new(base_url: &str, api_key: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Builds a client from `YELP_API_KEY` (required) and `YELP_API_URL` (optional).
+    pub fn from_env() -> Option<Self> {
+        let api_key = std::env::var(API_KEY_ENV).ok()?;
+        let base_url = std::env::var(API_URL_ENV).unwrap_or_else(|_| YELP_API_URL.to_string());
+        Some(Self::new(&base_url, &api_key))
+    }
+
+    pub fn search(&mut self, cx: &mut Cx, params: SearchParams) -> LiveId {
+        let path = format!("/businesses/search?{}", params.to_query());
+        self.send(cx, &path, PendingCall::Search(params))
+    }
+
+    pub fn business(&mut self, cx: &mut Cx, id: &str) -> LiveId {
+        let path = format!("/businesses/{}", url::percent_encode(id));
+        self.send(cx, &path, PendingCall::Business(id.to_string()))
+    }
+
+    /// Always newest first: Fusion can't sort by rating, and sorting each page
+    /// locally would interleave wrongly across pages.
+    pub fn reviews(&mut self, cx: &mut Cx, request: &ReviewsRequest) -> LiveId {
+        let query = url::query_string(&[
+            ("offset", request.offset.to_string()),
+            ("limit", request.limit.to_string()),
+            ("sort_by", "newest".to_string()),
+        ]);
+        let path = format!("/businesses/{}/reviews?{}", url::percent_encode(&request.business_id), query);
+        self.send(cx, &path, PendingCall::Reviews(request.clone()))
+    }
+
+    fn send(&mut self, cx: &mut Cx, path: &str, call: PendingCall) -> LiveId {
+        let request_id = LiveId::unique();
+        let mut request = HttpRequest::new(format!("{}{}", self.base_url, path), HttpMethod::GET);
+        request.set_header("Authorization".into(), format!("Bearer {}", self.api_key));
+        request.set_header("Accept".into(), "application/json".into());
+        cx.http_request(request_id, request);
+        self.pending.insert(request_id, call);
+        request_id
+    }
+
+    /// Returns `None` for responses that don't belong to this client.
+    pub fn handle_network_response(&mut self, item: &NetworkResponseItem) -> Option<YelpEvent> {
+        let body = match &item.response {
+            NetworkResponse::HttpResponse(response) => {
+                Ok((response.status_code, response.get_string_body().unwrap_or_default()))
+            }
+            NetworkResponse::HttpRequestError(err) => Err(YelpError::Network(format!("{:?}", err))),
+            // Progress and other interim events leave the call pending until its final response
+            _ => return None,
+        };
+        let call = self.pending.remove(&item.request_id)?;
+        Some(match call {
+            PendingCall::Search(params) => {
+                let result = body.and_then(|(status, body)| parse_search(status, &body, params.offset));
+                YelpEvent::Search { params, result }
+            }
+            PendingCall::Business(id) => {
+                let result = body.and_then(|(status, body)| parse_business(status, &body));
+                YelpEvent::Business { id, result }
+            }
+            PendingCall::Reviews(request) => {
+                let result = body.and_then(|(status, body)| parse_reviews(status, &body));
+                YelpEvent::Reviews { request, result }
+            }
+        })
+    }
+}
+
+pub fn parse_search(status_code: u16, body: &str, offset: usize) -> Result<SearchPage, YelpError> {
+    if status_code != 200 {
+        return Err(YelpError::from_status(status_code, body));
+    }
+    let response = YelpSearchResponse::deserialize_json(body)
+        .map_err(|err| YelpError::Parse(format!("{:?}", err)))?;
+    Ok(SearchPage {
+        businesses: response.businesses.into_iter().map(Business::from).collect(),
+        offset,
+        total: response.total as usize,
+    })
+}
+
+pub fn parse_business(status_code: u16, body: &str) -> Result<Business, YelpError> {
+    if status_code != 200 {
+        return Err(YelpError::from_status(status_code, body));
+    }
+    YelpBusiness::deserialize_json(body)
+        .map(Business::from)
+        .map_err(|err| YelpError::Parse(format!("{:?}", err)))
+}
+
+pub fn parse_reviews(status_code: u16, body: &str) -> Result<ReviewPage, YelpError> {
+    if status_code != 200 {
+        return Err(YelpError::from_status(status_code, body));
+    }
+    let response = YelpReviewsResponse::deserialize_json(body)
+        .map_err(|err| YelpError::Parse(format!("{:?}", err)))?;
+    let reviews = response.reviews.into_iter().map(Review::from).collect();
+    Ok(ReviewPage { reviews, total: response.total as usize })
+}
+
+// =====================
+// Provider
+// =====================
+
+enum PendingQuery {
+    Search(SearchRequest),
+    Nearby,
+    Area(AreaRequest),
+}
+
+/// `BusinessProvider` backed by the Fusion API (or the `yelp_stub` server).
+pub struct YelpProvider {
+    client: YelpClient,
+    queries: HashMap<LiveId, PendingQuery>,
+}
+
+impl YelpProvider {
+    pub fn new(client: YelpClient) -> Self {
+        Self { client, queries: HashMap::new() }
+    }
+}
+
+impl BusinessProvider for YelpProvider {
+    fn search(&mut self, cx: &mut Cx, request: &SearchRequest) -> Option<ProviderResponse> {
+        let params = SearchParams {
+            term: request.term.clone(),
+            latitude: request.center.map(|(lat, _)| lat),
+            longitude: request.center.map(|(_, lng)| lng),
+            radius_meters: request.center.map(|_| SEARCH_RADIUS_METERS as u32),
+            offset: request.offset,
+            limit: request.limit,
+            ..Default::default()
+        };
+        let request_id = self.client.search(cx, params);
+        self.queries.insert(request_id, PendingQuery::Search(request.clone()));
+        None
+    }
+
+    fn business(&mut self, cx: &mut Cx, id: &str) -> Option<ProviderResponse> {
+        self.client.business(cx, id);
+        None
+    }
+
+    fn nearby(&mut self, cx: &mut Cx, lat: f64, lng: f64, radius_meters: f64) -> Option<ProviderResponse> {
+        let params = SearchParams {
+            latitude: Some(lat),
+            longitude: Some(lng),
+            radius_meters: Some(radius_meters.round() as u32),
+            limit: MAX_LIMIT,
+            ..Default::default()
+        };
+        let request_id = self.client.search(cx, params);
+        self.queries.insert(request_id, PendingQuery::Nearby);
+        None
+    }
+
+    /// Fusion searches by radius, so the circle around the box is fetched and trimmed to it.
+    fn search_area(&mut self, cx: &mut Cx, request: &AreaRequest) -> Option<ProviderResponse> {
+        let (lat, lng) = request.bounds.center();
+        let params = SearchParams {
+            term: request.term.clone(),
+            latitude: Some(lat),
+            longitude: Some(lng),
+            radius_meters: Some(request.bounds.radius_meters().round() as u32),
+            limit: MAX_LIMIT,
+            ..Default::default()
+        };
+        let request_id = self.client.search(cx, params);
+        self.queries.insert(request_id, PendingQuery::Area(request.clone()));
+        None
+    }
+
+    fn reviews(&mut self, cx: &mut Cx, request: &ReviewsRequest) -> Option<ProviderResponse> {
+        self.client.reviews(cx, request);
+        None
+    }
+
+    /// Rating orders would only hold within each page.
+    fn review_sorts(&self) -> &'static [ReviewSort] {
+        &[ReviewSort::Newest]
+    }
+
+    /// Fusion is read-only, so submissions are rejected right away.
+    fn submit_review(&mut self, _cx: &mut Cx, review: &NewReview) -> Option<ProviderResponse> {
+        Some(ProviderResponse::ReviewSubmitted {
+            local_id: review.local_id.clone(),
+            business_id: review.business_id.clone(),
+            result: Err("Reviews can't be posted through the Yelp Fusion API".to_string()),
+        })
+    }
+
+    fn handle_network_response(&mut self, item: &NetworkResponseItem) -> Option<ProviderResponse> {
+        let event = self.client.handle_network_response(item)?;
+        Some(match event {
+            YelpEvent::Search { result, .. } => {
+                let query = self.queries.remove(&item.request_id)?;
+                match (query, result) {
+                    (PendingQuery::Search(request), Ok(page)) => {
+                        let has_more = page.next_offset().is_some();
+                        ProviderResponse::Search { request, businesses: page.businesses, has_more }
+                    }
+                    (PendingQuery::Search(request), Err(err)) => {
+                        ProviderResponse::SearchFailed { request, message: err.to_string() }
+                    }
+                    (PendingQuery::Nearby, Ok(page)) => ProviderResponse::Nearby(page.businesses),
+                    (PendingQuery::Area(request), Ok(page)) => provider::area_response(request, page.businesses),
+                    (PendingQuery::Nearby | PendingQuery::Area(_), Err(err)) => ProviderResponse::Error(err.to_string()),
+                }
+            }
+            YelpEvent::Business { result: Ok(business), .. } => ProviderResponse::Business(business),
+            YelpEvent::Business { id, result: Err(err) } => {
+                ProviderResponse::Error(format!("Business {}: {}", id, err))
+            }
+            YelpEvent::Reviews { request, result: Ok(page) } => ProviderResponse::Reviews { request, page },
+            YelpEvent::Reviews { request, result: Err(err) } => {
+                ProviderResponse::ReviewsFailed { request, message: err.to_string() }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEARCH_JSON: &str = include_str!("../resources/fixtures/yelp/search.json");
+    const REVIEWS_JSON: &str = include_str!("../resources/fixtures/yelp/reviews.json");
+
+    /// `reviews.json` groups reviews by business, as `yelp_stub` reads it.
+    #[derive(DeJson)]
+    struct ReviewFixture {
+        businesses: Vec<BusinessReviews>,
+    }
+
+    #[derive(DeJson)]
+    struct BusinessReviews {
+        business_id: String,
+        reviews: Vec<YelpReview>,
+    }
+
+    fn fixture_business(id: &str) -> YelpBusiness {
+        let fixture = YelpSearchResponse::deserialize_json(SEARCH_JSON).unwrap();
+        fixture.businesses.into_iter().find(|business| business.id == id).unwrap()
+    }
+
+    /// What `/businesses/{id}/reviews` returns for a fixture business.
+    fn reviews_body(id: &str) -> String {
+        let fixture = ReviewFixture::deserialize_json(REVIEWS_JSON).unwrap();
+        let reviews = fixture.businesses.into_iter().find(|entry| entry.business_id == id).unwrap().reviews;
+        YelpReviewsResponse { total: reviews.len() as u64, reviews }.serialize_json()
+    }
+
+    fn error_body(code: &str, description: &str) -> String {
+        YelpErrorResponse {
+            error: YelpErrorBody { code: code.to_string(), description: description.to_string() },
+        }
+        .serialize_json()
+    }
+
+    fn page(offset: usize, count: usize, total: usize) -> SearchPage {
+        let businesses = (0..count).map(|_| Business::from(YelpBusiness::default())).collect();
+        SearchPage { businesses, offset, total }
+    }
+
+    #[test]
+    fn parse_search_reads_the_fixture() {
+        let page = parse_search(200, SEARCH_JSON, 0).unwrap();
+        assert_eq!(page.businesses.len(), 14);
+        assert_eq!(page.total, 14);
+        let business = &page.businesses[0];
+        assert_eq!(business.id, "flour-water-san-francisco");
+        assert_eq!(business.name, "Flour + Water");
+        assert_eq!(business.categories, vec!["Italian", "Pizza"]);
+        assert_eq!(business.price.as_deref(), Some("$$$"));
+        assert_eq!((business.lat, business.lng), (37.7599, -122.4148));
+        assert_eq!(business.phone.as_deref(), Some("+14158267000"));
+        assert_eq!(business.distance_meters, Some(850.0));
+        assert_eq!(business.address, vec!["2401 Harrison St", "San Francisco, CA 94110"]);
+        assert_eq!(business.hours.len(), 7);
+        assert_eq!(business.hours[0], OpenInterval { day: 0, start: 1700, end: 2200, is_overnight: false });
+    }
+
+    #[test]
+    fn parse_search_keeps_the_requested_offset() {
+        let page = parse_search(200, SEARCH_JSON, 20).unwrap();
+        assert_eq!(page.offset, 20);
+    }
+
+    #[test]
+    fn parse_search_reports_bad_bodies_and_statuses() {
+        assert!(matches!(parse_search(200, "<html>", 0), Err(YelpError::Parse(_))));
+        let body = error_body("TOO_MANY_REQUESTS_PER_SECOND", "Slow down");
+        assert!(matches!(parse_search(429, &body, 0), Err(YelpError::RateLimited)));
+    }
+
+    #[test]
+    fn parse_business_reads_details() {
+        let body = fixture_business("tartine-bakery-san-francisco").serialize_json();
+        let business = parse_business(200, &body).unwrap();
+        assert_eq!(business.name, "Tartine Bakery");
+        assert_eq!(business.photos.len(), 3);
+        assert_eq!(business.special_hours, vec![SpecialHours {
+            date: "2026-12-25".to_string(),
+            is_closed: true,
+            start: None,
+            end: None,
+            is_overnight: false,
+        }]);
+    }
+
+    #[test]
+    fn parse_business_only_keeps_regular_hours() {
+        let mut yelp = fixture_business("tartine-bakery-san-francisco");
+        let mut happy_hour = yelp.hours.as_ref().unwrap()[0].clone();
+        happy_hour.hours_type = Some("HAPPY_HOUR".to_string());
+        yelp.hours.as_mut().unwrap().push(happy_hour);
+        let business = parse_business(200, &yelp.serialize_json()).unwrap();
+        assert_eq!(business.hours.len(), 7);
+    }
+
+    #[test]
+    fn parse_business_maps_unknown_ids() {
+        let body = error_body("BUSINESS_NOT_FOUND", "The requested business could not be found.");
+        match parse_business(404, &body) {
+            Err(YelpError::NotFound(description)) => {
+                assert_eq!(description, "BUSINESS_NOT_FOUND: The requested business could not be found.");
+            }
+            other => panic!("expected NotFound, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_reviews_reads_the_fixture() {
+        let page = parse_reviews(200, &reviews_body("flour-water-san-francisco")).unwrap();
+        assert_eq!(page.total, 5);
+        assert_eq!(page.reviews.len(), 5);
+        let review = &page.reviews[0];
+        assert_eq!(review.id, "flour-water-san-francisco-r1");
+        assert_eq!(review.author, "Maya R.");
+        assert_eq!(review.rating, 5.0);
+        // 2026-09-28 16:41:00 UTC
+        assert_eq!(review.created, 1_790_613_660);
+        assert!(review.photos.is_empty());
+    }
+
+    #[test]
+    fn from_status_maps_fusion_errors() {
+        let body = error_body("VALIDATION_ERROR", "limit+offset must be <= 240.");
+        match YelpError::from_status(400, &body) {
+            YelpError::BadRequest(description) => assert_eq!(description, "VALIDATION_ERROR: limit+offset must be <= 240."),
+            other => panic!("expected BadRequest, got {:?}", other),
+        }
+        let body = error_body("TOKEN_MISSING", "An access token must be supplied.");
+        assert!(matches!(YelpError::from_status(401, &body), YelpError::Unauthorized(_)));
+        assert!(matches!(YelpError::from_status(403, &body), YelpError::Unauthorized(_)));
+        assert!(matches!(YelpError::from_status(404, &body), YelpError::NotFound(_)));
+        assert!(matches!(YelpError::from_status(429, &body), YelpError::RateLimited));
+        assert!(matches!(YelpError::from_status(500, &body), YelpError::Server(500)));
+        assert!(matches!(YelpError::from_status(503, ""), YelpError::Server(503)));
+    }
+
+    #[test]
+    fn from_status_keeps_bodies_that_are_not_fusion_errors() {
+        match YelpError::from_status(401, "Unauthorized") {
+            YelpError::Unauthorized(description) => assert_eq!(description, "Unauthorized"),
+            other => panic!("expected Unauthorized, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn next_offset_follows_the_total() {
+        assert_eq!(page(0, 20, 100).next_offset(), Some(20));
+        assert_eq!(page(60, 20, 100).next_offset(), Some(80));
+        assert_eq!(page(80, 20, 100).next_offset(), None);
+        assert_eq!(page(0, 14, 14).next_offset(), None);
+        // An empty page ends paging even if the total says otherwise
+        assert_eq!(page(20, 0, 100).next_offset(), None);
+    }
+
+    #[test]
+    fn next_offset_stops_at_the_result_cap() {
+        assert_eq!(page(200, 20, 1_000).next_offset(), Some(220));
+        assert_eq!(page(220, 20, 1_000).next_offset(), None);
+        assert_eq!(page(190, 50, 1_000).next_offset(), None);
+    }
+
+    #[test]
+    fn to_query_encodes_the_term_and_location() {
+        let params = SearchParams {
+            term: "tacos & burritos".to_string(),
+            location: Some("Mission, SF".to_string()),
+            limit: 20,
+            ..SearchParams::default()
+        };
+        assert_eq!(params.to_query(), "term=tacos%20%26%20burritos&location=Mission%2C%20SF&offset=0&limit=20");
+    }
+
+    #[test]
+    fn to_query_prefers_coordinates_and_clamps_limits() {
+        let params = SearchParams {
+            term: "pizza".to_string(),
+            location: Some("ignored".to_string()),
+            latitude: Some(37.76),
+            longitude: Some(-122.42),
+            radius_meters: Some(50_000),
+            offset: 40,
+            limit: 100,
+        };
+        assert_eq!(
+            params.to_query(),
+            "term=pizza&latitude=37.76&longitude=-122.42&radius=40000&offset=40&limit=50"
+        );
+        // Without a location Fusion still needs one, and limit is at least 1
+        let params = SearchParams { term: String::new(), ..SearchParams::default() };
+        assert_eq!(params.to_query(), "term=&location=San%20Francisco%2C%20CA&offset=0&limit=1");
+    }
+}